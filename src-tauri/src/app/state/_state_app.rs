@@ -1,9 +1,13 @@
-use crate::app::event::UserAction;
+use crate::app::event::{StateChange, UserAction};
+use crate::app::state::analysis::{AnalysisSession, StateChangeSink};
 use crate::app::state::DynSession;
 use crate::app::{AeonApp, AeonError, DynError, AEON_VALUE};
+use crate::debug;
+use crate::sketchbook::data_structs::SketchData;
+use crate::sketchbook::{JsonSerde, Sketch};
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use tauri::{Manager, Window};
 
 /// [AppState] implements mapping between session IDs and session objects.
@@ -43,6 +47,49 @@ impl AppState {
         session_map.insert(id.to_string(), session.into());
     }
 
+    /// Create a new analysis session (with the given ID) working on a snapshot of the sketch
+    /// from the given editor session.
+    ///
+    /// The snapshot is taken right away, so that the main state is not locked during the
+    /// analysis. State changes produced by the analysis in the background are emitted directly
+    /// through the `app`.
+    pub fn analysis_session_created(
+        &self,
+        app: &AeonApp,
+        editor_session_id: &str,
+        analysis_session_id: &str,
+    ) -> Result<(), DynError> {
+        let snapshot = {
+            let guard = self.session_state.lock().unwrap_or_else(|_e| {
+                panic!("Main application state is poisoned. Cannot recover.");
+            });
+            let session_map = guard.deref();
+            if session_map.contains_key(analysis_session_id) {
+                let msg = format!("Session `{analysis_session_id}` already exists.");
+                return AeonError::throw(msg);
+            }
+            let Some(editor) = session_map.get(editor_session_id) else {
+                return AeonError::throw(format!("Unknown session id {editor_session_id}."));
+            };
+            let full_path = ["sketch".to_string(), "get_whole_sketch".to_string()];
+            editor.refresh(&full_path, &["sketch", "get_whole_sketch"])?
+        };
+        let Some(sketch_json) = snapshot.payload else {
+            return AeonError::throw("Sketch snapshot cannot be empty.");
+        };
+        let sketch = Sketch::new_from_sketch_data(&SketchData::from_json_str(&sketch_json)?)?;
+
+        let tauri = app.tauri.clone();
+        let sink: StateChangeSink = Arc::new(move |state_change: StateChange| {
+            if let Err(e) = tauri.emit_all(AEON_VALUE, state_change.events) {
+                debug!("Error sending analysis state event: `{}`.", e);
+            }
+        });
+        let session = AnalysisSession::new(analysis_session_id, sketch, sink);
+        self.session_created(analysis_session_id, Box::new(session) as DynSession);
+        Ok(())
+    }
+
     pub fn window_created(&self, id: &str, session_id: &str) {
         let mut guard = self.window_to_session.lock().unwrap_or_else(|_e| {
             panic!("Main application state is poisoned. Cannot recover.");
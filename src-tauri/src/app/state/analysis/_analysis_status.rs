@@ -0,0 +1,35 @@
+use crate::inference::InferenceProgress;
+use serde::{Deserialize, Serialize};
+
+/// Status of the analysis computed by an [super::AnalysisSession].
+///
+/// The status is sent to the frontend (as a JSON payload) every time it changes, so that
+/// the progress of long-running computations can be displayed.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum AnalysisStatus {
+    /// The analysis was not started yet.
+    #[default]
+    NotStarted,
+    /// The analysis is running. The `progress` describes the last evaluated property (if any),
+    /// and `elapsed_ms` is the time since the start of the analysis.
+    Running {
+        progress: Option<InferenceProgress>,
+        elapsed_ms: u64,
+    },
+    /// The analysis finished successfully, with the given (exact) number of satisfying networks.
+    Finished {
+        num_sat_networks: String,
+        elapsed_ms: u64,
+    },
+    /// The analysis was cancelled by the user before it finished.
+    Cancelled { elapsed_ms: u64 },
+    /// The analysis failed with the given error message.
+    Failed { error: String, elapsed_ms: u64 },
+}
+
+impl AnalysisStatus {
+    /// Check whether the analysis is currently running.
+    pub fn is_running(&self) -> bool {
+        matches!(self, AnalysisStatus::Running { .. })
+    }
+}
@@ -0,0 +1,37 @@
+use crate::inference::NetworkFormat;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving a request to export concrete networks from the frontend.
+///
+/// - `num_networks` is the (maximal) number of networks to export
+/// - `seed` is the seed for random sampling; if it is `None`, the first `num_networks`
+///   networks are enumerated instead
+/// - `format` is the format of the exported files
+/// - `path` is the directory into which the files are written
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NetworkExportData {
+    pub num_networks: usize,
+    pub seed: Option<u64>,
+    pub format: NetworkFormat,
+    pub path: String,
+}
+
+impl<'de> JsonSerde<'de> for NetworkExportData {}
+
+impl NetworkExportData {
+    /// Create new `NetworkExportData` instance.
+    pub fn new(
+        num_networks: usize,
+        seed: Option<u64>,
+        format: NetworkFormat,
+        path: &str,
+    ) -> NetworkExportData {
+        NetworkExportData {
+            num_networks,
+            seed,
+            format,
+            path: path.to_string(),
+        }
+    }
+}
@@ -0,0 +1,466 @@
+use crate::app::event::{Event, StateChange, UserAction};
+use crate::app::state::analysis::{AnalysisStatus, NetworkExportData};
+use crate::app::state::{Consumed, Session, SessionHelper, SessionState};
+use crate::app::{AeonError, DynError};
+use crate::debug;
+use crate::inference::{
+    export_networks, run_inference_with_progress, InferenceResults, InterpretationSummary,
+};
+use crate::sketchbook::{JsonSerde, Sketch};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Instant;
+
+/// A callback through which the [AnalysisSession] emits state changes produced by the worker
+/// thread (i.e., outside of any user action), such as progress updates.
+pub type StateChangeSink = Arc<dyn Fn(StateChange) + Send + Sync>;
+
+/// **(internal)** Error message used to stop the inference when cancelled.
+const CANCELLED_MSG: &str = "Analysis was cancelled.";
+
+/// The state of one analysis session.
+///
+/// An analysis session runs the inference on a snapshot of a `Sketch` (taken when the session
+/// is created, so that it is not affected by subsequent edits). The computation runs in
+/// a separate worker thread, so that it does not block the rest of the application. Progress
+/// is reported through the provided [StateChangeSink] as `analysis/status` events.
+///
+/// The session consumes the following events:
+///  - `analysis/start` to start the inference (if it is not running already),
+///  - `analysis/cancel` to request cancelling the running inference. The computation is
+///    stopped before evaluating the next property.
+///  - `analysis/export_networks` to sample (or enumerate) concrete networks from the finished
+///    analysis, and export them into files. The payload is a JSON [NetworkExportData].
+///
+/// The status of the analysis can be obtained via the `analysis/get_status` refresh path, and
+/// the summary of the consistent interpretations (see [InterpretationSummary]) via
+/// `analysis/get_interpretation_summary` (once the analysis finishes).
+pub struct AnalysisSession {
+    id: String,
+    sketch: Sketch,
+    sink: StateChangeSink,
+    status: Arc<Mutex<AnalysisStatus>>,
+    results: Arc<Mutex<Option<InferenceResults>>>,
+    cancelled: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl AnalysisSession {
+    /// Create a new analysis session for the given `sketch` snapshot. State changes produced
+    /// by the worker thread are emitted through the `sink`.
+    pub fn new(id: &str, sketch: Sketch, sink: StateChangeSink) -> AnalysisSession {
+        AnalysisSession {
+            id: id.to_string(),
+            sketch,
+            sink,
+            status: Arc::new(Mutex::new(AnalysisStatus::default())),
+            results: Arc::new(Mutex::new(None)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            worker: None,
+        }
+    }
+
+    /// Get the sketch snapshot that is analysed by this session.
+    pub fn get_sketch(&self) -> &Sketch {
+        &self.sketch
+    }
+
+    /// Get the current status of the analysis.
+    pub fn get_status(&self) -> AnalysisStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Get the results of the analysis, if it already finished successfully.
+    pub fn get_results(&self) -> Option<InferenceResults> {
+        self.results.lock().unwrap().clone()
+    }
+
+    /// Block until the worker thread (if there is any) finishes.
+    pub fn join(&mut self) {
+        if let Some(worker) = self.worker.take() {
+            if worker.join().is_err() {
+                debug!("Analysis worker of session `{}` panicked.", self.id);
+            }
+        }
+    }
+
+    /// **(internal)** Start the inference in a new worker thread.
+    fn start(&mut self) -> Result<Event, DynError> {
+        if self.get_status().is_running() {
+            return AeonError::throw("Analysis is already running.");
+        }
+        // the previous worker (if any) has already finished, so this does not block
+        self.join();
+
+        // every run has its own cancellation flag, so that a late cancel does not affect it
+        self.cancelled = Arc::new(AtomicBool::new(false));
+        *self.results.lock().unwrap() = None;
+        let running = AnalysisStatus::Running {
+            progress: None,
+            elapsed_ms: 0,
+        };
+        *self.status.lock().unwrap() = running.clone();
+
+        let worker = AnalysisWorker {
+            sketch: self.sketch.clone(),
+            sink: self.sink.clone(),
+            status: self.status.clone(),
+            results: self.results.clone(),
+            cancelled: self.cancelled.clone(),
+        };
+        self.worker = Some(std::thread::spawn(move || worker.run()));
+        Ok(mk_status_event(&running))
+    }
+
+    /// **(internal)** Request cancelling the running inference.
+    fn cancel(&mut self) -> Result<Consumed, DynError> {
+        if !self.get_status().is_running() {
+            let error = AeonError::new("There is no running analysis to cancel.", None);
+            return Ok(Consumed::InputError(Box::new(error)));
+        }
+        self.cancelled.store(true, Ordering::SeqCst);
+        Ok(Consumed::Irreversible {
+            state_change: Event::build(&["analysis", "cancel"], None),
+            reset: false,
+        })
+    }
+
+    /// **(internal)** Instantiate concrete networks from the results of the finished analysis,
+    /// and export them into files, as specified by the `export_data`.
+    fn export_networks(&self, export_data: &NetworkExportData) -> Result<Consumed, DynError> {
+        let Some(results) = self.get_results() else {
+            let error = AeonError::new("Analysis has not finished successfully yet.", None);
+            return Ok(Consumed::InputError(Box::new(error)));
+        };
+        let model = self.sketch.get_model();
+        let num_networks = export_data.num_networks;
+        let networks = match export_data.seed {
+            Some(seed) => results.sample_networks(model, num_networks, seed)?,
+            None => results.enumerate_networks(model, num_networks)?,
+        };
+        export_networks(&networks, &export_data.path, export_data.format)?;
+        Ok(Consumed::NoChange)
+    }
+}
+
+impl Drop for AnalysisSession {
+    fn drop(&mut self) {
+        // do not let the worker compute results that nobody can read
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+}
+
+impl Session for AnalysisSession {
+    fn perform_action(&mut self, action: &UserAction) -> Result<StateChange, DynError> {
+        // There is no undo stack, so all events are simply performed one by one.
+        let mut state_changes = Vec::new();
+        for event in &action.events {
+            let event_path = event.path.iter().map(|it| it.as_str()).collect::<Vec<_>>();
+            debug!("Executing event: `{:?}`.", event_path);
+            match self.perform_event(event, &event_path)? {
+                Consumed::Reversible { state_change, .. }
+                | Consumed::Irreversible { state_change, .. } => {
+                    state_changes.push(state_change);
+                }
+                Consumed::Restart(_) => {
+                    return AeonError::throw("Analysis session cannot restart events.");
+                }
+                Consumed::InputError(error) => return Err(error),
+                Consumed::NoChange => {}
+            }
+        }
+        Ok(StateChange {
+            events: state_changes,
+        })
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+}
+
+impl SessionHelper for AnalysisSession {}
+
+impl SessionState for AnalysisSession {
+    fn perform_event(&mut self, event: &Event, at_path: &[&str]) -> Result<Consumed, DynError> {
+        let component = "analysis";
+        if Self::matches(&["analysis", "start"], at_path) {
+            Self::assert_payload_empty(event, component)?;
+            let state_change = self.start()?;
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: false,
+            })
+        } else if Self::matches(&["analysis", "cancel"], at_path) {
+            Self::assert_payload_empty(event, component)?;
+            self.cancel()
+        } else if Self::matches(&["analysis", "export_networks"], at_path) {
+            let payload = Self::clone_payload_str(event, component)?;
+            let export_data = NetworkExportData::from_json_str(&payload)?;
+            self.export_networks(&export_data)
+        } else {
+            Self::invalid_path_error_specific(at_path, component)
+        }
+    }
+
+    fn refresh(&self, full_path: &[String], at_path: &[&str]) -> Result<Event, DynError> {
+        if Self::matches(&["analysis", "get_status"], at_path) {
+            let status = serde_json::to_string(&self.get_status())?;
+            Ok(Event {
+                path: full_path.to_vec(),
+                payload: Some(status),
+            })
+        } else if Self::matches(&["analysis", "get_interpretation_summary"], at_path) {
+            let Some(results) = self.get_results() else {
+                return AeonError::throw("Analysis has not finished successfully yet.");
+            };
+            let summary = InterpretationSummary::from_results(&results, self.sketch.get_model())?;
+            Ok(Event {
+                path: full_path.to_vec(),
+                payload: Some(serde_json::to_string(&summary)?),
+            })
+        } else {
+            Self::invalid_path_error_specific(at_path, "analysis")
+        }
+    }
+}
+
+/// **(internal)** Data moved to the worker thread of an [AnalysisSession].
+struct AnalysisWorker {
+    sketch: Sketch,
+    sink: StateChangeSink,
+    status: Arc<Mutex<AnalysisStatus>>,
+    results: Arc<Mutex<Option<InferenceResults>>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl AnalysisWorker {
+    /// **(internal)** Run the inference, reporting the progress before and after each property.
+    fn run(self) {
+        let start = Instant::now();
+        let elapsed_ms = || start.elapsed().as_millis() as u64;
+        let result = run_inference_with_progress(&self.sketch, |progress| {
+            if self.cancelled.load(Ordering::SeqCst) {
+                return Err(CANCELLED_MSG.to_string());
+            }
+            self.update_status(AnalysisStatus::Running {
+                progress: Some(progress.clone()),
+                elapsed_ms: elapsed_ms(),
+            });
+            Ok(())
+        });
+
+        let final_status = match result {
+            Ok(results) => {
+                let num_sat_networks = results.num_sat_networks_exact();
+                *self.results.lock().unwrap() = Some(results);
+                AnalysisStatus::Finished {
+                    num_sat_networks,
+                    elapsed_ms: elapsed_ms(),
+                }
+            }
+            Err(_) if self.cancelled.load(Ordering::SeqCst) => AnalysisStatus::Cancelled {
+                elapsed_ms: elapsed_ms(),
+            },
+            Err(error) => AnalysisStatus::Failed {
+                error,
+                elapsed_ms: elapsed_ms(),
+            },
+        };
+        self.update_status(final_status);
+    }
+
+    /// **(internal)** Save the new status and emit it as a state change.
+    fn update_status(&self, status: AnalysisStatus) {
+        let event = mk_status_event(&status);
+        *self.status.lock().unwrap() = status;
+        (self.sink)(StateChange::from(event));
+    }
+}
+
+/// **(internal)** Make a state-change event carrying the analysis status.
+fn mk_status_event(status: &AnalysisStatus) -> Event {
+    let payload = serde_json::to_string(status).unwrap();
+    Event::build(&["analysis", "status"], Some(&payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::event::{Event, StateChange, UserAction};
+    use crate::app::state::analysis::{
+        AnalysisSession, AnalysisStatus, NetworkExportData, StateChangeSink,
+    };
+    use crate::app::state::{Session, SessionState};
+    use crate::inference::{InterpretationSummary, NetworkFormat, Occurrence};
+    use crate::sketchbook::ids::DynPropertyId;
+    use crate::sketchbook::model::{ModelState, Monotonicity};
+    use crate::sketchbook::observations::ObservationManager;
+    use crate::sketchbook::properties::PropertyManager;
+    use crate::sketchbook::{JsonSerde, Sketch};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Barrier, Mutex};
+
+    /// Prepare an analysis session for a simple sketch with a single dynamic property. The
+    /// state changes emitted by the worker thread are collected into the returned list.
+    fn prepare_session() -> (AnalysisSession, Arc<Mutex<Vec<StateChange>>>) {
+        let emitted = Arc::new(Mutex::new(Vec::new()));
+        let emitted_clone = emitted.clone();
+        let sink: StateChangeSink = Arc::new(move |change| {
+            emitted_clone.lock().unwrap().push(change);
+        });
+        (prepare_session_with_sink(sink), emitted)
+    }
+
+    /// Prepare an analysis session for a simple sketch with a single dynamic property, emitting
+    /// the state changes of the worker thread through the given `sink`.
+    fn prepare_session_with_sink(sink: StateChangeSink) -> AnalysisSession {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model.add_multiple_regulations(vec!["a -?? b"]).unwrap();
+        let mut properties = PropertyManager::new_empty();
+        let prop_id = DynPropertyId::new("fp").unwrap();
+        properties
+            .add_dyn_generic(prop_id, "fp", "3{x}: @{x}: AX {x}")
+            .unwrap();
+        let sketch = Sketch::new(model, ObservationManager::new_empty(), properties);
+        AnalysisSession::new("analysis-1", sketch, sink)
+    }
+
+    #[test]
+    /// Test running the analysis in the worker thread, and reporting its progress.
+    fn test_run_analysis() {
+        let (mut session, emitted) = prepare_session();
+        assert_eq!(session.get_status(), AnalysisStatus::NotStarted);
+
+        let start = UserAction::from(Event::build(&["analysis", "start"], None));
+        let state_change = session.perform_action(&start).unwrap();
+        assert_eq!(state_change.events[0].path, vec!["analysis", "status"]);
+        session.join();
+
+        // `b` can be any fn of `a` (4 options), `a` is a constant (2 options)
+        assert!(matches!(
+            session.get_status(),
+            AnalysisStatus::Finished { num_sat_networks, .. } if num_sat_networks == "8"
+        ));
+        assert_eq!(session.get_results().unwrap().num_sat_networks(), 8.);
+
+        // progress updates before and after the single property, and the final status
+        let emitted = emitted.lock().unwrap();
+        assert_eq!(emitted.len(), 3);
+        let progress = emitted[..2]
+            .iter()
+            .map(|change| {
+                let payload = change.events[0].payload.as_ref().unwrap();
+                let AnalysisStatus::Running {
+                    progress: Some(progress),
+                    ..
+                } = serde_json::from_str(payload).unwrap()
+                else {
+                    panic!("Expected progress update.");
+                };
+                progress
+            })
+            .collect::<Vec<_>>();
+        assert!(!progress[0].finished);
+        assert_eq!(progress[0].num_evaluated, 0);
+        assert!(progress[1].finished);
+        assert_eq!(progress[1].num_evaluated, 1);
+        assert_eq!(progress[1].num_total, 1);
+
+        // the final status can also be obtained via refresh
+        let full_path = vec!["analysis".to_string(), "get_status".to_string()];
+        let event = session
+            .refresh(&full_path, &["analysis", "get_status"])
+            .unwrap();
+        let status: AnalysisStatus = serde_json::from_str(&event.payload.unwrap()).unwrap();
+        assert_eq!(status, session.get_status());
+    }
+
+    #[test]
+    /// Test that cancelling is only possible when the analysis is running, and that it stops
+    /// the analysis.
+    fn test_cancel_analysis() {
+        // the worker is paused at its first progress update (before evaluating the property),
+        // until the test cancels the analysis (the gate is passed twice, entering and leaving)
+        let gate = Arc::new(Barrier::new(2));
+        let gate_clone = gate.clone();
+        let gated = Arc::new(AtomicBool::new(true));
+        let sink: StateChangeSink = Arc::new(move |_| {
+            if gated.swap(false, Ordering::SeqCst) {
+                gate_clone.wait();
+                gate_clone.wait();
+            }
+        });
+        let mut session = prepare_session_with_sink(sink);
+        let cancel = UserAction::from(Event::build(&["analysis", "cancel"], None));
+        assert!(session.perform_action(&cancel).is_err());
+
+        let start = UserAction::from(Event::build(&["analysis", "start"], None));
+        session.perform_action(&start).unwrap();
+        gate.wait();
+        session.perform_action(&cancel).unwrap();
+        gate.wait();
+        session.join();
+        assert!(matches!(
+            session.get_status(),
+            AnalysisStatus::Cancelled { .. }
+        ));
+        assert!(session.get_results().is_none());
+
+        // the analysis can be started again after it ends
+        session.perform_action(&start).unwrap();
+        session.join();
+        assert!(matches!(
+            session.get_status(),
+            AnalysisStatus::Finished { .. }
+        ));
+    }
+
+    #[test]
+    /// Test exporting concrete networks from the results of the analysis.
+    fn test_export_networks() {
+        let (mut session, _) = prepare_session();
+        let dir = std::env::temp_dir().join("aeon_sketchbook_test_analysis_export");
+        std::fs::create_dir_all(&dir).unwrap();
+        let export_data =
+            NetworkExportData::new(10, None, NetworkFormat::Aeon, dir.to_str().unwrap());
+        let payload = export_data.to_json_str();
+        let export = UserAction::from(Event::build(
+            &["analysis", "export_networks"],
+            Some(&payload),
+        ));
+
+        // there are no results before the analysis finishes
+        assert!(session.perform_action(&export).is_err());
+
+        let start = UserAction::from(Event::build(&["analysis", "start"], None));
+        session.perform_action(&start).unwrap();
+        session.join();
+        session.perform_action(&export).unwrap();
+        // all 8 satisfying networks are exported
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 8);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    /// Test obtaining the summary of consistent interpretations via refresh.
+    fn test_refresh_interpretation_summary() {
+        let (mut session, _) = prepare_session();
+        let full_path = vec![
+            "analysis".to_string(),
+            "get_interpretation_summary".to_string(),
+        ];
+        let at_path = ["analysis", "get_interpretation_summary"];
+        assert!(session.refresh(&full_path, &at_path).is_err());
+
+        let start = UserAction::from(Event::build(&["analysis", "start"], None));
+        session.perform_action(&start).unwrap();
+        session.join();
+        let event = session.refresh(&full_path, &at_path).unwrap();
+        let summary: InterpretationSummary = serde_json::from_str(&event.payload.unwrap()).unwrap();
+        // `a` regulates `b` (with unknown sign and essentiality), `b` can be any fn of `a`
+        let reg_summary = summary.get_regulation("a", "b").unwrap();
+        assert_eq!(reg_summary.input.essential, Occurrence::Sometimes);
+        assert_eq!(reg_summary.input.monotonicity, Monotonicity::Unknown);
+    }
+}
@@ -0,0 +1,10 @@
+/// Declares [AnalysisStatus]: the status of a (possibly running) analysis.
+mod _analysis_status;
+/// Declares [NetworkExportData]: a request to export concrete networks.
+mod _network_export_data;
+/// Declares [AnalysisSession]: the root state object of an analysis window.
+mod _state_analysis_session;
+
+pub use _analysis_status::AnalysisStatus;
+pub use _network_export_data::NetworkExportData;
+pub use _state_analysis_session::{AnalysisSession, StateChangeSink};
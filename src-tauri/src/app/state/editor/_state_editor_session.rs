@@ -97,6 +97,15 @@ impl EditorSession {
             }
         }
 
+        // Renaming or removing variables might invalidate properties, which must be flagged.
+        if let Some(state_change) = self.sketch.mk_invalid_props_state_change(&state_changes) {
+            state_changes.push(state_change);
+        }
+        // Any change of the sketch might introduce or resolve some issues reported by the linter.
+        if let Some(state_change) = self.sketch.mk_diagnostics_state_change(&state_changes) {
+            state_changes.push(state_change);
+        }
+
         // If the action is not irreversible, we should add an entry to the undo stack.
         if let Some(events) = reverse {
             if !events.is_empty() {
@@ -212,3 +221,112 @@ impl SessionState for EditorSession {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::app::event::{Event, UserAction};
+    use crate::app::state::editor::EditorSession;
+    use crate::app::state::Session;
+    use crate::sketchbook::data_structs::{RegulationData, UpdateFnChangeData};
+    use crate::sketchbook::model::ModelState;
+    use crate::sketchbook::observations::ObservationManager;
+    use crate::sketchbook::properties::PropertyManager;
+    use crate::sketchbook::{JsonSerde, Sketch};
+
+    /// Prepare an editor session with variables `a` and `b`, regulations `a -> b` and `b -| b`,
+    /// and update function `b = a & !b`. Regulations are added via events, so that the sketch
+    /// also contains the corresponding static properties.
+    fn prepare_session() -> EditorSession {
+        let model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        let mut session = EditorSession::new("editor-1");
+        session.sketch = Sketch::new(
+            model,
+            ObservationManager::new_empty(),
+            PropertyManager::new_empty(),
+        );
+        for regulation in ["a -> b", "b -| b"] {
+            let payload = RegulationData::try_from_reg_str(regulation)
+                .unwrap()
+                .to_json_str();
+            let path = ["sketch", "model", "regulation", "add"];
+            let action = UserAction::from(Event::build(&path, Some(&payload)));
+            Session::perform_action(&mut session, &action).unwrap();
+        }
+        let path = ["sketch", "model", "variable", "b", "set_update_fn"];
+        let action = UserAction::from(Event::build(&path, Some("a & !b")));
+        Session::perform_action(&mut session, &action).unwrap();
+        session
+    }
+
+    /// Collect paths of the state changes related to the model.
+    fn model_change_paths(events: &[Event]) -> Vec<String> {
+        events
+            .iter()
+            .filter(|e| {
+                e.path
+                    .starts_with(&["sketch".to_string(), "model".to_string()])
+            })
+            .map(|e| e.path[2..].join("/"))
+            .collect()
+    }
+
+    #[test]
+    /// Test that the update function is rewritten before the regulation is removed (so that the
+    /// model is consistent after each step), and that undo restores both.
+    fn test_remove_regulation_with_fn_rewrite() {
+        let mut session = prepare_session();
+        let path = [
+            "sketch",
+            "model",
+            "regulation",
+            "a",
+            "b",
+            "remove_with_fn_rewrite",
+        ];
+        let action = UserAction::from(Event::build(&path, Some("false")));
+        let state_change = Session::perform_action(&mut session, &action).unwrap();
+        let paths = model_change_paths(&state_change.events);
+        assert_eq!(paths, vec!["variable/set_update_fn", "regulation/remove"]);
+
+        let model = session.sketch.get_model();
+        let (var_a, var_b) = (
+            model.get_var_id("a").unwrap(),
+            model.get_var_id("b").unwrap(),
+        );
+        assert!(model.get_regulation(&var_a, &var_b).is_err());
+        assert_eq!(model.get_update_fn_string(&var_b).unwrap(), "false");
+
+        let undo = UserAction::from(Event::build(&["undo_stack", "undo"], None));
+        Session::perform_action(&mut session, &undo).unwrap();
+        let model = session.sketch.get_model();
+        assert!(model.get_regulation(&var_a, &var_b).is_ok());
+        assert_eq!(model.get_update_fn_string(&var_b).unwrap(), "a & !b");
+    }
+
+    #[test]
+    /// Test that missing regulations are added before the update function is set (so that the
+    /// model is consistent after each step), and that undo removes them again.
+    fn test_set_update_fn_with_regulations() {
+        let mut session = prepare_session();
+        let path = ["sketch", "model", "variable", "a", "set_update_fn"];
+        let payload = UpdateFnChangeData::new("!b", true).to_json_str();
+        let action = UserAction::from(Event::build(&path, Some(&payload)));
+        let state_change = Session::perform_action(&mut session, &action).unwrap();
+        let paths = model_change_paths(&state_change.events);
+        assert_eq!(paths, vec!["regulation/add", "variable/set_update_fn"]);
+
+        let model = session.sketch.get_model();
+        let (var_a, var_b) = (
+            model.get_var_id("a").unwrap(),
+            model.get_var_id("b").unwrap(),
+        );
+        assert!(model.get_regulation(&var_b, &var_a).is_ok());
+        assert_eq!(model.get_update_fn_string(&var_a).unwrap(), "!b");
+
+        let undo = UserAction::from(Event::build(&["undo_stack", "undo"], None));
+        Session::perform_action(&mut session, &undo).unwrap();
+        let model = session.sketch.get_model();
+        assert!(model.get_regulation(&var_b, &var_a).is_err());
+        assert_eq!(model.get_update_fn_string(&var_a).unwrap(), "");
+    }
+}
@@ -7,6 +7,8 @@ mod _state_atomic;
 mod _state_map;
 pub mod _undo_stack;
 
+/// Declares state objects that are unique to the analysis sessions.
+pub mod analysis;
 /// Declares state objects that are unique to the sketchbook editor window.
 pub mod editor;
 
@@ -0,0 +1,132 @@
+use crate::sketchbook::properties::{DynProperty, HctlFormula};
+use crate::sketchbook::Sketch;
+use biodivine_hctl_model_checker::mc_utils::collect_unique_hctl_vars;
+use biodivine_hctl_model_checker::model_checking::model_check_formula_dirty;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+
+/// Encode the dynamic property `prop` of the `sketch` into a HCTL formula, and check that
+/// the formula is valid with respect to the sketch's model.
+pub(crate) fn prepare_dyn_prop_formula(
+    prop: &DynProperty,
+    sketch: &Sketch,
+) -> Result<HctlFormula, String> {
+    prop.check_with_model(sketch.get_model())?;
+    prop.encode_hctl(sketch.get_observations())
+}
+
+/// Compute the number of distinct HCTL variables used in the `formula`. The symbolic graph
+/// used for model checking must support (at least) this many additional sets of variables.
+pub(crate) fn num_hctl_vars(formula: &HctlFormula) -> usize {
+    collect_unique_hctl_vars(formula.get_tree().clone()).len()
+}
+
+/// Model check the HCTL `formula` (encoding a dynamic property) on the symbolic parametrised
+/// network `graph`, and compute the set of colors (interpretations) for which it holds.
+///
+/// A color is included if the formula is satisfied in at least one state. The properties are
+/// typically encoded by closed formulas (starting with a hybrid operator), which are satisfied
+/// either in all the states or in none of them.
+///
+/// The result is not sanitized by the model checker, so that the colors stay compatible with
+/// other colors of the `graph`. Instead, the additional HCTL state variables are projected out.
+pub(crate) fn eval_dyn_prop(
+    formula: &HctlFormula,
+    graph: &SymbolicAsyncGraph,
+) -> Result<GraphColors, String> {
+    let result = model_check_formula_dirty(&formula.to_string(), graph)?;
+    let extra_vars = graph.symbolic_context().all_extra_state_variables();
+    let colors = result.colors();
+    let colors = colors.copy(colors.as_bdd().exists(extra_vars));
+    Ok(colors.intersect(graph.unit_colors()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{eval_dyn_prop, num_hctl_vars, prepare_dyn_prop_formula};
+    use crate::inference::prepare_extended_graph;
+    use crate::sketchbook::ids::{DatasetId, ObservationId};
+    use crate::sketchbook::model::ModelState;
+    use crate::sketchbook::observations::{Dataset, Observation, ObservationManager};
+    use crate::sketchbook::properties::{DynProperty, PropertyManager};
+    use crate::sketchbook::Sketch;
+
+    /// Prepare a sketch with variables `a` and `b` regulating each other (without any
+    /// annotations, so there are 4 options for each update function), and a dataset `d`
+    /// with observations `o1` (`a=1`, `b=1`) and `o2` (`a=0`, `b=0`).
+    fn prepare_test_sketch() -> Sketch {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -?? b", "b -?? a"])
+            .unwrap();
+        let observations = vec![
+            Observation::try_from_str("11", "o1").unwrap(),
+            Observation::try_from_str("00", "o2").unwrap(),
+        ];
+        let dataset = Dataset::new_unspecified(observations, vec!["a", "b"]).unwrap();
+        let obs_manager = ObservationManager::from_datasets(vec![("d", dataset)]).unwrap();
+        Sketch::new(model, obs_manager, PropertyManager::new_empty())
+    }
+
+    /// Evaluate a dynamic property on the sketch, returning the number of satisfying colors.
+    fn eval_property(sketch: &Sketch, prop: &DynProperty) -> f64 {
+        let formula = prepare_dyn_prop_formula(prop, sketch).unwrap();
+        let num_vars = num_hctl_vars(&formula) as u16;
+        let graph = prepare_extended_graph(sketch.get_model(), num_vars).unwrap();
+        eval_dyn_prop(&formula, &graph)
+            .unwrap()
+            .approx_cardinality()
+    }
+
+    #[test]
+    /// Test evaluating generic dynamic properties.
+    fn test_eval_generic() {
+        let sketch = prepare_test_sketch();
+        let prop = DynProperty::mk_generic("p", "true").unwrap();
+        assert_eq!(eval_property(&sketch, &prop), 16.);
+        // state `11` is a fixed point iff both update functions are true for it (4 options)
+        let prop = DynProperty::mk_generic("p", "3{x}: @{x}: a & b & AX {x}").unwrap();
+        assert_eq!(eval_property(&sketch, &prop), 4.);
+        // propositions must correspond to the model's variables
+        let prop = DynProperty::mk_generic("p", "3{x}: @{x}: c").unwrap();
+        assert!(prepare_dyn_prop_formula(&prop, &sketch).is_err());
+    }
+
+    #[test]
+    /// Test evaluating template dynamic properties using datasets.
+    fn test_eval_templates() {
+        let sketch = prepare_test_sketch();
+        let d = DatasetId::new("d").ok();
+        let o1 = ObservationId::new("o1").ok();
+
+        let fixed_point = DynProperty::mk_fixed_point("p", d.clone(), o1.clone()).unwrap();
+        assert_eq!(eval_property(&sketch, &fixed_point), 4.);
+        // sub-space `11` is a trap space iff it is a fixed point
+        let trap_space =
+            DynProperty::mk_trap_space("p", d.clone(), o1.clone(), false, false).unwrap();
+        assert_eq!(eval_property(&sketch, &trap_space), 4.);
+        // states `11` and `00` both in attractors (e.g., both fixed points for `a=b, b=a`)
+        let attractors = DynProperty::mk_has_attractor("p", d.clone(), None).unwrap();
+        assert!(eval_property(&sketch, &attractors) > 0.);
+        let trajectory = DynProperty::mk_trajectory("p", d.clone()).unwrap();
+        assert!(eval_property(&sketch, &trajectory) > 0.);
+
+        // with 2 variables, there are at most 4 attractors, and at least 1
+        let count_any = DynProperty::mk_attractor_count("p", 1, 4).unwrap();
+        assert_eq!(eval_property(&sketch, &count_any), 16.);
+        // only `a=b, b=a` and `a=!b, b=!a` have 2 attractors (two fixed points each)
+        let count_two = DynProperty::mk_attractor_count("p", 2, 2).unwrap();
+        assert_eq!(eval_property(&sketch, &count_two), 2.);
+        // the upper bound is limited, since each counted attractor needs its own HCTL variable
+        let max = DynProperty::MAX_ATTRACTOR_COUNT;
+        assert!(DynProperty::mk_attractor_count("p", 1, max).is_ok());
+        assert!(DynProperty::mk_attractor_count("p", 1, max + 1).is_err());
+        let mut count_large = count_two.clone();
+        count_large.set_attr_count(1, max + 1).unwrap();
+        assert!(prepare_dyn_prop_formula(&count_large, &sketch).is_err());
+
+        // missing dataset or observation
+        let missing = DynProperty::mk_fixed_point("p", d, None).unwrap();
+        assert!(prepare_dyn_prop_formula(&missing, &sketch).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::sketchbook::model::{BinaryOp, FnTree};
+use biodivine_lib_bdd::Bdd;
+use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+
+/// Convert a context of a regulation property into a `Bdd` over the network variables of the
+/// `graph`. Missing context is converted to a `true` constant.
+pub(super) fn context_to_state_bdd(
+    graph: &SymbolicAsyncGraph,
+    context: Option<&FnTree>,
+) -> Result<Bdd, String> {
+    let symbolic_context = graph.symbolic_context();
+    let Some(context) = context else {
+        return Ok(symbolic_context.mk_constant(true));
+    };
+    match context {
+        FnTree::Const(value) => Ok(symbolic_context.mk_constant(*value)),
+        FnTree::Var(var_id) => {
+            let var = symbolic_context
+                .find_network_variable(var_id.as_str())
+                .ok_or(format!(
+                    "Variable with ID {var_id} does not exist in this model."
+                ))?;
+            Ok(symbolic_context.mk_state_variable_is_true(var))
+        }
+        FnTree::Not(inner) => Ok(context_to_state_bdd(graph, Some(inner))?.not()),
+        FnTree::Binary(op, l, r) => {
+            let l = context_to_state_bdd(graph, Some(l))?;
+            let r = context_to_state_bdd(graph, Some(r))?;
+            Ok(apply_binary_op(*op, &l, &r))
+        }
+        FnTree::PlaceholderVar(_) | FnTree::UninterpretedFn(..) => {
+            Err("Context of a regulation can only contain network variables.".to_string())
+        }
+    }
+}
+
+/// Evaluate a context of an uninterpreted function property on a single row of the function's
+/// table, i.e., a valuation of its arguments `var0`, `var1`, ... Missing context is always
+/// satisfied.
+pub(super) fn eval_context_on_row(context: Option<&FnTree>, row: &[bool]) -> Result<bool, String> {
+    let Some(context) = context else {
+        return Ok(true);
+    };
+    match context {
+        FnTree::Const(value) => Ok(*value),
+        FnTree::PlaceholderVar(var_id) => var_id
+            .as_str()
+            .strip_prefix("var")
+            .and_then(|idx| idx.parse::<usize>().ok())
+            .and_then(|idx| row.get(idx).copied())
+            .ok_or(format!(
+                "Invalid function argument `{var_id}` in the context."
+            )),
+        FnTree::Not(inner) => Ok(!eval_context_on_row(Some(inner), row)?),
+        FnTree::Binary(op, l, r) => {
+            let l = eval_context_on_row(Some(l), row)?;
+            let r = eval_context_on_row(Some(r), row)?;
+            Ok(match op {
+                BinaryOp::And => l && r,
+                BinaryOp::Or => l || r,
+                BinaryOp::Xor => l ^ r,
+                BinaryOp::Iff => l == r,
+                BinaryOp::Imp => !l || r,
+            })
+        }
+        FnTree::Var(_) | FnTree::UninterpretedFn(..) => {
+            Err("Context of a function input can only contain its arguments.".to_string())
+        }
+    }
+}
+
+/// **(internal)** Apply a binary Boolean operator on two BDDs.
+pub(super) fn apply_binary_op(op: BinaryOp, l: &Bdd, r: &Bdd) -> Bdd {
+    match op {
+        BinaryOp::And => l.and(r),
+        BinaryOp::Or => l.or(r),
+        BinaryOp::Xor => l.xor(r),
+        BinaryOp::Iff => l.iff(r),
+        BinaryOp::Imp => l.imp(r),
+    }
+}
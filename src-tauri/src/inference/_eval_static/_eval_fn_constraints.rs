@@ -0,0 +1,179 @@
+use super::_eval_context::{apply_binary_op, eval_context_on_row};
+use super::_eval_fn_input_props::{eval_fn_input_essential, eval_fn_input_monotonic};
+use super::colors_from_bdd;
+use crate::sketchbook::ids::UninterpretedFnId;
+use crate::sketchbook::model::{Essentiality, FnTree, ModelState, Monotonicity};
+use biodivine_lib_bdd::Bdd;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+
+/// Compute the set of colors of the `graph` that are consistent with the constraints on the
+/// uninterpreted functions of the `model`, i.e., the monotonicity and essentiality annotations
+/// of their arguments, and their (partially) specified expressions.
+///
+/// These constraints are not part of the `BooleanNetwork` produced by [ModelState::to_bn], so
+/// they have to be enforced on top of the network's colors.
+pub(crate) fn eval_fn_constraints(
+    model: &ModelState,
+    graph: &SymbolicAsyncGraph,
+) -> Result<GraphColors, String> {
+    let mut colors = graph.mk_unit_colors();
+    for (fn_id, uninterpreted_fn) in model.uninterpreted_fns() {
+        for (index, argument) in uninterpreted_fn.get_all_arguments().iter().enumerate() {
+            if argument.essential != Essentiality::Unknown {
+                let essential =
+                    eval_fn_input_essential(graph, fn_id, index, argument.essential, None)?;
+                colors = colors.intersect(&essential);
+            }
+            if argument.monotonicity != Monotonicity::Unknown {
+                let monotonic =
+                    eval_fn_input_monotonic(graph, fn_id, index, argument.monotonicity, None)?;
+                colors = colors.intersect(&monotonic);
+            }
+        }
+        if let Some(expression) = uninterpreted_fn.get_fn_tree() {
+            let expression_bdd = fn_expression_to_bdd(graph, fn_id, expression)?;
+            colors = colors.intersect(&colors_from_bdd(graph, &expression_bdd));
+        }
+    }
+    Ok(colors)
+}
+
+/// **(internal)** Convert the `expression` of the uninterpreted function `fn_id` into a `Bdd`
+/// over the parameters of the `graph`, which is satisfied exactly by the interpretations of
+/// `fn_id` whose function table matches the expression.
+fn fn_expression_to_bdd(
+    graph: &SymbolicAsyncGraph,
+    fn_id: &UninterpretedFnId,
+    expression: &FnTree,
+) -> Result<Bdd, String> {
+    let symbolic_context = graph.symbolic_context();
+    let parameter = symbolic_context
+        .find_network_parameter(fn_id.as_str())
+        .ok_or(format!(
+            "Uninterpreted function with ID {fn_id} does not exist in this model."
+        ))?;
+    let mut result = symbolic_context.mk_constant(true);
+    for (row, row_var) in symbolic_context.get_explicit_function_table(parameter) {
+        let row_value = eval_expression_on_row(graph, expression, &row)?;
+        let row_var = symbolic_context.bdd_variable_set().mk_var(row_var);
+        result = result.and(&row_var.iff(&row_value));
+    }
+    Ok(result)
+}
+
+/// **(internal)** Evaluate the expression of an uninterpreted function on a single row of its
+/// function table, i.e., a valuation of its arguments `var0`, `var1`, ...
+///
+/// The expression can itself contain other uninterpreted functions, so the result is a `Bdd`
+/// over their parameters (a constant if there are none).
+fn eval_expression_on_row(
+    graph: &SymbolicAsyncGraph,
+    expression: &FnTree,
+    row: &[bool],
+) -> Result<Bdd, String> {
+    let symbolic_context = graph.symbolic_context();
+    match expression {
+        FnTree::Const(value) => Ok(symbolic_context.mk_constant(*value)),
+        FnTree::PlaceholderVar(_) => {
+            let value = eval_context_on_row(Some(expression), row)?;
+            Ok(symbolic_context.mk_constant(value))
+        }
+        FnTree::Not(inner) => Ok(eval_expression_on_row(graph, inner, row)?.not()),
+        FnTree::Binary(op, l, r) => {
+            let l = eval_expression_on_row(graph, l, row)?;
+            let r = eval_expression_on_row(graph, r, row)?;
+            Ok(apply_binary_op(*op, &l, &r))
+        }
+        FnTree::UninterpretedFn(fn_id, args) => {
+            let args = args
+                .iter()
+                .map(|arg| eval_expression_on_row(graph, arg, row))
+                .collect::<Result<Vec<_>, String>>()?;
+            let parameter = symbolic_context
+                .find_network_parameter(fn_id.as_str())
+                .ok_or(format!(
+                    "Uninterpreted function with ID {fn_id} does not exist in this model."
+                ))?;
+            // the function is true if the arguments match any of its rows that is set to true
+            let mut result = symbolic_context.mk_constant(false);
+            for (inner_row, row_var) in symbolic_context.get_explicit_function_table(parameter) {
+                let mut row_bdd = symbolic_context.bdd_variable_set().mk_var(row_var);
+                for (arg, value) in args.iter().zip(inner_row) {
+                    row_bdd = if value {
+                        row_bdd.and(arg)
+                    } else {
+                        row_bdd.and_not(arg)
+                    };
+                }
+                result = result.or(&row_bdd);
+            }
+            Ok(result)
+        }
+        FnTree::Var(_) => Err(
+            "Expression of an uninterpreted function can only contain its arguments.".to_string(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::eval_fn_constraints;
+    use crate::inference::prepare_graph;
+    use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+
+    /// Prepare a test model with `b` updated by an uninterpreted fn `f` of arity 2, which
+    /// is applied to `a` and `b`, and `a` updated by an uninterpreted fn `g` of arity 1. There
+    /// are 16 options for the interpretation of `f` and 4 options for `g`.
+    fn prepare_test_model() -> ModelState {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -?? a", "a -?? b", "b -?? b"])
+            .unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 2).unwrap();
+        model.add_uninterpreted_fn_by_str("g", "g", 1).unwrap();
+        let var_a = model.get_var_id("a").unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        model.set_update_fn(&var_a, "g(a)").unwrap();
+        model.set_update_fn(&var_b, "f(a, b)").unwrap();
+        model
+    }
+
+    #[test]
+    /// Test evaluating argument annotations and expressions of uninterpreted functions.
+    fn test_fn_constraints() {
+        let model = prepare_test_model();
+        let graph = prepare_graph(&model).unwrap();
+        let colors = eval_fn_constraints(&model, &graph).unwrap();
+        assert_eq!(colors.approx_cardinality(), 16. * 4.);
+
+        let mut model_monotonic = model.clone();
+        model_monotonic
+            .set_uninterpreted_fn_monotonicity_by_str("f", Monotonicity::Activation, 0)
+            .unwrap();
+        let colors = eval_fn_constraints(&model_monotonic, &graph).unwrap();
+        assert_eq!(colors.approx_cardinality(), 9. * 4.);
+
+        let mut model_essential = model.clone();
+        model_essential
+            .set_uninterpreted_fn_essentiality_by_str("f", Essentiality::True, 0)
+            .unwrap();
+        let colors = eval_fn_constraints(&model_essential, &graph).unwrap();
+        assert_eq!(colors.approx_cardinality(), 12. * 4.);
+
+        // `f` is fully determined by `g`, which is unconstrained
+        let mut model_expression = model.clone();
+        model_expression
+            .set_uninterpreted_fn_expression_by_str("f", "g(var0) & var1")
+            .unwrap();
+        let colors = eval_fn_constraints(&model_expression, &graph).unwrap();
+        assert_eq!(colors.approx_cardinality(), 4.);
+
+        // `g` is a negation, so `f` is fully specified
+        model_expression
+            .set_uninterpreted_fn_expression_by_str("g", "!var0")
+            .unwrap();
+        let colors = eval_fn_constraints(&model_expression, &graph).unwrap();
+        assert_eq!(colors.approx_cardinality(), 1.);
+    }
+}
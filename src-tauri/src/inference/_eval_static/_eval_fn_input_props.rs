@@ -0,0 +1,171 @@
+use super::_eval_context::eval_context_on_row;
+use super::{apply_essentiality, apply_monotonicity, colors_from_bdd};
+use crate::sketchbook::ids::UninterpretedFnId;
+use crate::sketchbook::model::{Essentiality, FnTree, Monotonicity};
+use biodivine_lib_bdd::BddVariable;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use std::collections::HashMap;
+
+/// Compute the set of colors of the `graph` for which the input on `input_index` of the
+/// uninterpreted function `fn_id` has the given `Essentiality`.
+///
+/// The input is essential if there is a valuation of the function's arguments (satisfying the
+/// optional `context`) in which changing the value of the input changes the function's output.
+pub(super) fn eval_fn_input_essential(
+    graph: &SymbolicAsyncGraph,
+    fn_id: &UninterpretedFnId,
+    input_index: usize,
+    value: Essentiality,
+    context: Option<&FnTree>,
+) -> Result<GraphColors, String> {
+    let bdd_vars = graph.symbolic_context().bdd_variable_set();
+    let essential_bdd = fn_input_row_pairs(graph, fn_id, input_index, context)?
+        .into_iter()
+        .fold(bdd_vars.mk_false(), |acc, (row_false, row_true)| {
+            let row_false = bdd_vars.mk_var(row_false);
+            let row_true = bdd_vars.mk_var(row_true);
+            acc.or(&row_false.xor(&row_true))
+        });
+    let essential_colors = colors_from_bdd(graph, &essential_bdd);
+    Ok(apply_essentiality(graph, essential_colors, value))
+}
+
+/// Compute the set of colors of the `graph` for which the input on `input_index` of the
+/// uninterpreted function `fn_id` has the given `Monotonicity`.
+///
+/// The input is activating if there is no valuation of the function's arguments (satisfying
+/// the optional `context`) in which increasing the value of the input decreases the function's
+/// output (and vice versa for inhibition). It is dual if both the increase and decrease
+/// can happen.
+pub(super) fn eval_fn_input_monotonic(
+    graph: &SymbolicAsyncGraph,
+    fn_id: &UninterpretedFnId,
+    input_index: usize,
+    value: Monotonicity,
+    context: Option<&FnTree>,
+) -> Result<GraphColors, String> {
+    let bdd_vars = graph.symbolic_context().bdd_variable_set();
+    let row_pairs = fn_input_row_pairs(graph, fn_id, input_index, context)?;
+    let mut activation_bdd = bdd_vars.mk_false();
+    let mut inhibition_bdd = bdd_vars.mk_false();
+    for (row_false, row_true) in row_pairs {
+        let row_false = bdd_vars.mk_var(row_false);
+        let row_true = bdd_vars.mk_var(row_true);
+        activation_bdd = activation_bdd.or(&row_true.and_not(&row_false));
+        inhibition_bdd = inhibition_bdd.or(&row_false.and_not(&row_true));
+    }
+    let can_activate = colors_from_bdd(graph, &activation_bdd);
+    let can_inhibit = colors_from_bdd(graph, &inhibition_bdd);
+    Ok(apply_monotonicity(graph, can_activate, can_inhibit, value))
+}
+
+/// **(internal)** Collect pairs of symbolic variables representing rows of the function table
+/// of `fn_id` that only differ in the input on `input_index` (the first row of each pair has
+/// the input set to `false`, the second one to `true`).
+///
+/// Only pairs where at least one of the rows satisfies the `context` are collected.
+fn fn_input_row_pairs(
+    graph: &SymbolicAsyncGraph,
+    fn_id: &UninterpretedFnId,
+    input_index: usize,
+    context: Option<&FnTree>,
+) -> Result<Vec<(BddVariable, BddVariable)>, String> {
+    let symbolic_context = graph.symbolic_context();
+    let parameter = symbolic_context
+        .find_network_parameter(fn_id.as_str())
+        .ok_or(format!(
+            "Uninterpreted function with ID {fn_id} does not exist in this model."
+        ))?;
+    let arity = symbolic_context.get_network_parameter_arity(parameter) as usize;
+    if input_index >= arity {
+        return Err(format!(
+            "Uninterpreted function {fn_id} has arity {arity}, there is no input with index {input_index}."
+        ));
+    }
+
+    let table = symbolic_context.get_explicit_function_table(parameter);
+    let rows: HashMap<Vec<bool>, BddVariable> = table.into_iter().collect();
+    let mut row_pairs = Vec::new();
+    for (row, row_var) in rows.iter() {
+        if row[input_index] {
+            continue;
+        }
+        let mut row_flipped = row.clone();
+        row_flipped[input_index] = true;
+        if eval_context_on_row(context, row)? || eval_context_on_row(context, &row_flipped)? {
+            // all the rows are present in the table, we can unwrap
+            let row_flipped_var = *rows.get(&row_flipped).unwrap();
+            row_pairs.push((*row_var, row_flipped_var));
+        }
+    }
+    Ok(row_pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{eval_fn_input_essential, eval_fn_input_monotonic};
+    use crate::inference::prepare_graph;
+    use crate::sketchbook::model::{Essentiality, FnTree, ModelState, Monotonicity};
+
+    /// Prepare a test model with `b` updated by an uninterpreted fn `f` of arity 2, which
+    /// is applied to `a` and `b`. Variable `a` is an input (2 options), and there are 16 options
+    /// for the interpretation of `f`.
+    fn prepare_test_model() -> ModelState {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -?? b", "b -?? b"])
+            .unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 2).unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        model.set_update_fn(&var_b, "f(a, b)").unwrap();
+        model
+    }
+
+    #[test]
+    /// Test evaluating essentiality of a function input.
+    fn test_fn_input_essential() {
+        let model = prepare_test_model();
+        let graph = prepare_graph(&model).unwrap();
+        let f = model.get_uninterpreted_fn_id("f").unwrap();
+
+        let essential = eval_fn_input_essential(&graph, &f, 0, Essentiality::True, None).unwrap();
+        assert_eq!(essential.approx_cardinality(), 12. * 2.);
+        let non_essential =
+            eval_fn_input_essential(&graph, &f, 1, Essentiality::False, None).unwrap();
+        assert_eq!(non_essential.approx_cardinality(), 4. * 2.);
+        assert!(eval_fn_input_essential(&graph, &f, 2, Essentiality::True, None).is_err());
+    }
+
+    #[test]
+    /// Test evaluating monotonicity of a function input.
+    fn test_fn_input_monotonic() {
+        let model = prepare_test_model();
+        let graph = prepare_graph(&model).unwrap();
+        let f = model.get_uninterpreted_fn_id("f").unwrap();
+
+        let activation =
+            eval_fn_input_monotonic(&graph, &f, 0, Monotonicity::Activation, None).unwrap();
+        assert_eq!(activation.approx_cardinality(), 9. * 2.);
+        let dual = eval_fn_input_monotonic(&graph, &f, 1, Monotonicity::Dual, None).unwrap();
+        assert_eq!(dual.approx_cardinality(), 2. * 2.);
+    }
+
+    #[test]
+    /// Test evaluating properties of function inputs restricted to a context.
+    fn test_fn_input_context() {
+        let model = prepare_test_model();
+        let graph = prepare_graph(&model).unwrap();
+        let f_id = model.get_uninterpreted_fn_id("f").unwrap();
+        let f = model.get_uninterpreted_fn(&f_id).unwrap();
+        let context = FnTree::try_from_str("!var1", &model, Some((&f_id, f))).unwrap();
+
+        // first input must be essential when the second one is `false`
+        let essential =
+            eval_fn_input_essential(&graph, &f_id, 0, Essentiality::True, Some(&context)).unwrap();
+        assert_eq!(essential.approx_cardinality(), 8. * 2.);
+        let inhibition =
+            eval_fn_input_monotonic(&graph, &f_id, 0, Monotonicity::Inhibition, Some(&context))
+                .unwrap();
+        assert_eq!(inhibition.approx_cardinality(), 12. * 2.);
+    }
+}
@@ -0,0 +1,152 @@
+use super::colors_from_bdd;
+use crate::sketchbook::model::BinaryOp;
+use crate::sketchbook::properties::static_props::{FolTreeNode, Quantifier};
+use crate::sketchbook::properties::FirstOrderFormula;
+use biodivine_lib_bdd::Bdd;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use std::collections::HashMap;
+
+/// Compute the set of colors of the `graph` for which the first-order `formula` holds.
+///
+/// Function symbols of the formula are interpreted by the uninterpreted functions of the
+/// network (their symbolic function tables). Quantified variables range over Boolean values,
+/// and quantifiers are evaluated by expanding both possible values of the variable.
+pub(super) fn eval_generic_formula(
+    graph: &SymbolicAsyncGraph,
+    formula: &FirstOrderFormula,
+) -> Result<GraphColors, String> {
+    let result_bdd = eval_fol_tree(graph, formula.get_tree(), &mut HashMap::new())?;
+    Ok(colors_from_bdd(graph, &result_bdd))
+}
+
+/// **(internal)** Recursively compute a BDD (over parameters of the `graph`) representing
+/// interpretations that satisfy the formula given by `node`. The `valuation` assigns values
+/// to the currently bound variables.
+fn eval_fol_tree(
+    graph: &SymbolicAsyncGraph,
+    node: &FolTreeNode,
+    valuation: &mut HashMap<String, bool>,
+) -> Result<Bdd, String> {
+    let context = graph.symbolic_context();
+    let bdd = match node {
+        FolTreeNode::Constant(value) => context.mk_constant(*value),
+        FolTreeNode::Var(name) => {
+            let value = valuation
+                .get(name)
+                .ok_or(format!("Variable `{name}` is not bound by a quantifier."))?;
+            context.mk_constant(*value)
+        }
+        FolTreeNode::Function(name, args) => {
+            let parameter = context.find_network_parameter(name).ok_or(format!(
+                "Function symbol `{name}` is not an uninterpreted function of the model."
+            ))?;
+            let arity = context.get_network_parameter_arity(parameter) as usize;
+            if arity != args.len() {
+                return Err(format!(
+                    "Function `{name}` has arity {arity}, but it is applied to {} arguments.",
+                    args.len()
+                ));
+            }
+            let arg_bdds = args
+                .iter()
+                .map(|arg| eval_fol_tree(graph, arg, valuation))
+                .collect::<Result<Vec<Bdd>, String>>()?;
+            let table = context.get_explicit_function_table(parameter);
+            context.mk_function_table_true(table, &arg_bdds)
+        }
+        FolTreeNode::Not(inner) => eval_fol_tree(graph, inner, valuation)?.not(),
+        FolTreeNode::Binary(op, left, right) => {
+            let left = eval_fol_tree(graph, left, valuation)?;
+            let right = eval_fol_tree(graph, right, valuation)?;
+            match op {
+                BinaryOp::And => left.and(&right),
+                BinaryOp::Or => left.or(&right),
+                BinaryOp::Xor => left.xor(&right),
+                BinaryOp::Iff => left.iff(&right),
+                BinaryOp::Imp => left.imp(&right),
+            }
+        }
+        FolTreeNode::Quantified(quantifier, var, inner) => {
+            // remember the value of a possibly shadowed variable, so that it can be restored
+            let shadowed = valuation.get(var).copied();
+            valuation.insert(var.clone(), false);
+            let inner_false = eval_fol_tree(graph, inner, valuation);
+            valuation.insert(var.clone(), true);
+            let inner_true = eval_fol_tree(graph, inner, valuation);
+            match shadowed {
+                Some(value) => valuation.insert(var.clone(), value),
+                None => valuation.remove(var),
+            };
+            let (inner_false, inner_true) = (inner_false?, inner_true?);
+            match quantifier {
+                Quantifier::Exists => inner_false.or(&inner_true),
+                Quantifier::Forall => inner_false.and(&inner_true),
+            }
+        }
+    };
+    Ok(bdd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::eval_generic_formula;
+    use crate::inference::prepare_graph;
+    use crate::sketchbook::model::ModelState;
+    use crate::sketchbook::properties::FirstOrderFormula;
+
+    /// Prepare a test model with `b` updated by an uninterpreted fn `f` of arity 2 (16
+    /// options), and `c` updated by an uninterpreted fn `g` of arity 1 (4 options).
+    /// Variable `a` is an input (2 options).
+    fn prepare_test_model() -> ModelState {
+        let mut model =
+            ModelState::new_from_vars(vec![("a", "a"), ("b", "b"), ("c", "c")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -?? b", "b -?? b", "a -?? c"])
+            .unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 2).unwrap();
+        model.add_uninterpreted_fn_by_str("g", "g", 1).unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        model.set_update_fn(&var_b, "f(a, b)").unwrap();
+        let var_c = model.get_var_id("c").unwrap();
+        model.set_update_fn(&var_c, "g(a)").unwrap();
+        model
+    }
+
+    #[test]
+    /// Test evaluating simple first-order formulas.
+    fn test_eval_generic() {
+        let model = prepare_test_model();
+        let graph = prepare_graph(&model).unwrap();
+        let eval = |formula: &str| {
+            let formula = FirstOrderFormula::try_from_str(formula).unwrap();
+            eval_generic_formula(&graph, &formula)
+                .unwrap()
+                .approx_cardinality()
+        };
+
+        assert_eq!(eval("true"), 16. * 4. * 2.);
+        assert_eq!(eval("false"), 0.);
+        // `f` is symmetric in 8 cases (values for 00, 11, and the same value for 01 and 10)
+        assert_eq!(eval("\\forall x, y: f(x, y) <=> f(y, x)"), 8. * 4. * 2.);
+        // `g` is an identity
+        assert_eq!(eval("\\forall x: g(x) <=> x"), 16. * 2.);
+        // `g` is not a constant (negation or identity)
+        assert_eq!(eval("\\exists x: g(x) ^ g(!x)"), 16. * 2. * 2.);
+        // `f` can be composed with `g`, and nested quantifiers can shadow each other
+        assert_eq!(
+            eval("\\forall x: (\\exists x: g(x)) & f(g(x), x) <=> x"),
+            eval("\\forall x: (\\exists y: g(y)) & f(g(x), x) <=> x")
+        );
+    }
+
+    #[test]
+    /// Test that evaluating formulas invalid with respect to the model fails.
+    fn test_eval_generic_invalid() {
+        let model = prepare_test_model();
+        let graph = prepare_graph(&model).unwrap();
+        let wrong_arity = FirstOrderFormula::try_from_str("\\exists x: g(x, x)").unwrap();
+        assert!(eval_generic_formula(&graph, &wrong_arity).is_err());
+        let unknown_fn = FirstOrderFormula::try_from_str("\\exists x: h(x)").unwrap();
+        assert!(eval_generic_formula(&graph, &unknown_fn).is_err());
+    }
+}
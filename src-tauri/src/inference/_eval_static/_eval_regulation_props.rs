@@ -0,0 +1,180 @@
+use super::_eval_context::context_to_state_bdd;
+use super::{apply_essentiality, apply_monotonicity, colors_from_bdd};
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::{Essentiality, FnTree, Monotonicity};
+use biodivine_lib_bdd::Bdd;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+
+/// Compute the set of colors of the `graph` for which the regulation `input` -> `target`
+/// has the given `Essentiality`.
+///
+/// The regulation is essential if there is a state (satisfying the optional `context`) in
+/// which changing the value of `input` changes the value of the `target`'s update function.
+pub(super) fn eval_regulation_essential(
+    graph: &SymbolicAsyncGraph,
+    input: &VarId,
+    target: &VarId,
+    value: Essentiality,
+    context: Option<&FnTree>,
+) -> Result<GraphColors, String> {
+    let (fn_input_false, fn_input_true) = restrict_fn_update(graph, input, target)?;
+    let context_bdd = context_to_state_bdd(graph, context)?;
+    let essential_bdd = fn_input_false.xor(&fn_input_true).and(&context_bdd);
+    let essential_colors = colors_from_bdd(graph, &essential_bdd);
+    Ok(apply_essentiality(graph, essential_colors, value))
+}
+
+/// Compute the set of colors of the `graph` for which the regulation `input` -> `target`
+/// has the given `Monotonicity`.
+///
+/// The regulation is an activation if there is no state (satisfying the optional `context`)
+/// in which increasing the value of `input` decreases the value of the `target`'s update
+/// function (and vice versa for inhibition). It is dual if both the increase and decrease
+/// can happen.
+pub(super) fn eval_regulation_monotonic(
+    graph: &SymbolicAsyncGraph,
+    input: &VarId,
+    target: &VarId,
+    value: Monotonicity,
+    context: Option<&FnTree>,
+) -> Result<GraphColors, String> {
+    let (fn_input_false, fn_input_true) = restrict_fn_update(graph, input, target)?;
+    let context_bdd = context_to_state_bdd(graph, context)?;
+    let activation_bdd = fn_input_true.and_not(&fn_input_false).and(&context_bdd);
+    let inhibition_bdd = fn_input_false.and_not(&fn_input_true).and(&context_bdd);
+    let can_activate = colors_from_bdd(graph, &activation_bdd);
+    let can_inhibit = colors_from_bdd(graph, &inhibition_bdd);
+    Ok(apply_monotonicity(graph, can_activate, can_inhibit, value))
+}
+
+/// **(internal)** Get the symbolic update function of the `target`, restricted to `input`
+/// being `false` and `true`, respectively.
+fn restrict_fn_update(
+    graph: &SymbolicAsyncGraph,
+    input: &VarId,
+    target: &VarId,
+) -> Result<(Bdd, Bdd), String> {
+    let context = graph.symbolic_context();
+    let input_var = context
+        .find_network_variable(input.as_str())
+        .ok_or(format!(
+            "Variable with ID {input} does not exist in this model."
+        ))?;
+    let target_var = context
+        .find_network_variable(target.as_str())
+        .ok_or(format!(
+            "Variable with ID {target} does not exist in this model."
+        ))?;
+
+    let input_bdd_var = context.get_state_variable(input_var);
+    let fn_update = graph.get_symbolic_fn_update(target_var);
+    Ok((
+        fn_update.var_restrict(input_bdd_var, false),
+        fn_update.var_restrict(input_bdd_var, true),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{eval_regulation_essential, eval_regulation_monotonic};
+    use crate::inference::prepare_graph;
+    use crate::sketchbook::model::{Essentiality, FnTree, ModelState, Monotonicity};
+
+    /// Prepare a test model with `c` regulated by `a` and `b`, without any annotations.
+    /// Both `a` and `b` are inputs (there are 2 options for each of them), and there are
+    /// 16 options for the update function of `c`.
+    fn prepare_test_model() -> ModelState {
+        let mut model =
+            ModelState::new_from_vars(vec![("a", "a"), ("b", "b"), ("c", "c")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -?? c", "b -?? c"])
+            .unwrap();
+        model
+    }
+
+    #[test]
+    /// Test evaluating essentiality of a regulation.
+    fn test_regulation_essential() {
+        let model = prepare_test_model();
+        let graph = prepare_graph(&model).unwrap();
+        let a = model.get_var_id("a").unwrap();
+        let c = model.get_var_id("c").unwrap();
+
+        // there are 4 update fns of `c` that do not depend on `a` (out of 16)
+        let essential =
+            eval_regulation_essential(&graph, &a, &c, Essentiality::True, None).unwrap();
+        assert_eq!(essential.approx_cardinality(), 12. * 4.);
+        let non_essential =
+            eval_regulation_essential(&graph, &a, &c, Essentiality::False, None).unwrap();
+        assert_eq!(non_essential.approx_cardinality(), 4. * 4.);
+        let unknown =
+            eval_regulation_essential(&graph, &a, &c, Essentiality::Unknown, None).unwrap();
+        assert_eq!(unknown.approx_cardinality(), 16. * 4.);
+
+        // `c` does not regulate `a`, so the regulation can never be essential
+        let essential =
+            eval_regulation_essential(&graph, &c, &a, Essentiality::True, None).unwrap();
+        assert_eq!(essential.approx_cardinality(), 0.);
+    }
+
+    #[test]
+    /// Test evaluating monotonicity of a regulation.
+    fn test_regulation_monotonic() {
+        let model = prepare_test_model();
+        let graph = prepare_graph(&model).unwrap();
+        let a = model.get_var_id("a").unwrap();
+        let c = model.get_var_id("c").unwrap();
+
+        // for each value of `b`, there are 3 options monotonous in `a` (9 in total)
+        let activation =
+            eval_regulation_monotonic(&graph, &a, &c, Monotonicity::Activation, None).unwrap();
+        assert_eq!(activation.approx_cardinality(), 9. * 4.);
+        let inhibition =
+            eval_regulation_monotonic(&graph, &a, &c, Monotonicity::Inhibition, None).unwrap();
+        assert_eq!(inhibition.approx_cardinality(), 9. * 4.);
+        // only `xor` and `xnor` are non-monotonous in `a`
+        let dual = eval_regulation_monotonic(&graph, &a, &c, Monotonicity::Dual, None).unwrap();
+        assert_eq!(dual.approx_cardinality(), 2. * 4.);
+        let unknown =
+            eval_regulation_monotonic(&graph, &a, &c, Monotonicity::Unknown, None).unwrap();
+        assert_eq!(unknown.approx_cardinality(), 16. * 4.);
+    }
+
+    #[test]
+    /// Test that evaluating properties of non-existing variables fails.
+    fn test_regulation_invalid_var() {
+        let model = prepare_test_model();
+        let graph = prepare_graph(&model).unwrap();
+        let a = model.get_var_id("a").unwrap();
+        let other_model = ModelState::new_from_vars(vec![("x", "x")]).unwrap();
+        let x = other_model.get_var_id("x").unwrap();
+        assert!(eval_regulation_essential(&graph, &x, &a, Essentiality::True, None).is_err());
+        assert!(eval_regulation_monotonic(&graph, &a, &x, Monotonicity::Dual, None).is_err());
+    }
+
+    #[test]
+    /// Test evaluating regulation properties restricted to a context.
+    fn test_regulation_context() {
+        let model = prepare_test_model();
+        let graph = prepare_graph(&model).unwrap();
+        let a = model.get_var_id("a").unwrap();
+        let c = model.get_var_id("c").unwrap();
+        let context = FnTree::try_from_str("b", &model, None).unwrap();
+
+        // `a` must be essential for `b=1` (2 options for that half of the fn table, 4 for the other)
+        let essential =
+            eval_regulation_essential(&graph, &a, &c, Essentiality::True, Some(&context)).unwrap();
+        assert_eq!(essential.approx_cardinality(), 8. * 4.);
+        // `a` must be activating for `b=1` (3 options for that half of the fn table, 4 for the other)
+        let activation =
+            eval_regulation_monotonic(&graph, &a, &c, Monotonicity::Activation, Some(&context))
+                .unwrap();
+        assert_eq!(activation.approx_cardinality(), 12. * 4.);
+
+        // contradictory context is never satisfied, so `a` can never be essential in it
+        let context = FnTree::try_from_str("b & !b", &model, None).unwrap();
+        let essential =
+            eval_regulation_essential(&graph, &a, &c, Essentiality::True, Some(&context)).unwrap();
+        assert_eq!(essential.approx_cardinality(), 0.);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::sketchbook::model::{Essentiality, Monotonicity};
+use crate::sketchbook::properties::static_props::StatPropertyType;
+use crate::sketchbook::properties::StatProperty;
+use crate::sketchbook::Sketch;
+use biodivine_lib_bdd::Bdd;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+
+/// **(internal)** Conversion of property contexts into symbolic representation.
+mod _eval_context;
+/// **(internal)** Evaluation of the constraints given directly on uninterpreted functions of
+/// the model (argument annotations and expressions).
+mod _eval_fn_constraints;
+/// **(internal)** Evaluation of static properties regarding inputs of uninterpreted functions
+/// (their essentiality and monotonicity).
+mod _eval_fn_input_props;
+/// **(internal)** Evaluation of generic static properties given by first-order formulas.
+mod _eval_generic;
+/// **(internal)** Evaluation of static properties regarding regulations (their essentiality
+/// and monotonicity).
+mod _eval_regulation_props;
+
+pub(crate) use _eval_fn_constraints::eval_fn_constraints;
+use _eval_fn_input_props::{eval_fn_input_essential, eval_fn_input_monotonic};
+use _eval_generic::eval_generic_formula;
+use _eval_regulation_props::{eval_regulation_essential, eval_regulation_monotonic};
+
+/// Evaluate the static property `prop` on the symbolic parametrised network `graph`, and
+/// compute the set of colors (interpretations) for which it holds.
+pub(crate) fn eval_static_prop(
+    prop: &StatProperty,
+    sketch: &Sketch,
+    graph: &SymbolicAsyncGraph,
+) -> Result<GraphColors, String> {
+    let model = sketch.get_model();
+    match prop.get_prop_data() {
+        StatPropertyType::RegulationEssential(p)
+        | StatPropertyType::RegulationEssentialContext(p) => {
+            let input = p.input.as_ref().ok_or("Input variable is not specified.")?;
+            let target = p
+                .target
+                .as_ref()
+                .ok_or("Target variable is not specified.")?;
+            let context = prop.get_context_tree(model)?;
+            eval_regulation_essential(graph, input, target, p.value, context.as_ref())
+        }
+        StatPropertyType::RegulationMonotonic(p)
+        | StatPropertyType::RegulationMonotonicContext(p) => {
+            let input = p.input.as_ref().ok_or("Input variable is not specified.")?;
+            let target = p
+                .target
+                .as_ref()
+                .ok_or("Target variable is not specified.")?;
+            let context = prop.get_context_tree(model)?;
+            eval_regulation_monotonic(graph, input, target, p.value, context.as_ref())
+        }
+        StatPropertyType::FnInputEssential(p) | StatPropertyType::FnInputEssentialContext(p) => {
+            let index = p.input_index.ok_or("Input index is not specified.")?;
+            let target = p
+                .target
+                .as_ref()
+                .ok_or("Target function is not specified.")?;
+            let context = prop.get_context_tree(model)?;
+            eval_fn_input_essential(graph, target, index, p.value, context.as_ref())
+        }
+        StatPropertyType::FnInputMonotonic(p) | StatPropertyType::FnInputMonotonicContext(p) => {
+            let index = p.input_index.ok_or("Input index is not specified.")?;
+            let target = p
+                .target
+                .as_ref()
+                .ok_or("Target function is not specified.")?;
+            let context = prop.get_context_tree(model)?;
+            eval_fn_input_monotonic(graph, target, index, p.value, context.as_ref())
+        }
+        StatPropertyType::GenericStatProp(p) => {
+            p.processed_formula.check_with_model(model)?;
+            eval_generic_formula(graph, &p.processed_formula)
+        }
+    }
+}
+
+/// **(internal)** Convert a BDD over the states and parameters of the `graph` into the set of
+/// colors for which there exists at least one state satisfying the BDD. The result is limited
+/// to the valid colors of the `graph`.
+fn colors_from_bdd(graph: &SymbolicAsyncGraph, bdd: &Bdd) -> GraphColors {
+    let state_variables = graph.symbolic_context().state_variables();
+    let colors_bdd = bdd.exists(state_variables);
+    graph
+        .mk_unit_colors()
+        .copy(colors_bdd)
+        .intersect(graph.unit_colors())
+}
+
+/// **(internal)** Compute the set of colors satisfying the required `Essentiality`, given the
+/// set of colors for which the input is essential.
+fn apply_essentiality(
+    graph: &SymbolicAsyncGraph,
+    essential_colors: GraphColors,
+    value: Essentiality,
+) -> GraphColors {
+    match value {
+        Essentiality::True => essential_colors,
+        Essentiality::False => graph.mk_unit_colors().minus(&essential_colors),
+        Essentiality::Unknown => graph.mk_unit_colors(),
+    }
+}
+
+/// **(internal)** Compute the set of colors satisfying the required `Monotonicity`, given the
+/// sets of colors for which increasing the input can increase (`can_activate`) or decrease
+/// (`can_inhibit`) the function's output.
+fn apply_monotonicity(
+    graph: &SymbolicAsyncGraph,
+    can_activate: GraphColors,
+    can_inhibit: GraphColors,
+    value: Monotonicity,
+) -> GraphColors {
+    match value {
+        Monotonicity::Activation => graph.mk_unit_colors().minus(&can_inhibit),
+        Monotonicity::Inhibition => graph.mk_unit_colors().minus(&can_activate),
+        Monotonicity::Dual => can_activate.intersect(&can_inhibit),
+        Monotonicity::Unknown => graph.mk_unit_colors(),
+    }
+}
@@ -0,0 +1,36 @@
+use crate::inference::PropertyRef;
+use serde::{Deserialize, Serialize};
+
+/// Progress of a running inference, reported before and after each evaluated property.
+///
+/// The progress contains the property that is being (or was just) evaluated, whether its
+/// evaluation already finished, the number of properties evaluated so far (out of all the
+/// properties), and the size of the BDD representing the set of interpretations that satisfy
+/// all the properties evaluated so far.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InferenceProgress {
+    pub property: PropertyRef,
+    pub finished: bool,
+    pub num_evaluated: usize,
+    pub num_total: usize,
+    pub bdd_size: usize,
+}
+
+impl InferenceProgress {
+    /// Create new `InferenceProgress` object.
+    pub fn new(
+        property: PropertyRef,
+        finished: bool,
+        num_evaluated: usize,
+        num_total: usize,
+        bdd_size: usize,
+    ) -> InferenceProgress {
+        InferenceProgress {
+            property,
+            finished,
+            num_evaluated,
+            num_total,
+            bdd_size,
+        }
+    }
+}
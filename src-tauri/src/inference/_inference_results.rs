@@ -0,0 +1,46 @@
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+
+/// Results of the inference on a `Sketch`.
+///
+/// The results contain the symbolic parametrised network (`SymbolicAsyncGraph`) that was
+/// used for the computation, and the set of its colors (interpretations of uninterpreted
+/// functions and unspecified update functions) that satisfy all the properties of the sketch.
+#[derive(Clone)]
+pub struct InferenceResults {
+    graph: SymbolicAsyncGraph,
+    sat_colors: GraphColors,
+}
+
+impl InferenceResults {
+    /// Create new `InferenceResults` from the symbolic graph and the set of satisfying colors.
+    pub fn new(graph: SymbolicAsyncGraph, sat_colors: GraphColors) -> InferenceResults {
+        InferenceResults { graph, sat_colors }
+    }
+
+    /// Get the symbolic parametrised network that was used for the inference.
+    pub fn get_graph(&self) -> &SymbolicAsyncGraph {
+        &self.graph
+    }
+
+    /// Get the set of all satisfying colors (interpretations).
+    pub fn get_sat_colors(&self) -> &GraphColors {
+        &self.sat_colors
+    }
+
+    /// Check whether there is at least one satisfying interpretation.
+    pub fn is_satisfiable(&self) -> bool {
+        !self.sat_colors.is_empty()
+    }
+
+    /// Get the (approximate) number of satisfying interpretations.
+    pub fn num_sat_networks(&self) -> f64 {
+        self.sat_colors.approx_cardinality()
+    }
+
+    /// Get the exact number of satisfying interpretations, as a decimal string (the number can
+    /// be arbitrarily large).
+    pub fn num_sat_networks_exact(&self) -> String {
+        self.sat_colors.exact_cardinality().to_string()
+    }
+}
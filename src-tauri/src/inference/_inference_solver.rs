@@ -0,0 +1,515 @@
+use crate::inference::_eval_dynamic::{eval_dyn_prop, num_hctl_vars, prepare_dyn_prop_formula};
+use crate::inference::_eval_static::{eval_fn_constraints, eval_static_prop};
+use crate::inference::{InferenceProgress, InferenceResults, PropertyRef, SatisfiabilityReport};
+use crate::sketchbook::ids::StatPropertyId;
+use crate::sketchbook::model::ModelState;
+use crate::sketchbook::Sketch;
+use biodivine_hctl_model_checker::mc_utils::get_extended_symbolic_graph;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+
+/// Prepare the symbolic parametrised network (`SymbolicAsyncGraph`) corresponding to the
+/// `ModelState`. The network is built from [ModelState::to_bn], so its colors cover all
+/// uninterpreted functions and unspecified update functions of the model.
+///
+/// The colors are further restricted to the interpretations consistent with the constraints
+/// on uninterpreted functions (argument monotonicity and essentiality, and expressions), which
+/// are not part of the `BooleanNetwork` itself.
+pub fn prepare_graph(model: &ModelState) -> Result<SymbolicAsyncGraph, String> {
+    prepare_extended_graph(model, 0)
+}
+
+/// Prepare the symbolic parametrised network (`SymbolicAsyncGraph`) corresponding to the
+/// `ModelState`, with additional symbolic variables to support model checking of HCTL formulas
+/// with up to `num_hctl_vars` distinct HCTL variables.
+///
+/// See [prepare_graph] for details. Colors computed on graphs with different number of HCTL
+/// variables are not compatible.
+pub fn prepare_extended_graph(
+    model: &ModelState,
+    num_hctl_vars: u16,
+) -> Result<SymbolicAsyncGraph, String> {
+    let bn = model.to_bn();
+    let graph = get_extended_symbolic_graph(&bn, num_hctl_vars)?;
+    let fn_colors = eval_fn_constraints(model, &graph)?;
+    let unit_set = graph.unit_colored_vertices().intersect_colors(&fn_colors);
+    Ok(graph.restrict(&unit_set))
+}
+
+/// Run the inference on the given `Sketch`.
+///
+/// The symbolic parametrised network is built from the sketch's `ModelState`, every static
+/// and dynamic property is evaluated on it, and the resulting color sets are intersected.
+/// The returned `InferenceResults` carry the set of all interpretations satisfying all the
+/// properties, together with the symbolic network itself.
+///
+/// The properties are evaluated in the order of their IDs (static properties first), so that
+/// the process is deterministic.
+pub fn run_inference(sketch: &Sketch) -> Result<InferenceResults, String> {
+    run_inference_with_progress(sketch, |_| Ok(()))
+}
+
+/// Run the inference on the given `Sketch` (see [run_inference]), reporting the progress
+/// through `on_progress` before the evaluation of each property starts, and after it finishes.
+///
+/// If `on_progress` returns an error, the computation is stopped and the error is returned.
+/// This can be used to cancel long-running computations.
+///
+/// The color set of each property is intersected with the results right after it is evaluated
+/// and then dropped, so that only a single intermediate result is kept in memory.
+pub fn run_inference_with_progress<F>(
+    sketch: &Sketch,
+    mut on_progress: F,
+) -> Result<InferenceResults, String>
+where
+    F: FnMut(&InferenceProgress) -> Result<(), String>,
+{
+    let properties = sketch.get_properties();
+    let num_total = properties.num_stat_properties() + properties.num_dyn_properties();
+    let mut sat_colors: Option<GraphColors> = None;
+    let mut num_evaluated = 0;
+    let graph = eval_all_properties(sketch, |graph, prop, colors| {
+        let finished = colors.is_some();
+        if let Some(colors) = colors {
+            let new_colors = match sat_colors.take() {
+                Some(sat_colors) => sat_colors.intersect(&colors),
+                None => colors,
+            };
+            sat_colors = Some(new_colors);
+            num_evaluated += 1;
+        }
+        let bdd_size = sat_colors
+            .as_ref()
+            .unwrap_or(graph.unit_colors())
+            .as_bdd()
+            .size();
+        on_progress(&InferenceProgress::new(
+            prop.clone(),
+            finished,
+            num_evaluated,
+            num_total,
+            bdd_size,
+        ))
+    })?;
+    let sat_colors = sat_colors.unwrap_or(graph.mk_unit_colors());
+    Ok(InferenceResults::new(graph, sat_colors))
+}
+
+/// Compute the `SatisfiabilityReport` for the given `Sketch`, describing how many
+/// interpretations each property admits (alone, and paired with each other property), and
+/// which properties conflict in case there is no interpretation satisfying all of them.
+///
+/// The properties are evaluated the same way as in [run_inference].
+pub fn compute_satisfiability_report(sketch: &Sketch) -> Result<SatisfiabilityReport, String> {
+    let mut prop_colors = Vec::new();
+    let graph = eval_all_properties(sketch, |_, prop, colors| {
+        if let Some(colors) = colors {
+            prop_colors.push((prop.clone(), colors));
+        }
+        Ok(())
+    })?;
+    Ok(SatisfiabilityReport::from_prop_colors(
+        graph.unit_colors(),
+        prop_colors,
+    ))
+}
+
+/// **(internal)** Build the symbolic parametrised network for the `Sketch`, and evaluate all
+/// its properties (in the order of their IDs, static properties first) on it.
+///
+/// Dynamic properties are encoded into HCTL formulas first, so that the network can be
+/// extended with enough symbolic variables to model check all of them.
+///
+/// The `on_property` callback is called before each property is evaluated (with `None`), and
+/// after it is evaluated (with its color set, which is moved to the callback). If it returns
+/// an error, the evaluation is stopped.
+fn eval_all_properties<F>(sketch: &Sketch, mut on_property: F) -> Result<SymbolicAsyncGraph, String>
+where
+    F: FnMut(&SymbolicAsyncGraph, &PropertyRef, Option<GraphColors>) -> Result<(), String>,
+{
+    let mut dyn_props = sketch.get_properties().dyn_props().collect::<Vec<_>>();
+    dyn_props.sort_by_key(|(id, _)| *id);
+    let dyn_formulas = dyn_props
+        .into_iter()
+        .map(|(prop_id, prop)| {
+            prepare_dyn_prop_formula(prop, sketch)
+                .map(|formula| (prop_id, formula))
+                .map_err(|e| format!("Error evaluating dynamic property `{prop_id}`: {e}"))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let num_vars = dyn_formulas
+        .iter()
+        .map(|(_, formula)| num_hctl_vars(formula))
+        .max()
+        .unwrap_or(0);
+
+    let graph = prepare_extended_graph(sketch.get_model(), num_vars as u16)?;
+
+    let mut stat_props = sketch.get_properties().stat_props().collect::<Vec<_>>();
+    stat_props.sort_by_key(|(id, _)| *id);
+    for (prop_id, prop) in stat_props {
+        let prop_ref = PropertyRef::Static(prop_id.clone());
+        on_property(&graph, &prop_ref, None)?;
+        let colors = eval_static_prop(prop, sketch, &graph)
+            .map_err(|e| format!("Error evaluating static property `{prop_id}`: {e}"))?;
+        on_property(&graph, &prop_ref, Some(colors))?;
+    }
+
+    for (prop_id, formula) in dyn_formulas {
+        let prop_ref = PropertyRef::Dynamic(prop_id.clone());
+        on_property(&graph, &prop_ref, None)?;
+        let colors = eval_dyn_prop(&formula, &graph)
+            .map_err(|e| format!("Error evaluating dynamic property `{prop_id}`: {e}"))?;
+        on_property(&graph, &prop_ref, Some(colors))?;
+    }
+
+    Ok(graph)
+}
+
+/// Evaluate a single static property of the `Sketch` (given by its ID), ignoring all the other
+/// properties.
+///
+/// The resulting `InferenceResults` can be used to check whether the property is satisfiable
+/// at all (w.r.t. the model), i.e., whether it contradicts the rest of the model.
+pub fn run_inference_static_prop(
+    sketch: &Sketch,
+    prop_id: &StatPropertyId,
+) -> Result<InferenceResults, String> {
+    let graph = prepare_graph(sketch.get_model())?;
+    let prop = sketch.get_properties().get_stat_prop(prop_id)?;
+    let sat_colors = eval_static_prop(prop, sketch, &graph)
+        .map_err(|e| format!("Error evaluating static property `{prop_id}`: {e}"))?;
+    Ok(InferenceResults::new(graph, sat_colors))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::{
+        compute_satisfiability_report, run_inference, run_inference_static_prop,
+        run_inference_with_progress, PropertyRef,
+    };
+    use crate::sketchbook::ids::{DynPropertyId, StatPropertyId};
+    use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+    use crate::sketchbook::observations::ObservationManager;
+    use crate::sketchbook::properties::PropertyManager;
+    use crate::sketchbook::Sketch;
+
+    /// Prepare a simple sketch with the given regulations and no properties.
+    fn prepare_sketch(regulations: Vec<&str>) -> Sketch {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model.add_multiple_regulations(regulations).unwrap();
+        Sketch::new(
+            model,
+            ObservationManager::new_empty(),
+            PropertyManager::new_empty(),
+        )
+    }
+
+    #[test]
+    /// Test inference on a sketch with fully annotated regulations (single network possible).
+    fn test_inference_annotated() {
+        let sketch = prepare_sketch(vec!["a -> b", "b -| a"]);
+        let results = run_inference(&sketch).unwrap();
+        assert!(results.is_satisfiable());
+        assert_eq!(results.num_sat_networks(), 1.);
+        assert_eq!(results.num_sat_networks_exact(), "1");
+    }
+
+    #[test]
+    /// Test inference on a sketch with non-annotated regulations.
+    fn test_inference_unannotated() {
+        // b can have any update fn of a single input (4 options), a is a constant (2 options)
+        let sketch = prepare_sketch(vec!["a -?? b"]);
+        let results = run_inference(&sketch).unwrap();
+        assert_eq!(results.num_sat_networks(), 8.);
+    }
+
+    #[test]
+    /// Test inference on a sketch with update function containing an uninterpreted function.
+    fn test_inference_uninterpreted_fn() {
+        let mut sketch = prepare_sketch(vec!["a -> b", "b -?? a"]);
+        let mut model = sketch.get_model().clone();
+        model.add_uninterpreted_fn_by_str("f", "f", 1).unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        model.set_update_fn(&var_b, "f(a)").unwrap();
+        sketch = Sketch::new(
+            model,
+            ObservationManager::new_empty(),
+            PropertyManager::new_empty(),
+        );
+
+        // `f` must be an identity (essential activation), `a` can be any fn of b (4 options)
+        let results = run_inference(&sketch).unwrap();
+        assert_eq!(results.num_sat_networks(), 4.);
+    }
+
+    #[test]
+    /// Test that argument annotations and expressions of uninterpreted functions restrict the
+    /// admissible interpretations.
+    fn test_inference_uninterpreted_fn_constraints() {
+        // `c` is updated by `f(a, b)` with 16 options for `f`, `a` and `b` are constants (4 options)
+        let mut model =
+            ModelState::new_from_vars(vec![("a", "a"), ("b", "b"), ("c", "c")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -?? c", "b -?? c"])
+            .unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 2).unwrap();
+        let var_c = model.get_var_id("c").unwrap();
+        model.set_update_fn(&var_c, "f(a, b)").unwrap();
+        let mk_sketch = |model: &ModelState| {
+            Sketch::new(
+                model.clone(),
+                ObservationManager::new_empty(),
+                PropertyManager::new_empty(),
+            )
+        };
+        let results = run_inference(&mk_sketch(&model)).unwrap();
+        assert_eq!(results.num_sat_networks(), 16. * 4.);
+
+        // 9 functions never decrease when the first argument increases
+        let mut model_monotonic = model.clone();
+        model_monotonic
+            .set_uninterpreted_fn_monotonicity_by_str("f", Monotonicity::Activation, 0)
+            .unwrap();
+        let results = run_inference(&mk_sketch(&model_monotonic)).unwrap();
+        assert_eq!(results.num_sat_networks(), 9. * 4.);
+
+        // 12 functions depend on the first argument
+        let mut model_essential = model.clone();
+        model_essential
+            .set_uninterpreted_fn_essentiality_by_str("f", Essentiality::True, 0)
+            .unwrap();
+        let results = run_inference(&mk_sketch(&model_essential)).unwrap();
+        assert_eq!(results.num_sat_networks(), 12. * 4.);
+
+        // the expression fully determines `f`
+        let mut model_expression = model.clone();
+        model_expression
+            .set_uninterpreted_fn_expression_by_str("f", "var0")
+            .unwrap();
+        let results = run_inference(&mk_sketch(&model_expression)).unwrap();
+        assert_eq!(results.num_sat_networks(), 4.);
+    }
+
+    #[test]
+    /// Test that static regulation properties and dynamic properties are handled together.
+    fn test_inference_properties() {
+        let sketch = prepare_sketch(vec!["a -> b"]);
+        let mut properties = PropertyManager::new_empty();
+        let a = sketch.get_model().get_var_id("a").unwrap();
+        let b = sketch.get_model().get_var_id("b").unwrap();
+        let prop_id = StatPropertyId::new("essentiality_a_b").unwrap();
+        properties
+            .add_stat_reg_essential(prop_id, "p", Some(a), Some(b), Essentiality::True)
+            .unwrap();
+        let sketch = Sketch::new(
+            sketch.get_model().clone(),
+            ObservationManager::new_empty(),
+            properties.clone(),
+        );
+        // b is fully determined, a is a constant (2 options)
+        let results = run_inference(&sketch).unwrap();
+        assert_eq!(results.num_sat_networks(), 2.);
+
+        let dyn_prop_id = DynPropertyId::new("fp").unwrap();
+        properties
+            .add_dyn_generic(dyn_prop_id, "fp", "3{x}: @{x}: AX {x}")
+            .unwrap();
+        let sketch = Sketch::new(
+            sketch.get_model().clone(),
+            ObservationManager::new_empty(),
+            properties,
+        );
+        // `a` must be constant true or false for `b` to have a fixed point
+        let results = run_inference(&sketch).unwrap();
+        assert_eq!(results.num_sat_networks(), 2.);
+
+        // propositions of HCTL formulas must be valid variables
+        let dyn_prop_id = DynPropertyId::new("invalid").unwrap();
+        let mut properties = sketch.get_properties().clone();
+        properties
+            .add_dyn_generic(dyn_prop_id, "invalid", "3{x}: @{x}: c")
+            .unwrap();
+        let sketch = Sketch::new(
+            sketch.get_model().clone(),
+            ObservationManager::new_empty(),
+            properties,
+        );
+        assert!(run_inference(&sketch).is_err());
+    }
+
+    #[test]
+    /// Test evaluating satisfiability of single static properties that contradict the model.
+    fn test_inference_static_prop_satisfiable() {
+        let sketch = prepare_sketch(vec!["a -> b"]);
+        let a = sketch.get_model().get_var_id("a").unwrap();
+        let b = sketch.get_model().get_var_id("b").unwrap();
+        let mut properties = PropertyManager::new_empty();
+        let id_ok = StatPropertyId::new("monotonicity_ok").unwrap();
+        let id_conflict = StatPropertyId::new("monotonicity_conflict").unwrap();
+        properties
+            .add_stat_reg_monotonic(
+                id_ok.clone(),
+                "p1",
+                Some(a.clone()),
+                Some(b.clone()),
+                Monotonicity::Activation,
+            )
+            .unwrap();
+        properties
+            .add_stat_reg_monotonic(
+                id_conflict.clone(),
+                "p2",
+                Some(a),
+                Some(b),
+                Monotonicity::Inhibition,
+            )
+            .unwrap();
+        let sketch = Sketch::new(
+            sketch.get_model().clone(),
+            ObservationManager::new_empty(),
+            properties,
+        );
+
+        // activation is consistent with the model, but inhibition contradicts it
+        let results = run_inference_static_prop(&sketch, &id_ok).unwrap();
+        assert!(results.is_satisfiable());
+        let results = run_inference_static_prop(&sketch, &id_conflict).unwrap();
+        assert!(!results.is_satisfiable());
+        let results = run_inference(&sketch).unwrap();
+        assert!(!results.is_satisfiable());
+    }
+
+    #[test]
+    /// Test computing the satisfiability report, including the minimal unsatisfiable subset.
+    fn test_satisfiability_report() {
+        let sketch = prepare_sketch(vec!["a -?? b"]);
+        let a = sketch.get_model().get_var_id("a").unwrap();
+        let b = sketch.get_model().get_var_id("b").unwrap();
+        let mut properties = PropertyManager::new_empty();
+        let id_act = StatPropertyId::new("activation").unwrap();
+        let id_inh = StatPropertyId::new("inhibition").unwrap();
+        let id_fp = DynPropertyId::new("fixed_point").unwrap();
+        properties
+            .add_stat_reg_monotonic(
+                id_act.clone(),
+                "p1",
+                Some(a.clone()),
+                Some(b.clone()),
+                Monotonicity::Activation,
+            )
+            .unwrap();
+        properties
+            .add_stat_reg_monotonic(
+                id_inh.clone(),
+                "p2",
+                Some(a),
+                Some(b),
+                Monotonicity::Inhibition,
+            )
+            .unwrap();
+        properties
+            .add_dyn_generic(id_fp.clone(), "p3", "3{x}: @{x}: AX {x}")
+            .unwrap();
+        let sketch = Sketch::new(
+            sketch.get_model().clone(),
+            ObservationManager::new_empty(),
+            properties,
+        );
+        let (act, inh, fp) = (
+            PropertyRef::Static(id_act),
+            PropertyRef::Static(id_inh),
+            PropertyRef::Dynamic(id_fp),
+        );
+
+        // `b` can be any fn of `a` (4 options), `a` is a constant (2 options)
+        let report = compute_satisfiability_report(&sketch).unwrap();
+        assert_eq!(
+            report.get_properties(),
+            &vec![act.clone(), inh.clone(), fp.clone()]
+        );
+        assert_eq!(report.num_unconstrained(), 8.);
+        // activation excludes negation, inhibition excludes identity, any option has a fixed point
+        assert_eq!(report.num_admitted(&act).unwrap(), 6.);
+        assert_eq!(report.num_admitted(&inh).unwrap(), 6.);
+        assert_eq!(report.num_admitted(&fp).unwrap(), 8.);
+        // only constant functions are both activating and inhibiting
+        assert_eq!(report.num_admitted_pair(&act, &inh).unwrap(), 4.);
+        assert_eq!(report.num_admitted_pair(&act, &fp).unwrap(), 6.);
+        assert!(report.is_satisfiable());
+        assert!(report.get_unsat_core().is_none());
+
+        // essential regulation conflicts with both monotonicities together
+        let id_ess = StatPropertyId::new("essential").unwrap();
+        let ess = PropertyRef::Static(id_ess.clone());
+        let mut properties = sketch.get_properties().clone();
+        let a = sketch.get_model().get_var_id("a").unwrap();
+        let b = sketch.get_model().get_var_id("b").unwrap();
+        properties
+            .add_stat_reg_essential(id_ess, "p4", Some(a), Some(b), Essentiality::True)
+            .unwrap();
+        let sketch = Sketch::new(
+            sketch.get_model().clone(),
+            ObservationManager::new_empty(),
+            properties,
+        );
+        let report = compute_satisfiability_report(&sketch).unwrap();
+        assert!(!report.is_satisfiable());
+        assert_eq!(report.num_sat_networks(), 0.);
+        assert_eq!(report.get_unsat_core(), Some(&vec![act, ess, inh]));
+    }
+
+    #[test]
+    /// Test that progress is reported before and after each property, and that inference can be
+    /// cancelled.
+    fn test_inference_progress() {
+        let sketch = prepare_sketch(vec!["a -?? b"]);
+        let mut properties = PropertyManager::new_empty();
+        properties
+            .add_dyn_generic(DynPropertyId::new("p1").unwrap(), "p1", "true")
+            .unwrap();
+        properties
+            .add_dyn_generic(
+                DynPropertyId::new("p2").unwrap(),
+                "p2",
+                "3{x}: @{x}: AX {x}",
+            )
+            .unwrap();
+        let sketch = Sketch::new(
+            sketch.get_model().clone(),
+            ObservationManager::new_empty(),
+            properties,
+        );
+
+        let mut reported = Vec::new();
+        let results = run_inference_with_progress(&sketch, |progress| {
+            reported.push((
+                progress.property.clone(),
+                progress.finished,
+                progress.num_evaluated,
+            ));
+            assert_eq!(progress.num_total, 2);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(results.num_sat_networks(), 8.);
+        let p1 = PropertyRef::Dynamic(DynPropertyId::new("p1").unwrap());
+        let p2 = PropertyRef::Dynamic(DynPropertyId::new("p2").unwrap());
+        let expected = vec![
+            (p1.clone(), false, 0),
+            (p1, true, 1),
+            (p2.clone(), false, 1),
+            (p2, true, 2),
+        ];
+        assert_eq!(reported, expected);
+
+        // computation stops before the first property is evaluated
+        let mut num_calls = 0;
+        let result = run_inference_with_progress(&sketch, |_| {
+            num_calls += 1;
+            Err("Cancelled.".to_string())
+        });
+        assert_eq!(result.err(), Some("Cancelled.".to_string()));
+        assert_eq!(num_calls, 1);
+    }
+}
@@ -0,0 +1,342 @@
+use crate::inference::InferenceResults;
+use crate::sketchbook::model::{ModelState, Monotonicity};
+use biodivine_lib_bdd::{Bdd, BddVariable};
+use biodivine_lib_param_bn::symbolic_async_graph::{RegulationConstraint, SymbolicContext};
+use serde::{Deserialize, Serialize};
+
+/// How often does some feature (e.g., essentiality of an input) hold across all the
+/// consistent interpretations.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Occurrence {
+    Always,
+    Sometimes,
+    Never,
+}
+
+/// Summary of how a single input (an argument of an uninterpreted function, or a regulator
+/// of a variable) behaves across all the consistent interpretations.
+///
+/// - `essential` says whether the input is essential
+/// - `activating` and `inhibiting` say whether the input is positively/negatively monotonic
+///   (note that non-essential inputs are both)
+/// - `monotonicity` is the most specific monotonicity valid in all the interpretations, with
+///   `Unknown` if there is none (or if the input is never essential)
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct InputSummary {
+    pub essential: Occurrence,
+    pub activating: Occurrence,
+    pub inhibiting: Occurrence,
+    pub monotonicity: Monotonicity,
+}
+
+/// Summary of an uninterpreted function across all the consistent interpretations.
+///
+/// - `id` is the ID of the function
+/// - `num_instantiations` is the (approximate) number of distinct instantiations of the
+///   function that appear in at least one consistent interpretation
+/// - `is_unique` says whether the function is uniquely determined by the consistent
+///   interpretations (i.e., there is a single instantiation)
+/// - `arguments` summarize individual arguments of the function (in order)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UninterpretedFnSummary {
+    pub id: String,
+    pub num_instantiations: f64,
+    pub is_unique: bool,
+    pub arguments: Vec<InputSummary>,
+}
+
+/// Summary of a regulation across all the consistent interpretations.
+///
+/// - `regulator` and `target` are IDs of the variables of the regulation
+/// - `input` summarizes the regulator as an input of the target's update function
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegulationSummary {
+    pub regulator: String,
+    pub target: String,
+    pub input: InputSummary,
+}
+
+/// Summary of the uncertainty that remains in the set of consistent interpretations (colors),
+/// computed for every uninterpreted function and every regulation of the model.
+///
+/// The summary shows which parts of the model are already fixed by the properties of
+/// the sketch, and which are still undetermined.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InterpretationSummary {
+    uninterpreted_fns: Vec<UninterpretedFnSummary>,
+    regulations: Vec<RegulationSummary>,
+}
+
+impl InterpretationSummary {
+    /// Compute the summary of the inference `results`, which were computed for a sketch with
+    /// the given `model`.
+    ///
+    /// Functions and regulations are summarized in the same order as they are in the model.
+    pub fn from_results(
+        results: &InferenceResults,
+        model: &ModelState,
+    ) -> Result<InterpretationSummary, String> {
+        if !results.is_satisfiable() {
+            return Err("There are no consistent interpretations to summarize.".to_string());
+        }
+        let graph = results.get_graph();
+        let context = graph.symbolic_context();
+        let sat_colors = results.get_sat_colors().as_bdd();
+
+        let mut uninterpreted_fns = Vec::new();
+        for (fn_id, _) in model.uninterpreted_fns() {
+            let param = context
+                .find_network_parameter(fn_id.as_str())
+                .ok_or(format!(
+                    "Function `{fn_id}` is not in the symbolic context."
+                ))?;
+            let table = context.get_explicit_function_table(param);
+            let rows = table.into_iter().collect::<Vec<_>>();
+
+            let fn_vars = table.symbolic_variables();
+            let other_vars = context
+                .parameter_variables()
+                .iter()
+                .filter(|var| !fn_vars.contains(var))
+                .copied()
+                .collect::<Vec<_>>();
+            // the projection does not depend on all the remaining BDD variables
+            let num_free_vars = context.bdd_variable_set().num_vars() as usize - fn_vars.len();
+            let projected = sat_colors.exists(&other_vars);
+            let num_instantiations = projected.cardinality() / 2f64.powi(num_free_vars as i32);
+
+            let arguments = (0..table.arity as usize)
+                .map(|i| {
+                    let essential = mk_table_observability(context, &rows, i);
+                    let activating = mk_table_monotonicity(context, &rows, i, true);
+                    let inhibiting = mk_table_monotonicity(context, &rows, i, false);
+                    InputSummary::new(sat_colors, &essential, &activating, &inhibiting)
+                })
+                .collect();
+
+            uninterpreted_fns.push(UninterpretedFnSummary {
+                id: fn_id.to_string(),
+                num_instantiations,
+                is_unique: num_instantiations == 1.,
+                arguments,
+            });
+        }
+
+        let mut regulations = Vec::new();
+        for regulation in model.regulations() {
+            let find_var = |var_id: &str| {
+                context.find_network_variable(var_id).ok_or(format!(
+                    "Variable `{var_id}` is not in the symbolic context."
+                ))
+            };
+            let regulator = find_var(regulation.get_regulator().as_str())?;
+            let target = find_var(regulation.get_target().as_str())?;
+            let fn_is_true = graph.get_symbolic_fn_update(target);
+
+            let essential = RegulationConstraint::mk_observability(context, fn_is_true, regulator);
+            let activating = RegulationConstraint::mk_activation(context, fn_is_true, regulator);
+            let inhibiting = RegulationConstraint::mk_inhibition(context, fn_is_true, regulator);
+            regulations.push(RegulationSummary {
+                regulator: regulation.get_regulator().to_string(),
+                target: regulation.get_target().to_string(),
+                input: InputSummary::new(sat_colors, &essential, &activating, &inhibiting),
+            });
+        }
+
+        Ok(InterpretationSummary {
+            uninterpreted_fns,
+            regulations,
+        })
+    }
+
+    /// Summaries of all uninterpreted functions.
+    pub fn get_uninterpreted_fns(&self) -> &Vec<UninterpretedFnSummary> {
+        &self.uninterpreted_fns
+    }
+
+    /// Summaries of all regulations.
+    pub fn get_regulations(&self) -> &Vec<RegulationSummary> {
+        &self.regulations
+    }
+
+    /// Find the summary of an uninterpreted function with given ID.
+    pub fn get_uninterpreted_fn(&self, fn_id: &str) -> Option<&UninterpretedFnSummary> {
+        self.uninterpreted_fns.iter().find(|f| f.id == fn_id)
+    }
+
+    /// Find the summary of a regulation between given variables.
+    pub fn get_regulation(&self, regulator: &str, target: &str) -> Option<&RegulationSummary> {
+        self.regulations
+            .iter()
+            .find(|r| r.regulator == regulator && r.target == target)
+    }
+}
+
+impl InputSummary {
+    /// **(internal)** Summarize an input given the BDD of all consistent interpretations
+    /// (`sat_colors`), and BDDs of interpretations where the input is essential, activating,
+    /// and inhibiting, respectively.
+    fn new(sat_colors: &Bdd, essential: &Bdd, activating: &Bdd, inhibiting: &Bdd) -> InputSummary {
+        let essential = Occurrence::of(sat_colors, essential);
+        let activating = Occurrence::of(sat_colors, activating);
+        let inhibiting = Occurrence::of(sat_colors, inhibiting);
+        let monotonicity = match (activating, inhibiting) {
+            (Occurrence::Always, Occurrence::Always) => Monotonicity::Unknown,
+            (Occurrence::Always, _) => Monotonicity::Activation,
+            (_, Occurrence::Always) => Monotonicity::Inhibition,
+            (Occurrence::Never, Occurrence::Never) => Monotonicity::Dual,
+            _ => Monotonicity::Unknown,
+        };
+        InputSummary {
+            essential,
+            activating,
+            inhibiting,
+            monotonicity,
+        }
+    }
+}
+
+impl Occurrence {
+    /// **(internal)** Decide how often does a feature (given by the `feature` BDD) hold
+    /// in the set of `sat_colors`.
+    fn of(sat_colors: &Bdd, feature: &Bdd) -> Occurrence {
+        let with_feature = sat_colors.and(feature);
+        if with_feature.is_false() {
+            Occurrence::Never
+        } else if &with_feature == sat_colors {
+            Occurrence::Always
+        } else {
+            Occurrence::Sometimes
+        }
+    }
+}
+
+/// **(internal)** Pairs of rows of a function table that differ only in the `input`-th argument,
+/// in the form of `(row where input is false, row where input is true)`.
+fn table_row_pairs(
+    rows: &[(Vec<bool>, BddVariable)],
+    input: usize,
+) -> Vec<(BddVariable, BddVariable)> {
+    rows.iter()
+        .filter(|(args, _)| !args[input])
+        .map(|(args, var_low)| {
+            let mut args_high = args.clone();
+            args_high[input] = true;
+            // each valuation of arguments is present in the table
+            let (_, var_high) = rows.iter().find(|(a, _)| a == &args_high).unwrap();
+            (*var_low, *var_high)
+        })
+        .collect()
+}
+
+/// **(internal)** Compute a BDD representing all instantiations of the function (given by its
+/// table `rows`) where the `input`-th argument is essential.
+fn mk_table_observability(
+    context: &SymbolicContext,
+    rows: &[(Vec<bool>, BddVariable)],
+    input: usize,
+) -> Bdd {
+    let vars = context.bdd_variable_set();
+    table_row_pairs(rows, input)
+        .into_iter()
+        .fold(vars.mk_false(), |acc, (low, high)| {
+            acc.or(&vars.mk_var(low).xor(&vars.mk_var(high)))
+        })
+}
+
+/// **(internal)** Compute a BDD representing all instantiations of the function (given by its
+/// table `rows`) where the `input`-th argument is positively (if `positive` is true) or
+/// negatively monotonic.
+fn mk_table_monotonicity(
+    context: &SymbolicContext,
+    rows: &[(Vec<bool>, BddVariable)],
+    input: usize,
+    positive: bool,
+) -> Bdd {
+    let vars = context.bdd_variable_set();
+    table_row_pairs(rows, input)
+        .into_iter()
+        .fold(vars.mk_true(), |acc, (low, high)| {
+            let (low, high) = (vars.mk_var(low), vars.mk_var(high));
+            if positive {
+                acc.and(&low.imp(&high))
+            } else {
+                acc.and(&high.imp(&low))
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::{run_inference, InterpretationSummary, Occurrence};
+    use crate::sketchbook::ids::StatPropertyId;
+    use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+    use crate::sketchbook::observations::ObservationManager;
+    use crate::sketchbook::properties::PropertyManager;
+    use crate::sketchbook::Sketch;
+
+    #[test]
+    /// Test summarizing the interpretations of a model with an uninterpreted function.
+    fn test_interpretation_summary() {
+        // `b` is updated by `f(a, b)`, `a` has an unspecified update function
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -> b", "b ->? b", "b -? a"])
+            .unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 2).unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        model.set_update_fn(&var_b, "f(a, b)").unwrap();
+
+        // the first argument of `f` must be an essential activator
+        let mut properties = PropertyManager::new_empty();
+        let fn_id = model.get_uninterpreted_fn_id("f").unwrap();
+        let (prop_1, prop_2) = (StatPropertyId::new("p1"), StatPropertyId::new("p2"));
+        let (value_1, value_2) = (Monotonicity::Activation, Essentiality::True);
+        properties
+            .add_stat_fn_input_monotonic(
+                prop_1.unwrap(),
+                "p1",
+                Some(0),
+                Some(fn_id.clone()),
+                value_1,
+            )
+            .unwrap();
+        properties
+            .add_stat_fn_input_essential(prop_2.unwrap(), "p2", Some(0), Some(fn_id), value_2)
+            .unwrap();
+
+        let sketch = Sketch::new(model, ObservationManager::new_empty(), properties);
+        let results = run_inference(&sketch).unwrap();
+        let summary = InterpretationSummary::from_results(&results, sketch.get_model()).unwrap();
+
+        // f(a, b) is either `a`, `a & b`, `a | b` (b must be monotonic due to regulation)
+        let fn_summary = summary.get_uninterpreted_fn("f").unwrap();
+        assert_eq!(fn_summary.num_instantiations, 3.);
+        assert!(!fn_summary.is_unique);
+        let arg_a = &fn_summary.arguments[0];
+        assert_eq!(arg_a.essential, Occurrence::Always);
+        assert_eq!(arg_a.monotonicity, Monotonicity::Activation);
+        let arg_b = &fn_summary.arguments[1];
+        assert_eq!(arg_b.essential, Occurrence::Sometimes);
+        assert_eq!(arg_b.activating, Occurrence::Always);
+        assert_eq!(arg_b.monotonicity, Monotonicity::Activation);
+
+        // `b` regulates `a` (essential, unknown sign), so the update of `a` is `!b` or `b`
+        let reg_summary = summary.get_regulation("b", "a").unwrap();
+        assert_eq!(reg_summary.input.essential, Occurrence::Always);
+        assert_eq!(reg_summary.input.activating, Occurrence::Sometimes);
+        assert_eq!(reg_summary.input.monotonicity, Monotonicity::Unknown);
+        let reg_summary = summary.get_regulation("b", "b").unwrap();
+        assert_eq!(reg_summary.input.essential, Occurrence::Sometimes);
+        assert_eq!(reg_summary.input.monotonicity, Monotonicity::Activation);
+
+        // the summary is sent to the frontend as JSON, including the derived uniqueness flag
+        let json = serde_json::to_value(&summary).unwrap();
+        let fn_json = &json["uninterpreted_fns"][0];
+        assert_eq!(fn_json["id"], "f");
+        assert_eq!(fn_json["num_instantiations"], 3.);
+        assert_eq!(fn_json["is_unique"], false);
+        assert_eq!(fn_json["arguments"][0]["essential"], "Always");
+        assert_eq!(json["regulations"].as_array().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,264 @@
+use crate::inference::InferenceResults;
+use crate::sketchbook::model::{BnetExportMode, ModelState};
+use biodivine_lib_bdd::BddValuation;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+use biodivine_lib_param_bn::FnUpdate;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File formats into which the concrete networks can be exported.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum NetworkFormat {
+    Aeon,
+    Bnet,
+}
+
+impl NetworkFormat {
+    /// File extension corresponding to the format.
+    pub fn extension(&self) -> &str {
+        match self {
+            NetworkFormat::Aeon => "aeon",
+            NetworkFormat::Bnet => "bnet",
+        }
+    }
+
+    /// Convert a (fully specified) `model` into a string in this format.
+    pub fn model_to_string(&self, model: &ModelState) -> Result<String, String> {
+        match self {
+            NetworkFormat::Aeon => Ok(model.to_aeon()),
+            NetworkFormat::Bnet => model.to_bnet(BnetExportMode::Strict),
+        }
+    }
+}
+
+/// Obtaining concrete Boolean networks from the inference results.
+impl InferenceResults {
+    /// Enumerate the first `num_networks` satisfying interpretations (or all of them, if there are
+    /// fewer), and instantiate each of them as a concrete network.
+    ///
+    /// The `model` must be the model of the sketch for which the inference was computed. See
+    /// [Self::instantiate_model] for details on how the networks are created.
+    pub fn enumerate_networks(
+        &self,
+        model: &ModelState,
+        num_networks: usize,
+    ) -> Result<Vec<ModelState>, String> {
+        let mut remaining = self.get_sat_colors().clone();
+        let mut networks = Vec::new();
+        while networks.len() < num_networks && !remaining.is_empty() {
+            let color = remaining.pick_singleton();
+            // the singleton set is not empty, so there is a witness
+            let valuation = color.as_bdd().sat_witness().unwrap();
+            networks.push(self.instantiate_model(model, &valuation)?);
+            remaining = remaining.minus(&color);
+        }
+        Ok(networks)
+    }
+
+    /// Sample `num_networks` satisfying interpretations uniformly at random (with replacement),
+    /// and instantiate each of them as a concrete network. The random generator is initialized
+    /// with the given `seed`, so that the sampling is reproducible.
+    ///
+    /// The `model` must be the model of the sketch for which the inference was computed. See
+    /// [Self::instantiate_model] for details on how the networks are created.
+    pub fn sample_networks(
+        &self,
+        model: &ModelState,
+        num_networks: usize,
+        seed: u64,
+    ) -> Result<Vec<ModelState>, String> {
+        if !self.is_satisfiable() {
+            return Err("There are no satisfying networks to sample from.".to_string());
+        }
+        // the set of colors does not depend on state variables, so sampling valuations of the
+        // whole BDD uniformly also samples the colors uniformly
+        let colors_bdd = self.get_sat_colors().as_bdd();
+        let mut sampler = colors_bdd.mk_uniform_valuation_sampler(StdRng::seed_from_u64(seed));
+        (0..num_networks)
+            .map(|_| {
+                // the BDD is not empty, so there always is a sample
+                let valuation = colors_bdd.random_valuation_sample(&mut sampler).unwrap();
+                self.instantiate_model(model, &valuation)
+            })
+            .collect()
+    }
+
+    /// Create a concrete network (as a new `ModelState`) from the `model`, by fixing the
+    /// interpretation given by the `valuation` of the symbolic parameters.
+    ///
+    /// Every application of an uninterpreted function in each update function is substituted
+    /// with a concrete expression, and unspecified update functions are fully instantiated
+    /// (both in a disjunctive normal form). Uninterpreted functions are then removed from the
+    /// model. Variables, regulations, and layouts are kept.
+    pub fn instantiate_model(
+        &self,
+        model: &ModelState,
+        valuation: &BddValuation,
+    ) -> Result<ModelState, String> {
+        let graph = self.get_graph();
+        let context = graph.symbolic_context();
+        let bn = model.to_bn();
+
+        let mut instantiated = model.clone();
+        for bn_var in bn.variables() {
+            let var_id = model.get_var_id(bn.get_variable_name(bn_var))?;
+            let fn_update = match bn.get_update_function(bn_var) {
+                Some(fn_update) => substitute_params(graph, valuation, fn_update),
+                None => context
+                    .mk_instantiated_fn_update(valuation, graph.get_symbolic_fn_update(bn_var)),
+            };
+            instantiated.set_update_fn(&var_id, &fn_update.to_string(&bn))?;
+        }
+
+        // uninterpreted fns are not used anymore, but their expressions might reference each other
+        let fn_ids = model
+            .uninterpreted_fns()
+            .map(|(fn_id, _)| fn_id.clone())
+            .collect::<Vec<_>>();
+        for fn_id in fn_ids.iter() {
+            instantiated.set_uninterpreted_fn_expression(fn_id, "")?;
+        }
+        for fn_id in fn_ids.iter() {
+            instantiated.remove_uninterpreted_fn(fn_id)?;
+        }
+        Ok(instantiated)
+    }
+}
+
+/// Export each of the `networks` into a file in the `dir_path` directory, using the given
+/// `format`. The files are named `network_1`, `network_2`, and so on (with the format's
+/// extension). Returns the paths of the created files.
+pub fn export_networks(
+    networks: &[ModelState],
+    dir_path: &str,
+    format: NetworkFormat,
+) -> Result<Vec<PathBuf>, String> {
+    networks
+        .iter()
+        .enumerate()
+        .map(|(i, network)| {
+            let file_name = format!("network_{}.{}", i + 1, format.extension());
+            let path = Path::new(dir_path).join(file_name);
+            let contents = format.model_to_string(network)?;
+            let mut file = File::create(&path).map_err(|e| e.to_string())?;
+            file.write_all(contents.as_bytes())
+                .map_err(|e| e.to_string())?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// **(internal)** Recursively substitute every application of an uninterpreted function
+/// (parameter) in `fn_update` with its instantiation given by the `valuation`.
+fn substitute_params(
+    graph: &SymbolicAsyncGraph,
+    valuation: &BddValuation,
+    fn_update: &FnUpdate,
+) -> FnUpdate {
+    match fn_update {
+        FnUpdate::Const(_) | FnUpdate::Var(_) => fn_update.clone(),
+        FnUpdate::Param(param, args) => {
+            let args = args
+                .iter()
+                .map(|arg| substitute_params(graph, valuation, arg))
+                .collect::<Vec<_>>();
+            let context = graph.symbolic_context();
+            let bdd = context.instantiate_uninterpreted_function(valuation, *param, &args);
+            FnUpdate::build_from_bdd(context, &bdd)
+        }
+        FnUpdate::Not(inner) => FnUpdate::Not(Box::new(substitute_params(graph, valuation, inner))),
+        FnUpdate::Binary(op, left, right) => FnUpdate::Binary(
+            *op,
+            Box::new(substitute_params(graph, valuation, left)),
+            Box::new(substitute_params(graph, valuation, right)),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::{export_networks, run_inference, NetworkFormat};
+    use crate::sketchbook::model::ModelState;
+    use crate::sketchbook::observations::ObservationManager;
+    use crate::sketchbook::properties::PropertyManager;
+    use crate::sketchbook::Sketch;
+    use std::collections::HashSet;
+
+    /// Prepare a sketch where `b` is updated by `f(a) & b` with an uninterpreted `f` (4 options),
+    /// and `a` has an unspecified update function depending on `b` (2 monotonic options).
+    fn prepare_sketch() -> Sketch {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -?? b", "b -? b", "b -> a"])
+            .unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 1).unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        model.set_update_fn(&var_b, "f(a) & b").unwrap();
+        Sketch::new(
+            model,
+            ObservationManager::new_empty(),
+            PropertyManager::new_empty(),
+        )
+    }
+
+    #[test]
+    /// Test enumerating all the concrete networks.
+    fn test_enumerate_networks() {
+        let sketch = prepare_sketch();
+        let results = run_inference(&sketch).unwrap();
+        let num_sat = results.num_sat_networks() as usize;
+
+        let networks = results.enumerate_networks(sketch.get_model(), 100).unwrap();
+        assert_eq!(networks.len(), num_sat);
+        let expressions = networks
+            .iter()
+            .map(|network| {
+                // all the uninterpreted fns must be substituted
+                assert_eq!(network.num_uninterpreted_fns(), 0);
+                let var_a = network.get_var_id("a").unwrap();
+                let var_b = network.get_var_id("b").unwrap();
+                let fn_a = network.get_update_fn_string(&var_a).unwrap();
+                let fn_b = network.get_update_fn_string(&var_b).unwrap();
+                assert_eq!(fn_a, "b");
+                format!("{fn_a}; {fn_b}")
+            })
+            .collect::<HashSet<_>>();
+        // all the networks are different
+        assert_eq!(expressions.len(), num_sat);
+
+        let networks = results.enumerate_networks(sketch.get_model(), 1).unwrap();
+        assert_eq!(networks.len(), 1);
+    }
+
+    #[test]
+    /// Test sampling concrete networks, and exporting them into files.
+    fn test_sample_and_export_networks() {
+        let sketch = prepare_sketch();
+        let results = run_inference(&sketch).unwrap();
+        let sample = results.sample_networks(sketch.get_model(), 5, 42).unwrap();
+        assert_eq!(sample.len(), 5);
+        // sampling with the same seed is deterministic
+        let same_sample = results.sample_networks(sketch.get_model(), 5, 42).unwrap();
+        assert_eq!(sample, same_sample);
+
+        let dir = std::env::temp_dir().join("aeon_sketchbook_test_export_networks");
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir_path = dir.to_str().unwrap();
+        let paths = export_networks(&sample, dir_path, NetworkFormat::Bnet).unwrap();
+        assert_eq!(paths.len(), 5);
+        let contents = std::fs::read_to_string(&paths[0]).unwrap();
+        assert!(contents.starts_with("targets, factors"));
+        let paths = export_networks(&sample[..1], dir_path, NetworkFormat::Aeon).unwrap();
+        let contents = std::fs::read_to_string(&paths[0]).unwrap();
+        assert!(contents.contains("$a: b"));
+        std::fs::remove_dir_all(dir).unwrap();
+
+        // models with uninterpreted fns cannot be exported to bnet
+        assert!(export_networks(&[sketch.get_model().clone()], "", NetworkFormat::Bnet).is_err());
+    }
+}
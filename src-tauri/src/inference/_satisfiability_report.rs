@@ -0,0 +1,176 @@
+use crate::sketchbook::ids::{DynPropertyId, StatPropertyId};
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::GraphColors;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Reference to a (static or dynamic) property of a sketch.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum PropertyRef {
+    Static(StatPropertyId),
+    Dynamic(DynPropertyId),
+}
+
+impl fmt::Display for PropertyRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PropertyRef::Static(id) => write!(f, "static property `{id}`"),
+            PropertyRef::Dynamic(id) => write!(f, "dynamic property `{id}`"),
+        }
+    }
+}
+
+/// Report summarizing how individual properties of a sketch restrict the set of admissible
+/// interpretations (colors), which is useful to diagnose sketches without any consistent network.
+///
+/// For every property, the report contains the number of interpretations it admits alone,
+/// and the number of interpretations admitted by each pair of properties. If the properties
+/// are not satisfiable together, the report also contains a minimal subset of properties whose
+/// conjunction is unsatisfiable (removing any property from the subset makes it satisfiable).
+///
+/// All the numbers are (approximate) numbers of interpretations of the sketch's model.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatisfiabilityReport {
+    properties: Vec<PropertyRef>,
+    num_unconstrained: f64,
+    num_single: Vec<f64>,
+    num_pairwise: Vec<Vec<f64>>,
+    num_all: f64,
+    unsat_core: Option<Vec<PropertyRef>>,
+}
+
+impl SatisfiabilityReport {
+    /// Create the report given the set of all interpretations of the model (`unit_colors`),
+    /// and the list of properties, each paired with the set of interpretations it admits.
+    pub fn from_prop_colors(
+        unit_colors: &GraphColors,
+        prop_colors: Vec<(PropertyRef, GraphColors)>,
+    ) -> SatisfiabilityReport {
+        let num_single = prop_colors
+            .iter()
+            .map(|(_, colors)| colors.approx_cardinality())
+            .collect();
+        let num_pairwise = prop_colors
+            .iter()
+            .map(|(_, colors_1)| {
+                prop_colors
+                    .iter()
+                    .map(|(_, colors_2)| colors_1.intersect(colors_2).approx_cardinality())
+                    .collect()
+            })
+            .collect();
+
+        let all_colors = intersect_all(unit_colors, prop_colors.iter().map(|(_, c)| c));
+        let unsat_core = if all_colors.is_empty() {
+            Some(Self::find_unsat_core(unit_colors, &prop_colors))
+        } else {
+            None
+        };
+
+        SatisfiabilityReport {
+            properties: prop_colors.into_iter().map(|(prop, _)| prop).collect(),
+            num_unconstrained: unit_colors.approx_cardinality(),
+            num_single,
+            num_pairwise,
+            num_all: all_colors.approx_cardinality(),
+            unsat_core,
+        }
+    }
+
+    /// **(internal)** Find a minimal subset of properties whose conjunction is unsatisfiable,
+    /// given that the conjunction of all of them is unsatisfiable.
+    ///
+    /// Properties are removed one by one (in the given order), as long as the rest remains
+    /// unsatisfiable. The result is minimal with respect to inclusion, but not necessarily
+    /// the smallest such subset.
+    fn find_unsat_core(
+        unit_colors: &GraphColors,
+        prop_colors: &[(PropertyRef, GraphColors)],
+    ) -> Vec<PropertyRef> {
+        let mut core: Vec<usize> = (0..prop_colors.len()).collect();
+        let mut i = 0;
+        while i < core.len() {
+            let rest = core
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, prop_idx)| &prop_colors[*prop_idx].1);
+            if intersect_all(unit_colors, rest).is_empty() {
+                core.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        core.into_iter()
+            .map(|prop_idx| prop_colors[prop_idx].0.clone())
+            .collect()
+    }
+}
+
+/// Observing the `SatisfiabilityReport`.
+impl SatisfiabilityReport {
+    /// List of all properties covered by the report (in the order they were evaluated).
+    pub fn get_properties(&self) -> &Vec<PropertyRef> {
+        &self.properties
+    }
+
+    /// Number of interpretations of the model without any properties.
+    pub fn num_unconstrained(&self) -> f64 {
+        self.num_unconstrained
+    }
+
+    /// Number of interpretations admitted by the given property alone.
+    ///
+    /// Return `Err` if the property is not covered by the report.
+    pub fn num_admitted(&self, prop: &PropertyRef) -> Result<f64, String> {
+        let idx = self.get_prop_index(prop)?;
+        Ok(self.num_single[idx])
+    }
+
+    /// Number of interpretations admitted by both given properties.
+    ///
+    /// Return `Err` if any of the properties is not covered by the report.
+    pub fn num_admitted_pair(
+        &self,
+        prop_1: &PropertyRef,
+        prop_2: &PropertyRef,
+    ) -> Result<f64, String> {
+        let idx_1 = self.get_prop_index(prop_1)?;
+        let idx_2 = self.get_prop_index(prop_2)?;
+        Ok(self.num_pairwise[idx_1][idx_2])
+    }
+
+    /// Number of interpretations admitted by all the properties together.
+    pub fn num_sat_networks(&self) -> f64 {
+        self.num_all
+    }
+
+    /// Check whether all the properties are satisfiable together.
+    pub fn is_satisfiable(&self) -> bool {
+        self.unsat_core.is_none()
+    }
+
+    /// Get a minimal subset of properties whose conjunction is unsatisfiable, or `None` if all
+    /// the properties are satisfiable together.
+    ///
+    /// The subset is empty if the model itself has no admissible interpretation.
+    pub fn get_unsat_core(&self) -> Option<&Vec<PropertyRef>> {
+        self.unsat_core.as_ref()
+    }
+
+    /// **(internal)** Find the position of the property in the report.
+    fn get_prop_index(&self, prop: &PropertyRef) -> Result<usize, String> {
+        self.properties
+            .iter()
+            .position(|p| p == prop)
+            .ok_or(format!("The {prop} is not covered by the report."))
+    }
+}
+
+/// **(internal)** Intersect the `unit_colors` with all the given color sets.
+fn intersect_all<'a>(
+    unit_colors: &GraphColors,
+    colors: impl Iterator<Item = &'a GraphColors>,
+) -> GraphColors {
+    colors.fold(unit_colors.clone(), |acc, c| acc.intersect(c))
+}
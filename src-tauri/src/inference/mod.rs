@@ -0,0 +1,28 @@
+/// **(internal)** Evaluation of dynamic properties on the symbolic parametrised network.
+mod _eval_dynamic;
+/// **(internal)** Evaluation of static properties on the symbolic parametrised network.
+mod _eval_static;
+/// **(internal)** Definition and methods for `InferenceProgress`.
+mod _inference_progress;
+/// **(internal)** Definition and methods for `InferenceResults`.
+mod _inference_results;
+/// **(internal)** The main inference procedure, combining all the properties of a sketch.
+mod _inference_solver;
+/// **(internal)** Definition and methods for `InterpretationSummary`.
+mod _interpretation_summary;
+/// **(internal)** Sampling and enumeration of concrete networks from the inference results.
+mod _network_sampling;
+/// **(internal)** Definition and methods for `SatisfiabilityReport`.
+mod _satisfiability_report;
+
+pub use _inference_progress::InferenceProgress;
+pub use _inference_results::InferenceResults;
+pub use _inference_solver::{
+    compute_satisfiability_report, prepare_extended_graph, prepare_graph, run_inference,
+    run_inference_static_prop, run_inference_with_progress,
+};
+pub use _interpretation_summary::{
+    InputSummary, InterpretationSummary, Occurrence, RegulationSummary, UninterpretedFnSummary,
+};
+pub use _network_sampling::{export_networks, NetworkFormat};
+pub use _satisfiability_report::{PropertyRef, SatisfiabilityReport};
@@ -3,5 +3,6 @@ extern crate lazy_static;
 extern crate core;
 
 pub mod app;
+pub mod inference;
 pub mod logging;
 pub mod sketchbook;
@@ -7,13 +7,36 @@ use aeon_sketchbook::app::state::{AppState, DynSession};
 use aeon_sketchbook::app::{AeonApp, AEON_ACTION, AEON_REFRESH, AEON_VALUE};
 use aeon_sketchbook::debug;
 use serde::{Deserialize, Serialize};
-use tauri::{command, Manager, State, Window};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tauri::{command, AppHandle, Manager, State, Window};
+
+/// Counter used to assign unique IDs to analysis sessions (even if several analyses are started
+/// at the same time).
+static ANALYSIS_COUNTER: AtomicUsize = AtomicUsize::new(1);
 
 #[command]
 fn get_session_id(window: Window, state: State<AppState>) -> String {
     state.get_session_id(&window)
 }
 
+/// Start a new analysis session on a snapshot of the sketch edited in the window's session.
+/// Returns the ID of the new session, to which the analysis events can be sent.
+#[command]
+fn start_analysis_session(
+    window: Window,
+    state: State<AppState>,
+    app: AppHandle,
+) -> Result<String, String> {
+    let editor_session_id = state.get_session_id(&window);
+    let analysis_num = ANALYSIS_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let analysis_session_id = format!("analysis-{analysis_num}");
+    let aeon = AeonApp { tauri: app };
+    state
+        .analysis_session_created(&aeon, &editor_session_id, &analysis_session_id)
+        .map_err(|e| e.to_string())?;
+    Ok(analysis_session_id)
+}
+
 #[derive(Serialize, Deserialize)]
 struct AeonAction {
     session: String,
@@ -98,7 +121,10 @@ fn main() {
             });
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![get_session_id])
+        .invoke_handler(tauri::generate_handler![
+            get_session_id,
+            start_analysis_session
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,87 @@
+use crate::sketchbook::data_structs::SketchData;
+use crate::sketchbook::observations::ObservationManager;
+use crate::sketchbook::Sketch;
+use std::fs::File;
+use std::io::{Read, Write};
+use zip::result::ZipError;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Path of the file with the (versioned) sketch JSON inside the bundle.
+const SKETCH_FILE: &str = "sketch.json";
+/// Path of the file with free-form notes inside the bundle.
+const NOTES_FILE: &str = "notes.txt";
+/// Path of the directory with CSV files of all datasets inside the bundle.
+const DATASETS_DIR: &str = "datasets";
+
+/// Methods for exporting and importing the sketch as a zip bundle.
+///
+/// The bundle contains the versioned sketch JSON (the same as for the plain sketch export),
+/// a CSV file for each dataset (in the format used when loading datasets), and a text file with
+/// free-form notes. Datasets binarized from continuous data also have a `.raw.csv` file with
+/// the original values. The sketch JSON is the only source of truth when importing, the CSV files
+/// are there so that the data can be easily used (and versioned) outside the application.
+impl Sketch {
+    /// Export the sketch into a zip bundle at the given path, together with given notes.
+    pub fn export_bundle(&self, path: &str, notes: &str) -> Result<(), String> {
+        let sketch_data = SketchData::new(&self.model, &self.observations, &self.properties);
+        let mut datasets = self.observations.datasets().collect::<Vec<_>>();
+        datasets.sort_by_key(|(id, _)| *id);
+
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        let mut zip = ZipWriter::new(file);
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut write_file = |name: &str, contents: &str| -> Result<(), String> {
+            zip.start_file(name, options).map_err(|e| e.to_string())?;
+            zip.write_all(contents.as_bytes())
+                .map_err(|e| e.to_string())
+        };
+
+        write_file(SKETCH_FILE, &sketch_data.to_versioned_json_str())?;
+        write_file(NOTES_FILE, notes)?;
+        for (dataset_id, dataset) in datasets {
+            let csv_str = ObservationManager::dataset_to_csv_str(dataset)?;
+            write_file(&format!("{DATASETS_DIR}/{dataset_id}.csv"), &csv_str)?;
+            if let Some(raw_str) = ObservationManager::raw_dataset_to_csv_str(dataset)? {
+                write_file(&format!("{DATASETS_DIR}/{dataset_id}.raw.csv"), &raw_str)?;
+            }
+        }
+        zip.finish().map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Import a sketch from a zip bundle at the given path, replacing the current sketch.
+    /// Sketches of older schema versions are upgraded (see [SketchData::from_versioned_json_str]).
+    ///
+    /// Returns the notes stored in the bundle (empty if there are none).
+    pub fn import_bundle(&mut self, path: &str) -> Result<String, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+        let sketch_str = Self::read_bundle_file(&mut archive, SKETCH_FILE)?
+            .ok_or(format!("Bundle does not contain the `{SKETCH_FILE}` file."))?;
+        let notes = Self::read_bundle_file(&mut archive, NOTES_FILE)?.unwrap_or_default();
+
+        let sketch_data = SketchData::from_versioned_json_str(&sketch_str)?;
+        self.modify_from_sketch_data(&sketch_data)?;
+        Ok(notes)
+    }
+
+    /// **(internal)** Read the contents of a file in the bundle, or return `None` if there is
+    /// no such file.
+    fn read_bundle_file(
+        archive: &mut ZipArchive<File>,
+        name: &str,
+    ) -> Result<Option<String>, String> {
+        let mut zip_file = match archive.by_name(name) {
+            Ok(zip_file) => zip_file,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Err(e.to_string()),
+        };
+        let mut contents = String::new();
+        zip_file
+            .read_to_string(&mut contents)
+            .map_err(|e| e.to_string())?;
+        Ok(Some(contents))
+    }
+}
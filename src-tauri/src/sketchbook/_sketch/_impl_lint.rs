@@ -0,0 +1,207 @@
+use crate::sketchbook::data_structs::DiagnosticData;
+use crate::sketchbook::data_structs::DiagnosticSeverity::{Error, Info, Warning};
+use crate::sketchbook::observations::DataCategory;
+use crate::sketchbook::properties::dynamic_props::DynPropertyType;
+use crate::sketchbook::Sketch;
+use std::collections::HashSet;
+
+/// Linting the whole `Sketch`, i.e., looking for inconsistencies between its components.
+impl Sketch {
+    /// Check the whole sketch for inconsistencies and likely mistakes, and collect the
+    /// corresponding diagnostics. These cover the following issues:
+    /// - update functions using variables that are not their regulators (error)
+    /// - regulators not used by a specified update function (warning)
+    /// - uninterpreted functions that are never used (info)
+    /// - datasets with variables that do not exist in the model (error)
+    /// - dynamic properties referencing missing datasets or observations (error)
+    /// - `ExistsTrajectory` properties referencing datasets of `Unspecified` category (warning)
+    ///
+    /// The diagnostics are sorted by component paths and messages, so that the result is
+    /// deterministic.
+    pub fn lint(&self) -> Vec<DiagnosticData> {
+        let mut diagnostics = Vec::new();
+        self.lint_model(&mut diagnostics);
+        self.lint_observations(&mut diagnostics);
+        self.lint_properties(&mut diagnostics);
+        diagnostics.sort_by(|a, b| (&a.path, &a.message).cmp(&(&b.path, &b.message)));
+        diagnostics
+    }
+
+    /// **(internal)** Check update functions against regulations, and look for unused
+    /// uninterpreted functions.
+    fn lint_model(&self, diagnostics: &mut Vec<DiagnosticData>) {
+        let mut used_fn_symbols = HashSet::new();
+        for (var_id, _) in self.model.variables() {
+            // variable IDs are always valid, we can unwrap
+            let update_fn = self.model.get_update_fn(var_id).unwrap();
+            used_fn_symbols.extend(update_fn.collect_fn_symbols());
+            if update_fn.is_unspecified() {
+                continue;
+            }
+            let regulators: HashSet<_> =
+                self.model.regulators(var_id).unwrap().into_iter().collect();
+            let fn_vars = update_fn.collect_variables();
+            for fn_var in fn_vars.iter().filter(|v| !regulators.contains(v)) {
+                let message = format!(
+                    "Update function of `{var_id}` uses variable `{fn_var}`, which is not its regulator."
+                );
+                let path = ["model", "variable", var_id.as_str()];
+                diagnostics.push(DiagnosticData::new(Error, &path, &message));
+            }
+            for regulator in regulators.iter().filter(|r| !fn_vars.contains(r)) {
+                let message = format!(
+                    "Regulator `{regulator}` is not used in the update function of `{var_id}`."
+                );
+                let path = ["model", "regulation", regulator.as_str(), var_id.as_str()];
+                diagnostics.push(DiagnosticData::new(Warning, &path, &message));
+            }
+        }
+
+        for (_, uninterpreted_fn) in self.model.uninterpreted_fns() {
+            used_fn_symbols.extend(uninterpreted_fn.collect_fn_symbols());
+        }
+        for (fn_id, _) in self.model.uninterpreted_fns() {
+            if !used_fn_symbols.contains(fn_id) {
+                let message = format!("Uninterpreted function `{fn_id}` is never used.");
+                let path = ["model", "uninterpreted_fn", fn_id.as_str()];
+                diagnostics.push(DiagnosticData::new(Info, &path, &message));
+            }
+        }
+    }
+
+    /// **(internal)** Check that variables of all datasets exist in the model.
+    fn lint_observations(&self, diagnostics: &mut Vec<DiagnosticData>) {
+        for (dataset_id, dataset) in self.observations.datasets() {
+            for var_id in dataset.variables() {
+                if !self.model.is_valid_var_id(var_id) {
+                    let message = format!(
+                        "Dataset `{dataset_id}` contains variable `{var_id}`, which does not exist in the model."
+                    );
+                    let path = ["observations", dataset_id.as_str()];
+                    diagnostics.push(DiagnosticData::new(Error, &path, &message));
+                }
+            }
+        }
+    }
+
+    /// **(internal)** Check the datasets and observations referenced by dynamic properties.
+    fn lint_properties(&self, diagnostics: &mut Vec<DiagnosticData>) {
+        for (prop_id, prop) in self.properties.dyn_props() {
+            let path = ["properties", "dynamic", prop_id.as_str()];
+            let Some(dataset_id) = prop.get_dataset() else {
+                continue;
+            };
+            let Ok(dataset) = self.observations.get_dataset(dataset_id) else {
+                let message =
+                    format!("Property `{prop_id}` references missing dataset `{dataset_id}`.");
+                diagnostics.push(DiagnosticData::new(Error, &path, &message));
+                continue;
+            };
+            if let Some(obs_id) = prop.get_observation() {
+                if !dataset.is_valid_observation(obs_id) {
+                    let message = format!(
+                        "Property `{prop_id}` references missing observation `{obs_id}` of dataset `{dataset_id}`."
+                    );
+                    diagnostics.push(DiagnosticData::new(Error, &path, &message));
+                }
+            }
+            let is_trajectory =
+                matches!(prop.get_prop_data(), DynPropertyType::ExistsTrajectory(_));
+            if is_trajectory && *dataset.category() == DataCategory::Unspecified {
+                let message = format!(
+                    "Trajectory property `{prop_id}` references dataset `{dataset_id}` of unspecified category."
+                );
+                diagnostics.push(DiagnosticData::new(Warning, &path, &message));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::data_structs::DiagnosticSeverity::{Error, Info, Warning};
+    use crate::sketchbook::ids::{DatasetId, DynPropertyId, ObservationId};
+    use crate::sketchbook::model::ModelState;
+    use crate::sketchbook::observations::{DataCategory, Dataset, Observation, ObservationManager};
+    use crate::sketchbook::properties::PropertyManager;
+    use crate::sketchbook::Sketch;
+
+    #[test]
+    /// Test linting a sketch with various issues in all its components.
+    fn test_lint() {
+        let vars = vec![("a", "a"), ("b", "b"), ("c", "c")];
+        let mut model = ModelState::new_from_vars(vars).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -> b", "c -> b", "a -? c"])
+            .unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 1).unwrap();
+        model.add_uninterpreted_fn_by_str("g", "g", 1).unwrap();
+        let var = |id: &str| model.get_var_id(id).unwrap();
+        let (a, b, c) = (var("a"), var("b"), var("c"));
+        // `b` is not a regulator of `a`, `c` is not used in update function of `b`
+        model.set_update_fn(&a, "b").unwrap();
+        model.set_update_fn(&b, "a").unwrap();
+        model.set_update_fn(&c, "g(a)").unwrap();
+
+        // dataset contains variable `x` that is not in the model
+        let observation = Observation::new_full_ones(2, "o1").unwrap();
+        let dataset = Dataset::new_unspecified(vec![observation], vec!["a", "x"]).unwrap();
+        let dataset_id = DatasetId::new("d1").unwrap();
+        let observations = ObservationManager::from_datasets(vec![("d1", dataset)]).unwrap();
+
+        let mut properties = PropertyManager::new_empty();
+        let prop_id = |id: &str| DynPropertyId::new(id).unwrap();
+        let missing_obs = ObservationId::new("o2").unwrap();
+        let missing_dataset = DatasetId::new("d2").unwrap();
+        properties
+            .add_dyn_trajectory(prop_id("p1"), "p1", Some(dataset_id.clone()))
+            .unwrap();
+        properties
+            .add_dyn_fixed_point(prop_id("p2"), "p2", Some(dataset_id), Some(missing_obs))
+            .unwrap();
+        properties
+            .add_dyn_has_attractor(prop_id("p3"), "p3", Some(missing_dataset), None)
+            .unwrap();
+
+        let sketch = Sketch::new(model, observations, properties);
+        let diagnostics = sketch.lint();
+        let summary = diagnostics
+            .iter()
+            .map(|d| (d.severity, d.path.join("/")))
+            .collect::<Vec<_>>();
+        let expected = vec![
+            (Warning, "model/regulation/c/b".to_string()),
+            (Info, "model/uninterpreted_fn/f".to_string()),
+            (Error, "model/variable/a".to_string()),
+            (Error, "observations/d1".to_string()),
+            (Warning, "properties/dynamic/p1".to_string()),
+            (Error, "properties/dynamic/p2".to_string()),
+            (Error, "properties/dynamic/p3".to_string()),
+        ];
+        assert_eq!(summary, expected);
+        assert_eq!(
+            diagnostics[2].message,
+            "Update function of `a` uses variable `b`, which is not its regulator."
+        );
+    }
+
+    #[test]
+    /// Test that a consistent sketch has no diagnostics.
+    fn test_lint_consistent() {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -> b", "b -| a"])
+            .unwrap();
+        let dataset = Dataset::new_empty(vec!["a", "b"], DataCategory::TimeSeries).unwrap();
+        let observations = ObservationManager::from_datasets(vec![("d", dataset)]).unwrap();
+        let mut properties = PropertyManager::new_empty();
+        let prop_id = DynPropertyId::new("p").unwrap();
+        let dataset_id = DatasetId::new("d").unwrap();
+        properties
+            .add_dyn_trajectory(prop_id, "p", Some(dataset_id))
+            .unwrap();
+
+        let sketch = Sketch::new(model, observations, properties);
+        assert!(sketch.lint().is_empty());
+    }
+}
@@ -0,0 +1,348 @@
+use crate::sketchbook::data_structs::{
+    ConflictData, LayoutData, LayoutNodeData, MergeConflictData, SketchData, VariableData,
+};
+use crate::sketchbook::Sketch;
+
+/// Methods for merging two sketches (e.g., two separately extended versions of the same sketch).
+///
+/// Sketches are merged component-wise, with components identified by their IDs (regulations by
+/// the IDs of the regulator and target). Components present only in one of the sketches are
+/// simply added. If the same component differs between the sketches, the current version is
+/// kept and the difference is reported as a conflict, which can be resolved later by taking the
+/// incoming version (see [Sketch::resolve_merge_conflict]).
+impl Sketch {
+    /// Merge the `other` sketch into this one, and return the list of conflicts.
+    ///
+    /// Variables, regulations, uninterpreted functions, datasets, and properties are merged by
+    /// ID. Layouts are merged node-wise. Positions of nodes present in both sketches are taken
+    /// from this sketch, and missing nodes are added at the positions from the other sketch
+    /// (if it has a layout of the same ID).
+    ///
+    /// Returns `Err` if the merged components cannot form a valid sketch (e.g., if an incoming
+    /// update function uses an uninterpreted function with a conflicting arity). In such case,
+    /// this sketch is not modified.
+    pub fn merge(&mut self, other: &Sketch) -> Result<Vec<MergeConflictData>, String> {
+        let mut merged = SketchData::new(&self.model, &self.observations, &self.properties);
+        let incoming = SketchData::new(&other.model, &other.observations, &other.properties);
+        let mut conflicts = Vec::new();
+
+        merge_by_id(
+            &mut merged.model.variables,
+            &incoming.model.variables,
+            |v| v.id.clone(),
+            |current, incoming| {
+                let message = if current.update_fn != incoming.update_fn {
+                    format!("Variable `{}` has different update functions.", current.id)
+                } else {
+                    format!("Variable `{}` has different names.", current.id)
+                };
+                MergeConflictData::Variable(ConflictData::new(&message, current, incoming))
+            },
+            &mut conflicts,
+        );
+        merge_by_id(
+            &mut merged.model.regulations,
+            &incoming.model.regulations,
+            |r| (r.regulator.clone(), r.target.clone()),
+            |current, incoming| {
+                let difference = if current.sign != incoming.sign {
+                    "monotonicity"
+                } else {
+                    "essentiality"
+                };
+                let message = format!(
+                    "Regulation `{}` -> `{}` has different {difference}.",
+                    current.regulator, current.target
+                );
+                MergeConflictData::Regulation(ConflictData::new(&message, current, incoming))
+            },
+            &mut conflicts,
+        );
+        merge_by_id(
+            &mut merged.model.uninterpreted_fns,
+            &incoming.model.uninterpreted_fns,
+            |f| f.id.clone(),
+            |current, incoming| {
+                let message = format!(
+                    "Uninterpreted function `{}` has different definitions.",
+                    current.id
+                );
+                MergeConflictData::UninterpretedFn(ConflictData::new(&message, current, incoming))
+            },
+            &mut conflicts,
+        );
+        merge_by_id(
+            &mut merged.datasets,
+            &incoming.datasets,
+            |d| d.id.clone(),
+            |current, incoming| {
+                let message = format!("Dataset `{}` has different contents.", current.id);
+                MergeConflictData::Dataset(ConflictData::new(&message, current, incoming))
+            },
+            &mut conflicts,
+        );
+        merge_by_id(
+            &mut merged.dyn_properties,
+            &incoming.dyn_properties,
+            |p| p.id.clone(),
+            |current, incoming| {
+                let message = format!(
+                    "Dynamic property `{}` has different definitions.",
+                    current.id
+                );
+                MergeConflictData::DynProperty(ConflictData::new(&message, current, incoming))
+            },
+            &mut conflicts,
+        );
+        merge_by_id(
+            &mut merged.stat_properties,
+            &incoming.stat_properties,
+            |p| p.id.clone(),
+            |current, incoming| {
+                let message = format!(
+                    "Static property `{}` has different definitions.",
+                    current.id
+                );
+                MergeConflictData::StatProperty(ConflictData::new(&message, current, incoming))
+            },
+            &mut conflicts,
+        );
+        merge_layouts(
+            &mut merged.model.layouts,
+            &incoming.model.layouts,
+            &merged.model.variables,
+        );
+
+        self.modify_from_sketch_data(&merged)
+            .map_err(|e| format!("Cannot merge the sketches: {e}"))?;
+        Ok(conflicts)
+    }
+
+    /// Resolve a conflict found when merging sketches, by replacing the `current` version of
+    /// the conflicting component with the `incoming` one.
+    ///
+    /// Resolving the conflict with swapped versions (see [MergeConflictData::swapped]) reverts
+    /// the resolution. Returns `Err` if the component does not exist in this sketch, or if the
+    /// incoming version is not valid with respect to the rest of the sketch. In such case, this
+    /// sketch is not modified.
+    pub fn resolve_merge_conflict(&mut self, conflict: &MergeConflictData) -> Result<(), String> {
+        let mut data = SketchData::new(&self.model, &self.observations, &self.properties);
+        match conflict {
+            MergeConflictData::Variable(c) => {
+                replace_by_id(&mut data.model.variables, &c.incoming, |v| v.id.clone())
+            }
+            MergeConflictData::Regulation(c) => {
+                replace_by_id(&mut data.model.regulations, &c.incoming, |r| {
+                    (r.regulator.clone(), r.target.clone())
+                })
+            }
+            MergeConflictData::UninterpretedFn(c) => {
+                replace_by_id(&mut data.model.uninterpreted_fns, &c.incoming, |f| {
+                    f.id.clone()
+                })
+            }
+            MergeConflictData::Dataset(c) => {
+                replace_by_id(&mut data.datasets, &c.incoming, |d| d.id.clone())
+            }
+            MergeConflictData::DynProperty(c) => {
+                replace_by_id(&mut data.dyn_properties, &c.incoming, |p| p.id.clone())
+            }
+            MergeConflictData::StatProperty(c) => {
+                replace_by_id(&mut data.stat_properties, &c.incoming, |p| p.id.clone())
+            }
+        }
+        .map_err(|_| format!("Cannot resolve conflict: {}", conflict.message()))?;
+        self.modify_from_sketch_data(&data)
+    }
+}
+
+/// **(internal)** Merge the `incoming` list of components into the `current` one, identifying
+/// components by the given `key`. Components missing in `current` are added, and differing
+/// components are kept as they are, with a conflict (created by `mk_conflict` from the current
+/// and incoming versions) added to `conflicts`.
+fn merge_by_id<T: Clone + PartialEq, K: PartialEq>(
+    current: &mut Vec<T>,
+    incoming: &[T],
+    key: impl Fn(&T) -> K,
+    mk_conflict: impl Fn(&T, &T) -> MergeConflictData,
+    conflicts: &mut Vec<MergeConflictData>,
+) {
+    for incoming_item in incoming {
+        let incoming_key = key(incoming_item);
+        match current.iter().find(|item| key(item) == incoming_key) {
+            None => current.push(incoming_item.clone()),
+            Some(item) if item != incoming_item => conflicts.push(mk_conflict(item, incoming_item)),
+            Some(_) => {}
+        }
+    }
+}
+
+/// **(internal)** Replace a component in the list by the given `new_item` with the same `key`.
+///
+/// Returns `Err` if there is no such component.
+fn replace_by_id<T: Clone, K: PartialEq>(
+    items: &mut [T],
+    new_item: &T,
+    key: impl Fn(&T) -> K,
+) -> Result<(), String> {
+    let new_key = key(new_item);
+    let item = items
+        .iter_mut()
+        .find(|item| key(item) == new_key)
+        .ok_or("Component does not exist.".to_string())?;
+    *item = new_item.clone();
+    Ok(())
+}
+
+/// **(internal)** Merge the `incoming` layouts into the `current` ones, so that each resulting
+/// layout has a node for every variable (of the merged list). Positions are taken from the
+/// current layout first, then from the incoming layout with the same ID, and default to zero.
+fn merge_layouts(
+    current: &mut Vec<LayoutData>,
+    incoming: &[LayoutData],
+    variables: &[VariableData],
+) {
+    for incoming_layout in incoming {
+        if !current.iter().any(|l| l.id == incoming_layout.id) {
+            current.push(LayoutData {
+                id: incoming_layout.id.clone(),
+                name: incoming_layout.name.clone(),
+                nodes: Vec::new(),
+            });
+        }
+    }
+
+    for layout in current.iter_mut() {
+        let incoming_layout = incoming.iter().find(|l| l.id == layout.id);
+        let find_node = |nodes: &[LayoutNodeData], var_id: &str| {
+            nodes
+                .iter()
+                .find(|n| n.variable == var_id)
+                .map(|n| (n.px, n.py))
+        };
+        layout.nodes = variables
+            .iter()
+            .map(|var| {
+                let (px, py) = find_node(&layout.nodes, &var.id)
+                    .or_else(|| incoming_layout.and_then(|l| find_node(&l.nodes, &var.id)))
+                    .unwrap_or((0., 0.));
+                LayoutNodeData::new(&layout.id, &var.id, px, py)
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::data_structs::MergeConflictData;
+    use crate::sketchbook::ids::DynPropertyId;
+    use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+    use crate::sketchbook::observations::ObservationManager;
+    use crate::sketchbook::properties::PropertyManager;
+    use crate::sketchbook::Sketch;
+
+    /// Prepare a sketch with the given variables and regulations, update function of variable
+    /// `a`, and a generic property `p` with the given formula.
+    fn prepare_sketch(vars: &[&str], regulations: Vec<&str>, fn_a: &str, formula: &str) -> Sketch {
+        let vars = vars.iter().map(|v| (*v, *v)).collect();
+        let mut model = ModelState::new_from_vars(vars).unwrap();
+        model.add_multiple_regulations(regulations).unwrap();
+        let var_a = model.get_var_id("a").unwrap();
+        model.set_update_fn(&var_a, fn_a).unwrap();
+        let mut properties = PropertyManager::new_empty();
+        let prop_id = DynPropertyId::new("p").unwrap();
+        properties.add_dyn_generic(prop_id, "p", formula).unwrap();
+        Sketch::new(model, ObservationManager::new_empty(), properties)
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut sketch = prepare_sketch(&["a", "b"], vec!["a -> b", "b -| a"], "!b", "a");
+        let other = prepare_sketch(
+            &["a", "b", "c"],
+            vec!["a -| b", "b -| a", "c -> a"],
+            "!b & c",
+            "a",
+        );
+        let conflicts = sketch.merge(&other).unwrap();
+
+        // variable `c` and its regulation are added, regulation `b -| a` is the same
+        let model = sketch.get_model();
+        assert_eq!(model.num_vars(), 3);
+        assert_eq!(model.num_regulations(), 3);
+        assert_eq!(sketch.get_properties().num_dyn_properties(), 1);
+
+        // conflicts keep the current versions
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(
+            conflicts[0].message(),
+            "Variable `a` has different update functions."
+        );
+        assert_eq!(
+            conflicts[1].message(),
+            "Regulation `a` -> `b` has different monotonicity."
+        );
+        let var_a = model.get_var_id("a").unwrap();
+        assert_eq!(model.get_update_fn_string(&var_a).unwrap(), "!b");
+
+        // resolve the conflicts by taking the incoming versions
+        for conflict in conflicts.iter() {
+            sketch.resolve_merge_conflict(conflict).unwrap();
+        }
+        let model = sketch.get_model();
+        assert_eq!(model.get_update_fn_string(&var_a).unwrap(), "!b & c");
+        let regulation = model.get_regulation_by_str("a", "b").unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+        assert_eq!(regulation.get_essentiality(), &Essentiality::True);
+
+        // resolution can be reverted by swapping the versions
+        sketch
+            .resolve_merge_conflict(&conflicts[1].swapped())
+            .unwrap();
+        let regulation = sketch.get_model().get_regulation_by_str("a", "b").unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Activation);
+    }
+
+    #[test]
+    fn test_merge_invalid() {
+        let mut sketch = prepare_sketch(&["a", "b"], vec!["a -> b"], "b", "a");
+        let mut model = sketch.get_model().clone();
+        model.add_uninterpreted_fn_by_str("f", "f", 2).unwrap();
+        sketch = Sketch::new(
+            model,
+            ObservationManager::new_empty(),
+            PropertyManager::new_empty(),
+        );
+        let original = sketch.clone();
+
+        // the incoming update function of `b` uses function `f` with a different arity
+        let mut other = prepare_sketch(&["a", "b"], vec!["a -> b"], "b", "a");
+        let mut model = other.get_model().clone();
+        model.add_uninterpreted_fn_by_str("f", "f", 1).unwrap();
+        model.add_var_by_str("c", "c").unwrap();
+        let var_c = model.get_var_id("c").unwrap();
+        model.set_update_fn(&var_c, "f(a)").unwrap();
+        other = Sketch::new(
+            model,
+            ObservationManager::new_empty(),
+            PropertyManager::new_empty(),
+        );
+
+        let error = sketch.merge(&other).unwrap_err();
+        assert!(error.starts_with("Cannot merge the sketches: "));
+        assert_eq!(sketch, original);
+
+        // resolving a conflict for a component that does not exist fails
+        let conflicts = prepare_sketch(&["a", "b"], vec!["a -> b"], "b", "a")
+            .merge(&prepare_sketch(&["a", "b"], vec!["a -> b"], "b", "b"))
+            .unwrap();
+        assert!(matches!(conflicts[0], MergeConflictData::DynProperty(_)));
+        let error = Sketch::default()
+            .resolve_merge_conflict(&conflicts[0])
+            .unwrap_err();
+        assert_eq!(
+            error,
+            "Cannot resolve conflict: Dynamic property `p` has different definitions."
+        );
+    }
+}
@@ -1,14 +1,130 @@
 use crate::app::event::Event;
 use crate::app::state::{Consumed, SessionHelper, SessionState};
-use crate::app::DynError;
-use crate::sketchbook::data_structs::SketchData;
-use crate::sketchbook::event_utils::make_state_change;
+use crate::app::{AeonError, DynError};
+use crate::sketchbook::data_structs::{
+    BundleExportData, InteractionImportData, InvalidPropertyData, MergeConflictData,
+    SketchBundleData, SketchData, SketchImportData, SketchMergeData, StatPropertyData,
+    SvgExportData,
+};
+use crate::sketchbook::event_utils::{make_refresh_event, make_reversible, make_state_change};
+use crate::sketchbook::model::ModelState;
 use crate::sketchbook::{JsonSerde, Sketch};
 use std::fs::File;
 use std::io::{Read, Write};
 
 impl SessionHelper for Sketch {}
 
+impl Sketch {
+    /// Check whether the given `state_changes` (produced by a single user action) renamed or
+    /// removed some variable of the model. If so, the dynamic properties are re-checked
+    /// with respect to the modified model, and a state-change event listing all properties
+    /// that are not valid anymore (together with the errors) is returned.
+    ///
+    /// The list might be empty, signalling that all the properties are valid again (e.g., after
+    /// the renaming is undone).
+    pub fn mk_invalid_props_state_change(&self, state_changes: &[Event]) -> Option<Event> {
+        let vars_changed = state_changes.iter().any(|e| {
+            e.path.len() == 4
+                && e.path[..3] == ["sketch", "model", "variable"]
+                && (e.path[3] == "set_id" || e.path[3] == "remove")
+        });
+        if !vars_changed {
+            return None;
+        }
+        // serialization of a simple vector of structs cannot fail, we can unwrap
+        let payload = serde_json::to_string(&self.invalid_dyn_props_data()).unwrap();
+        Some(Event::build(
+            &["sketch", "properties", "dynamic", "invalid_props"],
+            Some(&payload),
+        ))
+    }
+
+    /// Check whether the given `state_changes` (produced by a single user action) modified the
+    /// sketch. If so, the whole sketch is linted again, and a state-change event listing all
+    /// the current diagnostics (see [Sketch::lint]) is returned.
+    ///
+    /// The list might be empty, signalling that all the previous issues were resolved.
+    pub fn mk_diagnostics_state_change(&self, state_changes: &[Event]) -> Option<Event> {
+        let sketch_changed = state_changes
+            .iter()
+            .any(|e| e.path.first().is_some_and(|p| p == "sketch"));
+        if !sketch_changed {
+            return None;
+        }
+        // serialization of a simple vector of structs cannot fail, we can unwrap
+        let payload = serde_json::to_string(&self.lint()).unwrap();
+        Some(Event::build(&["sketch", "diagnostics"], Some(&payload)))
+    }
+
+    /// **(internal)** Import an interaction network (from a file given by the event's
+    /// [InteractionImportData] payload), using the given `merge_fn` to parse it. The network is
+    /// either merged into the current model, or it replaces the whole sketch.
+    ///
+    /// The import is irreversible. The resulting state-change event carries the whole sketch,
+    /// together with warnings about duplicate or conflicting interactions.
+    fn import_interactions(
+        &mut self,
+        event: &Event,
+        merge_fn: fn(&mut ModelState, &str) -> Result<Vec<String>, String>,
+    ) -> Result<Consumed, DynError> {
+        let payload = Self::clone_payload_str(event, "sketch")?;
+        let import_data = InteractionImportData::from_json_str(&payload)?;
+        // read the file contents
+        let mut file = File::open(import_data.path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        let warnings = if import_data.merge {
+            merge_fn(&mut self.model, &contents)?
+        } else {
+            // parse the model first, so that the sketch is untouched if it fails
+            let mut model = ModelState::new_empty();
+            let warnings = merge_fn(&mut model, &contents)?;
+            self.set_to_empty();
+            self.model = model;
+            warnings
+        };
+
+        let sketch_data = SketchData::new(&self.model, &self.observations, &self.properties);
+        let import_data = SketchImportData {
+            sketch: sketch_data,
+            warnings,
+        };
+        let state_change = make_state_change(&["sketch", "set_all_with_warnings"], &import_data);
+        Ok(Consumed::Irreversible {
+            state_change,
+            reset: true,
+        })
+    }
+
+    /// **(internal)** Check whether the `event` adds or modifies a static property whose context
+    /// is invalid with respect to the model (e.g., it references a variable that does not exist).
+    /// The property manager itself only checks the syntax of contexts, since it has no access
+    /// to the model.
+    ///
+    /// Returns the error message if the context is invalid. Events with malformed payloads are
+    /// left for the property manager to report.
+    fn check_stat_prop_context(&self, event: &Event, at_path: &[&str]) -> Option<String> {
+        if !matches!(at_path, ["static", "add"] | ["static", _, "set_content"]) {
+            return None;
+        }
+        let payload = event.payload.as_ref()?;
+        let property = StatPropertyData::from_json_str(payload)
+            .ok()?
+            .to_property()
+            .ok()?;
+        property.get_context_tree(&self.model).err()
+    }
+
+    /// **(internal)** Prepare data about all the invalid dynamic properties of the sketch.
+    fn invalid_dyn_props_data(&self) -> Vec<InvalidPropertyData> {
+        self.get_invalid_dyn_props()
+            .iter()
+            .map(|(id, error)| InvalidPropertyData::from_dyn_prop(id, error))
+            .collect()
+    }
+}
+
 impl SessionState for Sketch {
     fn perform_event(&mut self, event: &Event, at_path: &[&str]) -> Result<Consumed, DynError> {
         // just distribute the events one layer down, or answer some specific cases
@@ -17,6 +133,10 @@ impl SessionState for Sketch {
         } else if let Some(at_path) = Self::starts_with("observations", at_path) {
             self.observations.perform_event(event, at_path)
         } else if let Some(at_path) = Self::starts_with("properties", at_path) {
+            if let Some(message) = self.check_stat_prop_context(event, at_path) {
+                let error = AeonError::new(format!("Invalid context: {message}"), None);
+                return Ok(Consumed::InputError(Box::new(error)));
+            }
             self.properties.perform_event(event, at_path)
         } else if Self::starts_with("new_sketch", at_path).is_some() {
             self.set_to_empty();
@@ -31,8 +151,8 @@ impl SessionState for Sketch {
             let sketch_data = SketchData::new(&self.model, &self.observations, &self.properties);
             let path = Self::clone_payload_str(event, "sketch")?;
             let mut file = File::create(path).map_err(|e| e.to_string())?;
-            // write sketch in JSON to the file
-            file.write_all(sketch_data.to_pretty_json_str().as_bytes())
+            // write sketch in (versioned) JSON to the file
+            file.write_all(sketch_data.to_versioned_json_str().as_bytes())
                 .map_err(|e| e.to_string())?;
             Ok(Consumed::NoChange)
         } else if Self::starts_with("import_sketch", at_path).is_some() {
@@ -42,8 +162,8 @@ impl SessionState for Sketch {
             let mut contents = String::new();
             file.read_to_string(&mut contents)?;
 
-            // parse the SketchData, modify the sketch
-            let sketch_data = SketchData::from_json_str(&contents)?;
+            // parse the SketchData (upgrading older versions), modify the sketch
+            let sketch_data = SketchData::from_versioned_json_str(&contents)?;
             self.modify_from_sketch_data(&sketch_data)?;
 
             let state_change = make_state_change(&["sketch", "set_all"], &sketch_data);
@@ -52,6 +172,170 @@ impl SessionState for Sketch {
                 state_change,
                 reset: true,
             })
+        } else if Self::starts_with("export_dataset_hctl", at_path).is_some() {
+            let path = Self::clone_payload_str(event, "sketch")?;
+            let mut file = File::create(path).map_err(|e| e.to_string())?;
+            // write HCTL formulas of all dataset-backed dynamic properties to the file
+            file.write_all(self.dataset_props_to_hctl_str().as_bytes())
+                .map_err(|e| e.to_string())?;
+            Ok(Consumed::NoChange)
+        } else if Self::starts_with("export_bundle", at_path).is_some() {
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let export_data = BundleExportData::from_json_str(&payload)?;
+            self.export_bundle(&export_data.path, &export_data.notes)?;
+            Ok(Consumed::NoChange)
+        } else if Self::starts_with("import_bundle", at_path).is_some() {
+            let file_path = Self::clone_payload_str(event, "sketch")?;
+            let notes = self.import_bundle(&file_path)?;
+
+            let sketch_data = SketchData::new(&self.model, &self.observations, &self.properties);
+            let bundle_data = SketchBundleData {
+                sketch: sketch_data,
+                notes,
+            };
+            let state_change = make_state_change(&["sketch", "set_all_from_bundle"], &bundle_data);
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: true,
+            })
+        } else if Self::starts_with("export_aeon", at_path).is_some() {
+            let path = Self::clone_payload_str(event, "sketch")?;
+            let mut file = File::create(path).map_err(|e| e.to_string())?;
+            // write the model in the aeon format to the file
+            file.write_all(self.model.to_aeon().as_bytes())
+                .map_err(|e| e.to_string())?;
+            Ok(Consumed::NoChange)
+        } else if Self::starts_with("import_aeon", at_path).is_some() {
+            let file_path = Self::clone_payload_str(event, "sketch")?;
+            // read the file contents
+            let mut file = File::open(file_path)?;
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)?;
+
+            // parse the model, and replace the whole sketch by a sketch with just this model
+            let model = ModelState::from_aeon(&contents)?;
+            self.set_to_empty();
+            self.model = model;
+
+            let sketch_data = SketchData::new(&self.model, &self.observations, &self.properties);
+            let state_change = make_state_change(&["sketch", "set_all"], &sketch_data);
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: true,
+            })
+        } else if Self::starts_with("export_sbml", at_path).is_some() {
+            let path = Self::clone_payload_str(event, "sketch")?;
+            let (sbml_str, warnings) = self.model.to_sbml();
+            let mut file = File::create(path).map_err(|e| e.to_string())?;
+            file.write_all(sbml_str.as_bytes())
+                .map_err(|e| e.to_string())?;
+            if warnings.is_empty() {
+                return Ok(Consumed::NoChange);
+            }
+            // notify the frontend about parts of the model that were lost (bypassing the stack)
+            let payload = serde_json::to_string(&warnings)?;
+            Ok(Consumed::Irreversible {
+                state_change: Event::build(&["sketch", "export_sbml_warnings"], Some(&payload)),
+                reset: false,
+            })
+        } else if Self::starts_with("import_sbml", at_path).is_some() {
+            let file_path = Self::clone_payload_str(event, "sketch")?;
+            // read the file contents
+            let mut file = File::open(file_path)?;
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)?;
+
+            // parse the model, and replace the whole sketch by a sketch with just this model
+            let (model, warnings) = ModelState::from_sbml(&contents)?;
+            self.set_to_empty();
+            self.model = model;
+
+            // warnings about parts of the model lost during parsing are sent to the frontend
+            let sketch_data = SketchData::new(&self.model, &self.observations, &self.properties);
+            let import_data = SketchImportData {
+                sketch: sketch_data,
+                warnings,
+            };
+            let state_change =
+                make_state_change(&["sketch", "set_all_with_warnings"], &import_data);
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: true,
+            })
+        } else if Self::starts_with("merge_sketch", at_path).is_some() {
+            let file_path = Self::clone_payload_str(event, "sketch")?;
+            // read the file contents
+            let mut file = File::open(file_path)?;
+            let mut contents = String::new();
+            file.read_to_string(&mut contents)?;
+
+            // parse the other sketch (upgrading older versions), and merge it into this one
+            let other_data = SketchData::from_versioned_json_str(&contents)?;
+            let other = Sketch::new_from_sketch_data(&other_data)?;
+            let orig_data = SketchData::new(&self.model, &self.observations, &self.properties);
+            let conflicts = self.merge(&other)?;
+            let sketch_data = SketchData::new(&self.model, &self.observations, &self.properties);
+
+            // the whole merge is reversible by setting the original sketch back (and it is
+            // redone by setting the merged sketch, without re-reading the file)
+            let perform_event =
+                Event::build(&["sketch", "set_sketch"], Some(&sketch_data.to_json_str()));
+            let reverse_event =
+                Event::build(&["sketch", "set_sketch"], Some(&orig_data.to_json_str()));
+            let merge_data = SketchMergeData {
+                sketch: sketch_data,
+                conflicts,
+            };
+            let state_change = make_state_change(&["sketch", "set_all_merged"], &merge_data);
+            Ok(Consumed::Reversible {
+                state_change,
+                perform_reverse: (perform_event, reverse_event),
+            })
+        } else if Self::starts_with("set_sketch", at_path).is_some() {
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let sketch_data = SketchData::from_json_str(&payload)?;
+            let orig_data = SketchData::new(&self.model, &self.observations, &self.properties);
+            self.modify_from_sketch_data(&sketch_data)?;
+
+            let state_change = make_state_change(&["sketch", "set_all"], &sketch_data);
+            let reverse_event =
+                Event::build(&["sketch", "set_sketch"], Some(&orig_data.to_json_str()));
+            Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with("resolve_merge_conflict", at_path).is_some() {
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let conflict = MergeConflictData::from_json_str(&payload)?;
+            self.resolve_merge_conflict(&conflict)?;
+
+            let sketch_data = SketchData::new(&self.model, &self.observations, &self.properties);
+            let state_change = make_state_change(&["sketch", "set_all"], &sketch_data);
+            // resolving the conflict with swapped versions reverts the resolution
+            let reverse_payload = conflict.swapped().to_json_str();
+            let reverse_event = Event::build(
+                &["sketch", "resolve_merge_conflict"],
+                Some(&reverse_payload),
+            );
+            Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with("export_dot", at_path).is_some() {
+            let path = Self::clone_payload_str(event, "sketch")?;
+            let mut file = File::create(path).map_err(|e| e.to_string())?;
+            // write the regulatory graph in the DOT format to the file
+            file.write_all(self.model.to_dot().as_bytes())
+                .map_err(|e| e.to_string())?;
+            Ok(Consumed::NoChange)
+        } else if Self::starts_with("export_svg", at_path).is_some() {
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let export_data = SvgExportData::from_json_str(&payload)?;
+            let layout_id = self.model.get_layout_id(&export_data.layout_id)?;
+            // render the figure before creating the file, so that no empty file is left on error
+            let svg_str = self.model.to_svg(&layout_id)?;
+            let mut file = File::create(export_data.path).map_err(|e| e.to_string())?;
+            file.write_all(svg_str.as_bytes())
+                .map_err(|e| e.to_string())?;
+            Ok(Consumed::NoChange)
+        } else if Self::starts_with("import_sif", at_path).is_some() {
+            self.import_interactions(event, ModelState::merge_sif)
+        } else if Self::starts_with("import_graphml", at_path).is_some() {
+            self.import_interactions(event, ModelState::merge_graphml)
         } else {
             Self::invalid_path_error_generic(at_path)
         }
@@ -65,6 +349,10 @@ impl SessionState for Sketch {
             self.observations.refresh(full_path, at_path)
         } else if let Some(at_path) = Self::starts_with("properties", at_path) {
             self.properties.refresh(full_path, at_path)
+        } else if Self::starts_with("get_invalid_dyn_props", at_path).is_some() {
+            make_refresh_event(full_path, self.invalid_dyn_props_data())
+        } else if Self::starts_with("get_diagnostics", at_path).is_some() {
+            make_refresh_event(full_path, self.lint())
         } else if Self::starts_with("get_whole_sketch", at_path).is_some() {
             let sketch_data = SketchData::new(&self.model, &self.observations, &self.properties);
             Ok(Event {
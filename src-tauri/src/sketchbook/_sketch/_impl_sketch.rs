@@ -1,10 +1,24 @@
 use crate::sketchbook::data_structs::SketchData;
+use crate::sketchbook::ids::DynPropertyId;
 use crate::sketchbook::model::ModelState;
 use crate::sketchbook::observations::{Dataset, ObservationManager};
 use crate::sketchbook::properties::{DynProperty, PropertyManager, StatProperty};
 use crate::sketchbook::Sketch;
 
 impl Sketch {
+    /// Create a new `Sketch` instance from its (already prepared) components.
+    pub fn new(
+        model: ModelState,
+        observations: ObservationManager,
+        properties: PropertyManager,
+    ) -> Sketch {
+        Sketch {
+            model,
+            observations,
+            properties,
+        }
+    }
+
     /// Parse and validate all components of `Sketch` from a corresponding `SketchData` instance.
     pub fn components_from_sketch_data(
         sketch_data: &SketchData,
@@ -78,3 +92,70 @@ impl Sketch {
         self.properties = PropertyManager::default();
     }
 }
+
+/// Observing the `Sketch`.
+impl Sketch {
+    /// Get the `ModelState` of this sketch.
+    pub fn get_model(&self) -> &ModelState {
+        &self.model
+    }
+
+    /// Get the `ObservationManager` of this sketch.
+    pub fn get_observations(&self) -> &ObservationManager {
+        &self.observations
+    }
+
+    /// Get the `PropertyManager` of this sketch.
+    pub fn get_properties(&self) -> &PropertyManager {
+        &self.properties
+    }
+}
+
+/// Validating the `Sketch`.
+impl Sketch {
+    /// Check all dynamic properties with respect to the model, and collect those that are
+    /// not valid (e.g., their HCTL formulas reference non-existing variables), together with
+    /// the corresponding error messages. The list is sorted by property IDs.
+    pub fn get_invalid_dyn_props(&self) -> Vec<(DynPropertyId, String)> {
+        let mut invalid_props: Vec<(DynPropertyId, String)> = self
+            .properties
+            .dyn_props()
+            .filter_map(|(id, prop)| {
+                prop.check_with_model(&self.model)
+                    .err()
+                    .map(|e| (id.clone(), e))
+            })
+            .collect();
+        invalid_props.sort_by(|(id1, _), (id2, _)| id1.cmp(id2));
+        invalid_props
+    }
+}
+
+/// Exporting derived data of the `Sketch`.
+impl Sketch {
+    /// Encode all dynamic properties that reference a dataset into HCTL formulas, and list them
+    /// in a human-readable text format (sorted by property IDs). Each property is described by
+    /// a comment line with its ID, name, and dataset, followed by the formula.
+    ///
+    /// Properties that cannot be encoded (e.g., because the observation is not specified) are
+    /// listed with the corresponding error instead of the formula.
+    pub fn dataset_props_to_hctl_str(&self) -> String {
+        let mut dataset_props: Vec<_> = self
+            .properties
+            .dyn_props()
+            .filter_map(|(id, prop)| prop.get_dataset().map(|d| (id, prop, d)))
+            .collect();
+        dataset_props.sort_by_key(|(id, _, _)| *id);
+
+        let mut hctl_str = String::new();
+        for (prop_id, prop, dataset_id) in dataset_props {
+            let name = prop.get_name();
+            hctl_str.push_str(&format!("# {prop_id} ({name}), dataset `{dataset_id}`\n"));
+            match prop.encode_hctl(&self.observations) {
+                Ok(formula) => hctl_str.push_str(&format!("{formula}\n")),
+                Err(e) => hctl_str.push_str(&format!("# error: {e}\n")),
+            }
+        }
+        hctl_str
+    }
+}
@@ -4,6 +4,12 @@ use crate::sketchbook::properties::PropertyManager;
 use crate::sketchbook::{JsonSerde, Manager};
 use serde::{Deserialize, Serialize};
 
+/// **(internal)** Exporting and importing the sketch as a zip bundle.
+mod _impl_bundle;
+/// **(internal)** Linting the whole sketch for inconsistencies between its components.
+mod _impl_lint;
+/// **(internal)** Merging two sketches and resolving the merge conflicts.
+mod _impl_merge;
 /// **(internal)** Implementation of event-based API for the [SessionState] trait.
 mod _impl_session_state;
 /// **(internal)** Utility methods for `Sketch`.
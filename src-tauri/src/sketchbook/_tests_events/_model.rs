@@ -3,8 +3,8 @@ use crate::sketchbook::_tests_events::{check_reverse, stringify_path};
 use crate::sketchbook::data_structs::*;
 use crate::sketchbook::event_utils::mk_model_event;
 use crate::sketchbook::ids::VarId;
-use crate::sketchbook::layout::NodePosition;
-use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+use crate::sketchbook::layout::{LayoutAlgorithm, NodePosition};
+use crate::sketchbook::model::{Essentiality, FnNormalForm, ModelState, Monotonicity};
 use crate::sketchbook::JsonSerde;
 
 #[test]
@@ -262,6 +262,229 @@ fn test_change_position() {
     check_reverse(&mut model, &model_orig, result, &at_path);
 }
 
+#[test]
+/// Test automatically placing a subset of layout nodes via event.
+fn test_auto_layout() {
+    let vars = vec![("a", "a"), ("b", "b"), ("c", "c")];
+    let mut model = ModelState::new_from_vars(vars).unwrap();
+    model.add_multiple_regulations(vec!["a -> b"]).unwrap();
+    let layout_id = ModelState::get_default_layout_id();
+
+    // only nodes `a` and `b` are placed, `c` stays at the default position
+    let auto_layout_data = AutoLayoutData {
+        algorithm: LayoutAlgorithm::Hierarchical,
+        variables: vec!["a".to_string(), "b".to_string()],
+    };
+    let payload = auto_layout_data.to_json_str();
+    let at_path = ["layout", layout_id.as_str(), "auto_layout"];
+    let event = mk_model_event(&at_path, Some(&payload));
+    let result = model.perform_event(&event, &at_path).unwrap();
+
+    // the positions are changed by a chain of `update_position` events
+    let Consumed::Restart(events) = result else {
+        panic!()
+    };
+    assert_eq!(events.len(), 2);
+    let update_path = [
+        "sketch",
+        "model",
+        "layout",
+        layout_id.as_str(),
+        "update_position",
+    ];
+    for event in events {
+        assert_eq!(event.path, stringify_path(&update_path));
+        let at_path: Vec<&str> = event.path[2..].iter().map(|s| s.as_str()).collect();
+        model.perform_event(&event, &at_path).unwrap();
+    }
+    let position = |id: &str| {
+        let var_id = model.get_var_id(id).unwrap();
+        model
+            .get_node_position(&layout_id, &var_id)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(position("a"), NodePosition(0., -60.));
+    assert_eq!(position("b"), NodePosition(0., 60.));
+    assert_eq!(position("c"), NodePosition(0., 0.));
+
+    // repeating the layout changes nothing
+    let result = model.perform_event(&event, &at_path).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+}
+
+#[test]
+/// Test synchronizing regulation annotations with update functions via event.
+fn test_sync_regulation_annotations() {
+    let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+    model
+        .add_multiple_regulations(vec!["a -> b", "b -?? b"])
+        .unwrap();
+    let var_b = model.get_var_id("b").unwrap();
+    model.set_update_fn(&var_b, "!a").unwrap();
+
+    // the annotations are changed by a chain of `set_sign` and `set_essentiality` events
+    let at_path = ["regulation", "sync_annotations"];
+    let event = mk_model_event(&at_path, None);
+    let result = model.perform_event(&event, &at_path).unwrap();
+    let Consumed::Restart(events) = result else {
+        panic!()
+    };
+    let paths_and_payloads = events
+        .into_iter()
+        .map(|e| (e.path[3..].join("/"), e.payload.unwrap()))
+        .collect::<Vec<_>>();
+    let expected = vec![
+        (
+            "a/b/set_sign".to_string(),
+            Monotonicity::Inhibition.to_json_str(),
+        ),
+        (
+            "b/b/set_essentiality".to_string(),
+            Essentiality::False.to_json_str(),
+        ),
+    ];
+    assert_eq!(paths_and_payloads, expected);
+
+    // once the annotations match, nothing changes
+    model
+        .change_regulation_sign(&VarId::new("a").unwrap(), &var_b, &Monotonicity::Inhibition)
+        .unwrap();
+    model
+        .change_regulation_essentiality(&var_b, &var_b, &Essentiality::False)
+        .unwrap();
+    let result = model.perform_event(&event, &at_path).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+}
+
+#[test]
+/// Test setting update function together with adding the missing regulations via event.
+fn test_set_update_fn_with_regulations() {
+    let vars = vec![("a", "a"), ("b", "b"), ("c", "c")];
+    let mut model = ModelState::new_from_vars(vars).unwrap();
+    model.add_multiple_regulations(vec!["a -> c"]).unwrap();
+
+    // regulations `b -| c` and `c -? c` are missing, and they are added with the function
+    let expression = "a & !b | (c ^ a)";
+    let at_path = ["variable", "c", "set_update_fn"];
+    let payload = UpdateFnChangeData::new(expression, true).to_json_str();
+    let event = mk_model_event(&at_path, Some(&payload));
+    let result = model.perform_event(&event, &at_path).unwrap();
+    let Consumed::Restart(events) = result else {
+        panic!()
+    };
+    // events are executed from last to first, so the function is set after the regulations
+    assert_eq!(events.len(), 3);
+    let fn_path = stringify_path(&["sketch", "model", "variable", "c", "set_update_fn"]);
+    assert_eq!(events[0].path, fn_path);
+    assert_eq!(events[0].payload, Some(expression.to_string()));
+    let reg_add_path = stringify_path(&["sketch", "model", "regulation", "add"]);
+    assert_eq!(events[1].path, reg_add_path);
+    let reg_data = RegulationData::from_json_str(events[1].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        reg_data,
+        RegulationData::try_from_reg_str("b -|? c").unwrap()
+    );
+    assert_eq!(events[2].path, reg_add_path);
+    let reg_data = RegulationData::from_json_str(events[2].payload.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        reg_data,
+        RegulationData::try_from_reg_str("c -?? c").unwrap()
+    );
+
+    // invalid expressions are refused right away
+    let payload = UpdateFnChangeData::new("a & d", true).to_json_str();
+    let event = mk_model_event(&at_path, Some(&payload));
+    assert!(model.perform_event(&event, &at_path).is_err());
+}
+
+#[test]
+/// Test removing regulation together with rewriting the target's update function via event.
+fn test_remove_regulation_with_fn_rewrite() {
+    let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+    model
+        .add_multiple_regulations(vec!["a -> b", "b -| b"])
+        .unwrap();
+    let var_b = model.get_var_id("b").unwrap();
+    model.set_update_fn(&var_b, "!b | (a & b)").unwrap();
+
+    // occurrences of `a` are substituted with `false` (and the function is simplified), then
+    // the regulation is removed
+    let at_path = ["regulation", "a", "b", "remove_with_fn_rewrite"];
+    let event = mk_model_event(&at_path, Some("false"));
+    let result = model.perform_event(&event, &at_path).unwrap();
+    let Consumed::Restart(events) = result else {
+        panic!()
+    };
+    // events are executed from last to first, so the function is rewritten first
+    assert_eq!(events.len(), 2);
+    let remove_path = stringify_path(&["sketch", "model", "regulation", "a", "b", "remove"]);
+    assert_eq!(events[0].path, remove_path);
+    let fn_path = stringify_path(&["sketch", "model", "variable", "b", "set_update_fn"]);
+    assert_eq!(events[1].path, fn_path);
+    assert_eq!(events[1].payload, Some("!b".to_string()));
+
+    // if the function does not use the regulator, it is kept as is
+    model.set_update_fn(&var_b, "!b").unwrap();
+    let result = model.perform_event(&event, &at_path).unwrap();
+    let Consumed::Restart(events) = result else {
+        panic!()
+    };
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].path, remove_path);
+}
+
+#[test]
+/// Test converting update function and uninterpreted function's expression into normal forms
+/// via events.
+fn test_normalize_fn_expressions() {
+    let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+    model
+        .add_multiple_regulations(vec!["a -> b", "b -> b"])
+        .unwrap();
+    model.add_uninterpreted_fn_by_str("f", "f", 2).unwrap();
+    let var_b = model.get_var_id("b").unwrap();
+    let fn_f = model.get_uninterpreted_fn_id("f").unwrap();
+    model.set_update_fn(&var_b, "a & true | !(!b)").unwrap();
+    model
+        .set_uninterpreted_fn_expression(&fn_f, "var0 & (var1 | !var0)")
+        .unwrap();
+
+    // the update function is simplified via a standard `set_update_fn` event
+    let at_path = ["variable", "b", "normalize_update_fn"];
+    let payload = FnNormalForm::Simplified.to_json_str();
+    let event = mk_model_event(&at_path, Some(&payload));
+    let result = model.perform_event(&event, &at_path).unwrap();
+    let Consumed::Restart(events) = result else {
+        panic!()
+    };
+    assert_eq!(events.len(), 1);
+    let fn_path = ["sketch", "model", "variable", "b", "set_update_fn"];
+    assert_eq!(events[0].path, stringify_path(&fn_path));
+    assert_eq!(events[0].payload, Some("a | b".to_string()));
+
+    // the uninterpreted function's expression is converted via a `set_expression` event
+    let at_path = ["uninterpreted_fn", "f", "normalize_expression"];
+    let payload = FnNormalForm::Canonical.to_json_str();
+    let event = mk_model_event(&at_path, Some(&payload));
+    let result = model.perform_event(&event, &at_path).unwrap();
+    let Consumed::Restart(events) = result else {
+        panic!()
+    };
+    let expr_path = ["sketch", "model", "uninterpreted_fn", "f", "set_expression"];
+    assert_eq!(events[0].path, stringify_path(&expr_path));
+    assert_eq!(events[0].payload, Some("var0 & var1".to_string()));
+
+    // performing the restarted event changes the expression
+    let at_path: Vec<&str> = events[0].path[2..].iter().map(|s| s.as_str()).collect();
+    model.perform_event(&events[0], &at_path).unwrap();
+    let new_expression = model
+        .get_uninterpreted_fn(&fn_f)
+        .unwrap()
+        .get_fn_expression();
+    assert_eq!(new_expression, "var0 & var1");
+}
+
 #[test]
 /// Test changing monotonicity and essentiality of uninterpreted function's argument via event.
 fn test_change_fn_arg_monotonicity_essentiality() {
@@ -350,6 +573,15 @@ fn test_refresh() {
     assert_eq!(reg_list[0].target, var_a.to_string());
     assert_eq!(reg_list[0].regulator, var_a.to_string());
 
+    // test regulation mismatches getter (all regulations match the functions)
+    let full_path = stringify_path(&["sketch", "model", "get_regulation_mismatches"]);
+    let event = model
+        .refresh(&full_path, &["get_regulation_mismatches"])
+        .unwrap();
+    let mismatch_list: Vec<RegulationMismatchData> =
+        serde_json::from_str(&event.payload.unwrap()).unwrap();
+    assert!(mismatch_list.is_empty());
+
     // test layouts getter
     let full_path = stringify_path(&["sketch", "model", "get_layouts"]);
     let event = model.refresh(&full_path, &["get_layouts"]).unwrap();
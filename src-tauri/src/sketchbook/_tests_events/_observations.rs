@@ -1,9 +1,12 @@
 use crate::app::event::Event;
-use crate::app::state::SessionState;
+use crate::app::state::{Consumed, SessionState};
 use crate::sketchbook::_tests_events::{check_reverse, stringify_path};
 use crate::sketchbook::data_structs::*;
 use crate::sketchbook::ids::DatasetId;
-use crate::sketchbook::observations::{DataCategory, Dataset, Observation, ObservationManager};
+use crate::sketchbook::observations::{
+    BinarizationMethod, DataCategory, Dataset, DatasetImportOptions, Observation,
+    ObservationManager,
+};
 use crate::sketchbook::JsonSerde;
 
 /// Prepare a simple dataset with 3 variables and 2 observations (of fixed-point type).
@@ -55,6 +58,102 @@ fn test_add_remove_datasets() {
     check_reverse(&mut manager, &manager_orig, result, &["add"]);
 }
 
+#[test]
+/// Test loading dataset from a file via event, using custom import options.
+fn test_load_dataset_with_options() {
+    let mut manager = ObservationManager::new_empty();
+    let manager_orig = manager.clone();
+    let path = std::env::temp_dir().join("aeon_sketchbook_test_load_dataset.tsv");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "ID\ta\tb\no1\t1\tNA\n").unwrap();
+
+    let options = DatasetImportOptions {
+        missing_tokens: vec!["NA".to_string()],
+        ..DatasetImportOptions::tsv()
+    };
+    let payload = DatasetLoadData::new_with_options("d", path, options).to_json_str();
+    let full_path = ["observations", "load"];
+    let event = Event::build(&full_path, Some(payload.as_str()));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    let dataset = manager.get_dataset_by_str("d").unwrap();
+    assert_eq!(
+        dataset
+            .get_observation_on_idx(0)
+            .unwrap()
+            .to_values_string(),
+        "1*"
+    );
+    check_reverse(&mut manager, &manager_orig, result, &["d", "remove"]);
+
+    // payloads without options use the default (comma-separated) format, which fails here
+    let payload = format!("{{\"id\": \"d\", \"path\": \"{path}\"}}");
+    let event = Event::build(&full_path, Some(payload.as_str()));
+    assert!(manager.perform_event(&event, &full_path[1..]).is_err());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+/// Test exporting a dataset into a CSV file via event, and loading it back.
+fn test_export_dataset() {
+    let d1 = prepare_dataset_3v_2o();
+    let mut manager = ObservationManager::from_datasets(vec![("d1", d1.clone())]).unwrap();
+    let path = std::env::temp_dir().join("aeon_sketchbook_test_export_dataset.csv");
+    let path = path.to_str().unwrap();
+
+    let full_path = ["observations", "d1", "export"];
+    let event = Event::build(&full_path, Some(path));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        "ID,a,b,c\no1,*,1,1\no2,0,0,0\n"
+    );
+
+    // loading resets the category, otherwise the dataset is the same
+    let mut loaded = ObservationManager::load_dataset(path).unwrap();
+    loaded.set_category(*d1.category());
+    assert_eq!(loaded, d1);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+/// Test binarizing the raw data of a dataset again (using a different method) via event.
+fn test_binarize_dataset() {
+    let raw_values = vec![vec![Some(1.)], vec![Some(2.)], vec![Some(9.)]];
+    let dataset = Dataset::new_binarized(
+        vec!["o1", "o2", "o3"],
+        vec!["a"],
+        raw_values,
+        BinarizationMethod::Median,
+        DataCategory::Unspecified,
+    )
+    .unwrap();
+    let mut manager = ObservationManager::from_datasets(vec![("d", dataset)]).unwrap();
+    let manager_orig = manager.clone();
+
+    let payload = BinarizationMethod::KMeans.to_json_str();
+    let full_path = ["observations", "d", "binarize"];
+    let event = Event::build(&full_path, Some(payload.as_str()));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    let dataset = manager.get_dataset_by_str("d").unwrap();
+    let binarization = dataset.binarization().unwrap();
+    assert_eq!(binarization.get_method(), &BinarizationMethod::KMeans);
+    assert_eq!(
+        dataset
+            .get_observation_on_idx(1)
+            .unwrap()
+            .to_values_string(),
+        "0"
+    );
+    check_reverse(&mut manager, &manager_orig, result, &["d", "set_content"]);
+
+    // datasets without raw data cannot be binarized
+    let mut manager =
+        ObservationManager::from_datasets(vec![("d", prepare_dataset_2v_1o())]).unwrap();
+    let event = Event::build(&full_path, Some(payload.as_str()));
+    assert!(manager.perform_event(&event, &full_path[1..]).is_err());
+}
+
 #[test]
 /// Test setting various dataset fields via events.
 fn test_set_dataset_fields() {
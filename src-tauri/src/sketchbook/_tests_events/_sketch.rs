@@ -0,0 +1,513 @@
+use crate::app::event::Event;
+use crate::app::state::{Consumed, SessionState};
+use crate::sketchbook::_tests_events::{check_reverse, stringify_path};
+use crate::sketchbook::data_structs::{
+    BundleExportData, DiagnosticData, DiagnosticSeverity, InteractionImportData,
+    InvalidPropertyData, SketchBundleData, SketchData, SketchImportData, SketchMergeData,
+    StatPropertyData, SvgExportData, UninterpretedFnData,
+};
+use crate::sketchbook::event_utils::mk_model_event;
+use crate::sketchbook::ids::{DatasetId, DynPropertyId, ObservationId, StatPropertyId, VarId};
+use crate::sketchbook::model::{Essentiality, ModelState};
+use crate::sketchbook::observations::{
+    BinarizationMethod, DatasetImportOptions, ObservationManager,
+};
+use crate::sketchbook::properties::{PropertyManager, StatProperty};
+use crate::sketchbook::{JsonSerde, Sketch};
+
+/// Prepare a sketch with variables `a` and `b`, and a generic dynamic property `p` whose
+/// formula references both variables.
+fn prepare_test_sketch() -> Sketch {
+    let model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+    let mut properties = PropertyManager::new_empty();
+    let prop_id = DynPropertyId::new("p").unwrap();
+    properties
+        .add_dyn_generic(prop_id, "p", "a & EF b")
+        .unwrap();
+    Sketch::new(model, ObservationManager::new_empty(), properties)
+}
+
+#[test]
+/// Test that renaming a variable flags the properties that became invalid, and that the
+/// properties are valid again once the change is reverted.
+fn test_invalid_props_after_var_id_change() {
+    let mut sketch = prepare_test_sketch();
+    assert!(sketch.get_invalid_dyn_props().is_empty());
+
+    // rename variable `a` to `c`, which invalidates property `p`
+    let at_path = ["model", "variable", "a", "set_id"];
+    let event = mk_model_event(&at_path[1..], Some("c"));
+    let result = sketch.perform_event(&event, &at_path).unwrap();
+    let Consumed::Reversible {
+        state_change,
+        perform_reverse: (_, reverse),
+    } = result
+    else {
+        panic!()
+    };
+    let flag_event = sketch
+        .mk_invalid_props_state_change(&[state_change])
+        .unwrap();
+    assert_eq!(
+        flag_event.path,
+        stringify_path(&["sketch", "properties", "dynamic", "invalid_props"])
+    );
+    let invalid: Vec<InvalidPropertyData> =
+        serde_json::from_str(&flag_event.payload.unwrap()).unwrap();
+    assert_eq!(invalid.len(), 1);
+    assert_eq!(invalid[0].id, "p");
+    assert_eq!(
+        invalid[0].error,
+        "Proposition `a` at position 0 does not correspond to any variable of the model."
+    );
+
+    // revert the renaming, property is valid again
+    let reverse_path: Vec<&str> = reverse.path.iter().map(|s| s.as_str()).collect();
+    let result = sketch.perform_event(&reverse, &reverse_path[1..]).unwrap();
+    let Consumed::Reversible { state_change, .. } = result else {
+        panic!()
+    };
+    let flag_event = sketch
+        .mk_invalid_props_state_change(&[state_change])
+        .unwrap();
+    assert_eq!(flag_event.payload, Some("[]".to_string()));
+}
+
+#[test]
+/// Test that unrelated state changes do not trigger re-checking of properties.
+fn test_invalid_props_unrelated_change() {
+    let sketch = prepare_test_sketch();
+    let state_change = Event::build(&["sketch", "model", "variable", "set_name"], Some("{}"));
+    assert!(sketch
+        .mk_invalid_props_state_change(&[state_change])
+        .is_none());
+}
+
+#[test]
+/// Test that the diagnostics are recomputed after sketch changes, and that they can be
+/// obtained via a refresh event.
+fn test_diagnostics() {
+    let mut sketch = prepare_test_sketch();
+    let full_path = stringify_path(&["sketch", "get_diagnostics"]);
+    let event = sketch.refresh(&full_path, &["get_diagnostics"]).unwrap();
+    assert_eq!(event.payload, Some("[]".to_string()));
+
+    // adding an unused uninterpreted function produces a diagnostic
+    let payload = UninterpretedFnData::new("f", "f", &[], "").to_json_str();
+    let at_path = ["model", "uninterpreted_fn", "add"];
+    let event = mk_model_event(&at_path[1..], Some(&payload));
+    let result = sketch.perform_event(&event, &at_path).unwrap();
+    let Consumed::Reversible { state_change, .. } = result else {
+        panic!()
+    };
+    let diagnostics_event = sketch.mk_diagnostics_state_change(&[state_change]).unwrap();
+    assert_eq!(
+        diagnostics_event.path,
+        stringify_path(&["sketch", "diagnostics"])
+    );
+    let diagnostics: Vec<DiagnosticData> =
+        serde_json::from_str(&diagnostics_event.payload.unwrap()).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Info);
+    assert_eq!(diagnostics[0].path, ["model", "uninterpreted_fn", "f"]);
+
+    // changes outside the sketch do not trigger the linter
+    let state_change = Event::build(&["tab_bar", "active"], Some("1"));
+    assert!(sketch
+        .mk_diagnostics_state_change(&[state_change])
+        .is_none());
+}
+
+#[test]
+/// Test that contexts of static properties are validated against the model, both when adding
+/// a property and when modifying it.
+fn test_stat_prop_context_validation() {
+    let mut sketch = prepare_test_sketch();
+    let prop_id = StatPropertyId::new("ctx").unwrap();
+    let mk_payload = |context: &str| {
+        let property = StatProperty::mk_regulation_essential_context(
+            "ctx",
+            Some(VarId::new("a").unwrap()),
+            Some(VarId::new("b").unwrap()),
+            Essentiality::True,
+            context.to_string(),
+        )
+        .unwrap();
+        StatPropertyData::from_property(&prop_id, &property).to_json_str()
+    };
+
+    // context referencing an unknown variable is rejected, and nothing is added
+    let at_path = ["properties", "static", "add"];
+    let event = Event::build(
+        &["sketch", "properties", "static", "add"],
+        Some(&mk_payload("a & c")),
+    );
+    let result = sketch.perform_event(&event, &at_path).unwrap();
+    assert!(matches!(result, Consumed::InputError(..)));
+    assert_eq!(sketch.get_properties().num_stat_properties(), 0);
+
+    // valid context is accepted
+    let event = Event::build(
+        &["sketch", "properties", "static", "add"],
+        Some(&mk_payload("a & !b")),
+    );
+    let result = sketch.perform_event(&event, &at_path).unwrap();
+    assert!(matches!(result, Consumed::Reversible { .. }));
+
+    // modifying the context to an invalid one is rejected as well
+    let at_path = ["properties", "static", "ctx", "set_content"];
+    let event = Event::build(
+        &["sketch", "properties", "static", "ctx", "set_content"],
+        Some(&mk_payload("c")),
+    );
+    let result = sketch.perform_event(&event, &at_path).unwrap();
+    assert!(matches!(result, Consumed::InputError(..)));
+    let property = sketch.get_properties().get_stat_prop(&prop_id).unwrap();
+    assert_eq!(property.get_context(), Some("a & !b"));
+}
+
+#[test]
+/// Test exporting the model into an `.aeon` file, and importing it back.
+fn test_export_import_aeon() {
+    let mut sketch = prepare_test_sketch();
+    let path = std::env::temp_dir().join("aeon_sketchbook_test_export_import.aeon");
+    let path = path.to_str().unwrap();
+
+    let event = Event::build(&["sketch", "export_aeon"], Some(path));
+    let result = sketch.perform_event(&event, &["export_aeon"]).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+
+    // importing the model replaces the whole sketch (including properties)
+    let mut new_sketch = Sketch::default();
+    let event = Event::build(&["sketch", "import_aeon"], Some(path));
+    let result = new_sketch.perform_event(&event, &["import_aeon"]).unwrap();
+    assert!(matches!(result, Consumed::Irreversible { reset: true, .. }));
+    assert_eq!(new_sketch.get_model(), sketch.get_model());
+    assert_eq!(new_sketch.get_properties().num_dyn_properties(), 0);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+/// Test exporting the model into an SBML file (with warnings about uninterpreted fns),
+/// and importing it back.
+fn test_export_import_sbml() {
+    let mut sketch = prepare_test_sketch();
+    let path = std::env::temp_dir().join("aeon_sketchbook_test_export_import.sbml");
+    let path = path.to_str().unwrap();
+
+    let event = Event::build(&["sketch", "export_sbml"], Some(path));
+    let result = sketch.perform_event(&event, &["export_sbml"]).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+
+    let mut new_sketch = Sketch::default();
+    let event = Event::build(&["sketch", "import_sbml"], Some(path));
+    let result = new_sketch.perform_event(&event, &["import_sbml"]).unwrap();
+    assert!(matches!(result, Consumed::Irreversible { reset: true, .. }));
+    assert_eq!(new_sketch.get_model().num_vars(), 2);
+
+    // unused uninterpreted fns are reported
+    let mut model = sketch.get_model().clone();
+    model.add_uninterpreted_fn_by_str("f", "f", 1).unwrap();
+    let mut sketch = Sketch::new(
+        model,
+        ObservationManager::new_empty(),
+        PropertyManager::new_empty(),
+    );
+    let event = Event::build(&["sketch", "export_sbml"], Some(path));
+    let result = sketch.perform_event(&event, &["export_sbml"]).unwrap();
+    let Consumed::Irreversible { state_change, .. } = result else {
+        panic!()
+    };
+    let warnings: Vec<String> = serde_json::from_str(&state_change.payload.unwrap()).unwrap();
+    assert_eq!(warnings.len(), 1);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+/// Test that warnings produced while importing an SBML model are sent with the new sketch.
+fn test_import_sbml_warnings() {
+    // the model does not declare SBML-qual as a requirement
+    let sbml_str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <sbml xmlns="http://www.sbml.org/sbml/level3/version1/core" level="3" version="1"
+            xmlns:qual="http://www.sbml.org/sbml/level3/version1/qual/version1">
+        <model>
+            <qual:listOfQualitativeSpecies>
+                <qual:qualitativeSpecies qual:id="a" qual:maxLevel="1" qual:constant="false"/>
+            </qual:listOfQualitativeSpecies>
+            <qual:listOfTransitions/>
+        </model>
+        </sbml>"#;
+    let path = std::env::temp_dir().join("aeon_sketchbook_test_import_warnings.sbml");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, sbml_str).unwrap();
+
+    let mut sketch = Sketch::default();
+    let event = Event::build(&["sketch", "import_sbml"], Some(path));
+    let result = sketch.perform_event(&event, &["import_sbml"]).unwrap();
+    let Consumed::Irreversible {
+        state_change,
+        reset: true,
+    } = result
+    else {
+        panic!()
+    };
+    assert_eq!(state_change.path, ["sketch", "set_all_with_warnings"]);
+    let import_data = SketchImportData::from_json_str(&state_change.payload.unwrap()).unwrap();
+    let requirement_warning = "does not declare SBML-qual as a requirement";
+    assert!(import_data
+        .warnings
+        .iter()
+        .any(|w| w.contains(requirement_warning)));
+    assert_eq!(sketch.get_model().num_vars(), 1);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+/// Test exporting the sketch into a zip bundle (with datasets and notes), and importing it back.
+fn test_export_import_bundle() {
+    let model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+    let dataset = ObservationManager::load_dataset_from_str("ID,a,b\no1,0,1\no2,*,0\n").unwrap();
+    let raw_str = "ID,a,b\no1,0.5,12\no2,*,3.5\n";
+    let continuous = ObservationManager::load_continuous_dataset_from_str(
+        raw_str,
+        &DatasetImportOptions::default(),
+        BinarizationMethod::Threshold(10.),
+    )
+    .unwrap();
+    let datasets = vec![("d", dataset), ("e", continuous.clone())];
+    let observations = ObservationManager::from_datasets(datasets).unwrap();
+    let mut sketch = Sketch::new(model, observations, PropertyManager::new_empty());
+    let path = std::env::temp_dir().join("aeon_sketchbook_test_export_import.zip");
+    let path = path.to_str().unwrap();
+
+    let export_data = BundleExportData {
+        path: path.to_string(),
+        notes: "Some notes.".to_string(),
+    };
+    let payload = export_data.to_json_str();
+    let event = Event::build(&["sketch", "export_bundle"], Some(&payload));
+    let result = sketch.perform_event(&event, &["export_bundle"]).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+
+    let mut new_sketch = Sketch::default();
+    let event = Event::build(&["sketch", "import_bundle"], Some(path));
+    let result = new_sketch
+        .perform_event(&event, &["import_bundle"])
+        .unwrap();
+    let Consumed::Irreversible {
+        state_change,
+        reset: true,
+    } = result
+    else {
+        panic!()
+    };
+    let bundle_data = SketchBundleData::from_json_str(&state_change.payload.unwrap()).unwrap();
+    assert_eq!(bundle_data.notes, "Some notes.");
+    assert_eq!(new_sketch, sketch);
+    // raw values of the binarized dataset are kept
+    let dataset_id = DatasetId::new("e").unwrap();
+    let imported = new_sketch
+        .get_observations()
+        .get_dataset(&dataset_id)
+        .unwrap();
+    assert_eq!(imported.binarization(), continuous.binarization());
+
+    // the bundle also contains the datasets in the CSV format (raw values for binarized ones)
+    let file = std::fs::File::open(path).unwrap();
+    let mut archive = zip::ZipArchive::new(file).unwrap();
+    let mut read_csv = |name: &str| {
+        let mut csv_str = String::new();
+        let mut csv_file = archive.by_name(name).unwrap();
+        std::io::Read::read_to_string(&mut csv_file, &mut csv_str).unwrap();
+        csv_str
+    };
+    assert_eq!(read_csv("datasets/d.csv"), "ID,a,b\no1,0,1\no2,*,0\n");
+    assert_eq!(read_csv("datasets/e.csv"), "ID,a,b\no1,0,1\no2,*,0\n");
+    assert_eq!(read_csv("datasets/e.raw.csv"), raw_str);
+    assert!(archive.by_name("datasets/d.raw.csv").is_err());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+/// Test exporting HCTL formulas of the dataset-backed dynamic properties into a text file.
+fn test_export_dataset_hctl() {
+    let model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+    let dataset = ObservationManager::load_dataset_from_str("ID,a,b\no1,1,0\n").unwrap();
+    let observations = ObservationManager::from_datasets(vec![("d", dataset)]).unwrap();
+    let mut properties = PropertyManager::new_empty();
+    let dataset_id = Some(DatasetId::new("d").unwrap());
+    let obs_id = Some(ObservationId::new("o1").unwrap());
+    let prop_id = |id: &str| DynPropertyId::new(id).unwrap();
+    properties
+        .add_dyn_generic(prop_id("p"), "generic", "3{x}: @{x}: a")
+        .unwrap();
+    properties
+        .add_dyn_fixed_point(prop_id("fp"), "fixed point", dataset_id.clone(), obs_id)
+        .unwrap();
+    properties
+        .add_dyn_fixed_point(prop_id("fq"), "incomplete", dataset_id, None)
+        .unwrap();
+    let mut sketch = Sketch::new(model, observations, properties);
+    let path = std::env::temp_dir().join("aeon_sketchbook_test_export_hctl.txt");
+    let path = path.to_str().unwrap();
+
+    let event = Event::build(&["sketch", "export_dataset_hctl"], Some(path));
+    let result = sketch
+        .perform_event(&event, &["export_dataset_hctl"])
+        .unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+
+    // the generic property is not dataset-backed, and is skipped
+    let hctl_str = std::fs::read_to_string(path).unwrap();
+    let lines: Vec<&str> = hctl_str.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "# fp (fixed point), dataset `d`");
+    assert!(lines[1].starts_with("(3{x}: (@{x}: ((a & (~b))"));
+    assert_eq!(lines[2], "# fq (incomplete), dataset `d`");
+    assert_eq!(lines[3], "# error: Observation is not specified.");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+/// Test importing an interaction network in SIF, both replacing the sketch and merging into it.
+fn test_import_sif() {
+    let model = ModelState::new_from_vars(vec![("a", "a")]).unwrap();
+    let dataset = ObservationManager::load_dataset_from_str("ID,a\no1,0\n").unwrap();
+    let observations = ObservationManager::from_datasets(vec![("d", dataset)]).unwrap();
+    let mut sketch = Sketch::new(model, observations, PropertyManager::new_empty());
+    let path = std::env::temp_dir().join("aeon_sketchbook_test_import.sif");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "a\tactivates\tb\nb\tinhibits\ta\nb\tinhibits\ta\n").unwrap();
+
+    // merge into the existing model, keeping the datasets
+    let import_data = InteractionImportData {
+        path: path.to_string(),
+        merge: true,
+    };
+    let event = Event::build(&["sketch", "import_sif"], Some(&import_data.to_json_str()));
+    let result = sketch.perform_event(&event, &["import_sif"]).unwrap();
+    let Consumed::Irreversible {
+        state_change,
+        reset: true,
+    } = result
+    else {
+        panic!()
+    };
+    let sketch_data = SketchImportData::from_json_str(&state_change.payload.unwrap()).unwrap();
+    assert_eq!(sketch_data.warnings.len(), 1);
+    assert_eq!(sketch.get_model().num_vars(), 2);
+    assert_eq!(sketch.get_model().num_regulations(), 2);
+    assert_eq!(sketch.get_observations().num_datasets(), 1);
+
+    // replace the whole sketch
+    let import_data = InteractionImportData {
+        path: path.to_string(),
+        merge: false,
+    };
+    let event = Event::build(&["sketch", "import_sif"], Some(&import_data.to_json_str()));
+    sketch.perform_event(&event, &["import_sif"]).unwrap();
+    assert_eq!(sketch.get_model().num_regulations(), 2);
+    assert_eq!(sketch.get_observations().num_datasets(), 0);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+/// Test exporting the regulatory graph into DOT and SVG files.
+fn test_export_dot_svg() {
+    let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+    model.add_multiple_regulations(vec!["a -> b"]).unwrap();
+    let mut sketch = Sketch::new(
+        model,
+        ObservationManager::new_empty(),
+        PropertyManager::new_empty(),
+    );
+    let path = std::env::temp_dir().join("aeon_sketchbook_test_export_graph");
+    let path = path.to_str().unwrap();
+
+    let event = Event::build(&["sketch", "export_dot"], Some(path));
+    let result = sketch.perform_event(&event, &["export_dot"]).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+    let dot_str = std::fs::read_to_string(path).unwrap();
+    assert_eq!(dot_str, sketch.get_model().to_dot());
+
+    let export_data = SvgExportData {
+        path: path.to_string(),
+        layout_id: ModelState::get_default_layout_id().to_string(),
+    };
+    let event = Event::build(&["sketch", "export_svg"], Some(&export_data.to_json_str()));
+    let result = sketch.perform_event(&event, &["export_svg"]).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+    let svg_str = std::fs::read_to_string(path).unwrap();
+    assert!(svg_str.starts_with("<svg"));
+    std::fs::remove_file(path).unwrap();
+
+    // exporting with an invalid layout fails
+    let export_data = SvgExportData {
+        path: path.to_string(),
+        layout_id: "invalid".to_string(),
+    };
+    let event = Event::build(&["sketch", "export_svg"], Some(&export_data.to_json_str()));
+    assert!(sketch.perform_event(&event, &["export_svg"]).is_err());
+}
+
+#[test]
+/// Test merging a sketch from a file as a single reversible action, and resolving a conflict.
+fn test_merge_sketch() {
+    let mut sketch = prepare_test_sketch();
+    let orig_sketch = sketch.clone();
+    let mut other_model = ModelState::new_from_vars(vec![("a", "a"), ("c", "c")]).unwrap();
+    other_model.add_regulation_by_str("c -> a").unwrap();
+    let var_a = other_model.get_var_id("a").unwrap();
+    other_model.set_update_fn(&var_a, "c").unwrap();
+    let other = Sketch::new(
+        other_model,
+        ObservationManager::new_empty(),
+        PropertyManager::new_empty(),
+    );
+    let path = std::env::temp_dir().join("aeon_sketchbook_test_merge.json");
+    let path = path.to_str().unwrap();
+    let other_data = SketchData::new(
+        other.get_model(),
+        other.get_observations(),
+        other.get_properties(),
+    );
+    std::fs::write(path, other_data.to_versioned_json_str()).unwrap();
+
+    let event = Event::build(&["sketch", "merge_sketch"], Some(path));
+    let result = sketch.perform_event(&event, &["merge_sketch"]).unwrap();
+    std::fs::remove_file(path).unwrap();
+    let Consumed::Reversible {
+        state_change,
+        perform_reverse: (perform, reverse),
+    } = result
+    else {
+        panic!()
+    };
+    assert_eq!(sketch.get_model().num_vars(), 3);
+    let merge_data = SketchMergeData::from_json_str(&state_change.payload.unwrap()).unwrap();
+    assert_eq!(merge_data.conflicts.len(), 1);
+    let merged_sketch = sketch.clone();
+
+    // resolve the conflict (different update functions of `a`), and revert the resolution
+    let payload = merge_data.conflicts[0].to_json_str();
+    let event = Event::build(&["sketch", "resolve_merge_conflict"], Some(&payload));
+    let result = sketch
+        .perform_event(&event, &["resolve_merge_conflict"])
+        .unwrap();
+    assert_eq!(
+        sketch.get_model().get_update_fn_string(&var_a).unwrap(),
+        "c"
+    );
+    check_reverse(
+        &mut sketch,
+        &merged_sketch,
+        result,
+        &["resolve_merge_conflict"],
+    );
+
+    // undo and redo the whole merge
+    sketch.perform_event(&reverse, &["set_sketch"]).unwrap();
+    assert_eq!(sketch, orig_sketch);
+    sketch.perform_event(&perform, &["set_sketch"]).unwrap();
+    assert_eq!(sketch, merged_sketch);
+}
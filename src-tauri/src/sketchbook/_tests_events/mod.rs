@@ -4,6 +4,8 @@ use crate::app::state::{Consumed, SessionState};
 mod _model;
 /// **(internal)** Tests for the event-based API of `ObservationManager`.
 mod _observations;
+/// **(internal)** Tests for the event-based API of `Sketch`.
+mod _sketch;
 
 /// Given a state of a manager class *after* a particular event is performed (`state_after_event`),
 /// check that by performing a reverse event, we get precisely the original state (`orig_state`).
@@ -0,0 +1,15 @@
+use crate::sketchbook::layout::LayoutAlgorithm;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data about automatically placing nodes of a layout. This includes
+/// the algorithm to use, and IDs of variables whose nodes should be placed (if the list is empty,
+/// all the nodes are placed).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AutoLayoutData {
+    pub algorithm: LayoutAlgorithm,
+    #[serde(default)]
+    pub variables: Vec<String>,
+}
+
+impl<'de> JsonSerde<'de> for AutoLayoutData {}
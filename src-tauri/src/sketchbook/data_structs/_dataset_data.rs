@@ -1,6 +1,8 @@
 use crate::sketchbook::data_structs::ObservationData;
 use crate::sketchbook::ids::DatasetId;
-use crate::sketchbook::observations::{DataCategory, Dataset, Observation};
+use crate::sketchbook::observations::{
+    Binarization, BinarizationMethod, DataCategory, Dataset, DatasetImportOptions, Observation,
+};
 use crate::sketchbook::JsonSerde;
 use serde::{Deserialize, Serialize};
 
@@ -14,6 +16,8 @@ pub struct DatasetData {
     pub observations: Vec<ObservationData>,
     pub variables: Vec<String>,
     pub category: DataCategory,
+    #[serde(default)]
+    pub binarization: Option<Binarization>,
 }
 
 /// Structure for sending *metadata* about `Dataset`. This includes id, variable names, data type,
@@ -29,11 +33,19 @@ pub struct DatasetMetaData {
 }
 
 /// Structure for receiving *metadata* about `Dataset` to load from a file.
-/// This includes just an id and path to load it from.
+/// This includes an id, path to load it from, and options describing the file format
+/// (default options are used if they are not provided).
+///
+/// If a binarization method is given, the file is expected to contain continuous (numeric)
+/// data, which are binarized using this method.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DatasetLoadData {
     pub id: String,
     pub path: String,
+    #[serde(default)]
+    pub options: DatasetImportOptions,
+    #[serde(default)]
+    pub binarization: Option<BinarizationMethod>,
 }
 
 impl<'de> JsonSerde<'de> for DatasetData {}
@@ -54,6 +66,7 @@ impl DatasetData {
             observations,
             variables,
             category: *dataset.category(),
+            binarization: dataset.binarization().cloned(),
         }
     }
 
@@ -66,7 +79,9 @@ impl DatasetData {
             .map(|o| o.to_observation())
             .collect::<Result<Vec<Observation>, String>>()?;
         let variables = self.variables.iter().map(|v| v.as_str()).collect();
-        Dataset::new(observations, variables, self.category)
+        let mut dataset = Dataset::new(observations, variables, self.category)?;
+        dataset.set_binarization(self.binarization.clone())?;
+        Ok(dataset)
     }
 }
 
@@ -83,11 +98,36 @@ impl DatasetMetaData {
 }
 
 impl DatasetLoadData {
-    /// Create new `DatasetLoadData` instance.
+    /// Create new `DatasetLoadData` instance, with default import options.
     pub fn new(id: &str, path: &str) -> DatasetLoadData {
+        Self::new_with_options(id, path, DatasetImportOptions::default())
+    }
+
+    /// Create new `DatasetLoadData` instance with given import options.
+    pub fn new_with_options(
+        id: &str,
+        path: &str,
+        options: DatasetImportOptions,
+    ) -> DatasetLoadData {
         DatasetLoadData {
             id: id.to_string(),
             path: path.to_string(),
+            options,
+            binarization: None,
+        }
+    }
+
+    /// Create new `DatasetLoadData` instance for continuous data that are binarized using
+    /// a given method.
+    pub fn new_continuous(
+        id: &str,
+        path: &str,
+        options: DatasetImportOptions,
+        method: BinarizationMethod,
+    ) -> DatasetLoadData {
+        DatasetLoadData {
+            binarization: Some(method),
+            ..Self::new_with_options(id, path, options)
         }
     }
 }
@@ -0,0 +1,39 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Possible severities of a diagnostic produced by the sketch linter.
+///
+/// - `Error` means the sketch is inconsistent (e.g., something references a missing component)
+/// - `Warning` means the sketch is consistent, but most likely not what the user intended
+/// - `Info` means a harmless redundancy
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// Structure for sending a single diagnostic about the sketch to the frontend.
+///
+/// The `path` identifies the affected component in the same way as the event paths do (e.g.,
+/// `["model", "variable", "A"]` or `["properties", "dynamic", "p1"]`).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticData {
+    pub severity: DiagnosticSeverity,
+    pub path: Vec<String>,
+    pub message: String,
+}
+
+impl<'de> JsonSerde<'de> for DiagnosticSeverity {}
+impl<'de> JsonSerde<'de> for DiagnosticData {}
+
+impl DiagnosticData {
+    /// Create new `DiagnosticData` object given its severity, component path, and message.
+    pub fn new(severity: DiagnosticSeverity, path: &[&str], message: &str) -> DiagnosticData {
+        DiagnosticData {
+            severity,
+            path: path.iter().map(|s| s.to_string()).collect(),
+            message: message.to_string(),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::sketchbook::data_structs::SketchData;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data about an interaction network (SIF or GraphML) to import. This
+/// includes a path to the file, and whether the network should be merged into the current model
+/// (instead of replacing the whole sketch).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InteractionImportData {
+    pub path: String,
+    #[serde(default)]
+    pub merge: bool,
+}
+
+/// Structure for sending data about a sketch after an import that might have produced warnings
+/// (such as duplicate or conflicting interactions). This includes data about the whole sketch,
+/// and the list of warnings.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SketchImportData {
+    pub sketch: SketchData,
+    pub warnings: Vec<String>,
+}
+
+impl<'de> JsonSerde<'de> for InteractionImportData {}
+impl<'de> JsonSerde<'de> for SketchImportData {}
@@ -0,0 +1,29 @@
+use crate::sketchbook::ids::DynPropertyId;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for sending data about a property that is not valid with respect to the rest of
+/// the sketch (for instance, its formula references a variable that no longer exists) to the
+/// frontend. The `error` describes why the property is invalid.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InvalidPropertyData {
+    pub id: String,
+    pub error: String,
+}
+
+impl<'de> JsonSerde<'de> for InvalidPropertyData {}
+
+impl InvalidPropertyData {
+    /// Create new `InvalidPropertyData` object given the property's ID and error message.
+    pub fn new(id: &str, error: &str) -> InvalidPropertyData {
+        InvalidPropertyData {
+            id: id.to_string(),
+            error: error.to_string(),
+        }
+    }
+
+    /// Create new `InvalidPropertyData` object given a dynamic property's ID and error message.
+    pub fn from_dyn_prop(id: &DynPropertyId, error: &str) -> InvalidPropertyData {
+        Self::new(id.as_str(), error)
+    }
+}
@@ -0,0 +1,86 @@
+use crate::sketchbook::data_structs::{
+    DatasetData, DynPropertyData, RegulationData, SketchData, StatPropertyData,
+    UninterpretedFnData, VariableData,
+};
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for sending data about a single conflict found when merging two sketches. It
+/// contains a description of the conflict, and both versions of the conflicting component: the
+/// `current` one (kept in the merged sketch), and the `incoming` one (from the merged sketch).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConflictData<T> {
+    pub message: String,
+    pub current: T,
+    pub incoming: T,
+}
+
+/// Enum covering all kinds of conflicts found when merging two sketches, one variant for each
+/// kind of sketch component that is merged by ID.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "component")]
+pub enum MergeConflictData {
+    Variable(ConflictData<VariableData>),
+    Regulation(ConflictData<RegulationData>),
+    UninterpretedFn(ConflictData<UninterpretedFnData>),
+    Dataset(ConflictData<DatasetData>),
+    DynProperty(ConflictData<DynPropertyData>),
+    StatProperty(ConflictData<StatPropertyData>),
+}
+
+/// Structure for sending data about a sketch after a merge. This includes data about the whole
+/// (merged) sketch, and the list of conflicts to resolve.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SketchMergeData {
+    pub sketch: SketchData,
+    pub conflicts: Vec<MergeConflictData>,
+}
+
+impl<'de> JsonSerde<'de> for MergeConflictData {}
+impl<'de> JsonSerde<'de> for SketchMergeData {}
+
+impl<T: Clone> ConflictData<T> {
+    /// Create new `ConflictData` object given both versions of the component.
+    pub fn new(message: &str, current: &T, incoming: &T) -> ConflictData<T> {
+        ConflictData {
+            message: message.to_string(),
+            current: current.clone(),
+            incoming: incoming.clone(),
+        }
+    }
+
+    /// Create a copy of this conflict with the `current` and `incoming` versions swapped.
+    pub fn swapped(&self) -> ConflictData<T> {
+        ConflictData::new(&self.message, &self.incoming, &self.current)
+    }
+}
+
+impl MergeConflictData {
+    /// Get the description of this conflict.
+    pub fn message(&self) -> &str {
+        match self {
+            MergeConflictData::Variable(c) => &c.message,
+            MergeConflictData::Regulation(c) => &c.message,
+            MergeConflictData::UninterpretedFn(c) => &c.message,
+            MergeConflictData::Dataset(c) => &c.message,
+            MergeConflictData::DynProperty(c) => &c.message,
+            MergeConflictData::StatProperty(c) => &c.message,
+        }
+    }
+
+    /// Create a copy of this conflict with the `current` and `incoming` versions swapped.
+    ///
+    /// Resolving the swapped conflict reverts the resolution of the original one.
+    pub fn swapped(&self) -> MergeConflictData {
+        match self {
+            MergeConflictData::Variable(c) => MergeConflictData::Variable(c.swapped()),
+            MergeConflictData::Regulation(c) => MergeConflictData::Regulation(c.swapped()),
+            MergeConflictData::UninterpretedFn(c) => {
+                MergeConflictData::UninterpretedFn(c.swapped())
+            }
+            MergeConflictData::Dataset(c) => MergeConflictData::Dataset(c.swapped()),
+            MergeConflictData::DynProperty(c) => MergeConflictData::DynProperty(c.swapped()),
+            MergeConflictData::StatProperty(c) => MergeConflictData::StatProperty(c.swapped()),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::sketchbook::model::{Essentiality, Monotonicity, Regulation};
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for sending data about a regulation whose annotation (monotonicity or essentiality)
+/// does not match the effect inferred from the fully specified update function of its target.
+///
+/// The `sign` and `essential` fields hold the current annotation, the `inferred_*` fields hold
+/// the values deduced from the update function. The `message` describes the mismatch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegulationMismatchData {
+    pub regulator: String,
+    pub target: String,
+    pub sign: Monotonicity,
+    pub essential: Essentiality,
+    pub inferred_sign: Monotonicity,
+    pub inferred_essential: Essentiality,
+    pub message: String,
+}
+
+impl<'de> JsonSerde<'de> for RegulationMismatchData {}
+
+impl RegulationMismatchData {
+    /// Create new `RegulationMismatchData` object given the annotated `regulation` and the
+    /// inferred values. The message is generated automatically.
+    pub fn new(
+        regulation: &Regulation,
+        inferred_sign: Monotonicity,
+        inferred_essential: Essentiality,
+    ) -> RegulationMismatchData {
+        let regulator = regulation.get_regulator().to_string();
+        let target = regulation.get_target().to_string();
+        let sign = *regulation.get_sign();
+        let essential = *regulation.get_essentiality();
+
+        let mut issues = Vec::new();
+        if sign != inferred_sign {
+            issues.push(format!(
+                "monotonicity is `{}`, but the update function implies `{}`",
+                sign.as_str_full(),
+                inferred_sign.as_str_full()
+            ));
+        }
+        if essential != inferred_essential {
+            issues.push(format!(
+                "essentiality is `{essential:?}`, but the update function implies `{inferred_essential:?}`"
+            ));
+        }
+        let message = format!(
+            "Regulation `{regulator}` -> `{target}`: {}.",
+            issues.join("; ")
+        );
+
+        RegulationMismatchData {
+            regulator,
+            target,
+            sign,
+            essential,
+            inferred_sign,
+            inferred_essential,
+            message,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::sketchbook::data_structs::SketchData;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data about a sketch bundle to export. This includes a path to the
+/// bundle file, and free-form notes to store in it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BundleExportData {
+    pub path: String,
+    pub notes: String,
+}
+
+/// Structure for sending data about an imported sketch bundle. This includes data about the
+/// whole sketch, and notes stored in the bundle.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SketchBundleData {
+    pub sketch: SketchData,
+    pub notes: String,
+}
+
+impl<'de> JsonSerde<'de> for BundleExportData {}
+impl<'de> JsonSerde<'de> for SketchBundleData {}
@@ -4,6 +4,24 @@ use crate::sketchbook::observations::ObservationManager;
 use crate::sketchbook::properties::PropertyManager;
 use crate::sketchbook::JsonSerde;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Current version of the schema of exported sketch files. It must be incremented (and a
+/// corresponding migration added to [MIGRATIONS]) whenever the structure of `SketchData`
+/// (or any of its parts) changes.
+pub const SKETCH_SCHEMA_VERSION: u64 = 2;
+
+/// Name of the JSON field carrying the schema version in exported sketch files.
+const VERSION_FIELD: &str = "schema_version";
+
+/// **(internal)** A migration transforming the JSON of a sketch in one schema version to the
+/// next version.
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+/// **(internal)** List of migrations between subsequent schema versions. The migration at index
+/// `i` transforms a sketch of version `i` into version `i + 1`.
+const MIGRATIONS: [Migration; SKETCH_SCHEMA_VERSION as usize] =
+    [migrate_v0_to_v1, migrate_v1_to_v2];
 
 /// Structure for sending/exporting data about the whole Sketch.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -43,4 +61,122 @@ impl SketchData {
             stat_properties,
         }
     }
+
+    /// Serialize the sketch into a pretty JSON string that carries the current schema version
+    /// (see [SKETCH_SCHEMA_VERSION]). This is the format used for exporting sketches to files.
+    pub fn to_versioned_json_str(&self) -> String {
+        // serialization of the sketch data cannot fail, we can unwrap
+        let mut json_value = serde_json::to_value(self).unwrap();
+        if let Value::Object(fields) = &mut json_value {
+            fields.insert(
+                VERSION_FIELD.to_string(),
+                Value::from(SKETCH_SCHEMA_VERSION),
+            );
+        }
+        serde_json::to_string_pretty(&json_value).unwrap()
+    }
+
+    /// Parse the sketch from a JSON string produced by [Self::to_versioned_json_str], possibly
+    /// by an older version of the application.
+    ///
+    /// Files of older schema versions are upgraded to the current version by applying all the
+    /// subsequent migrations. Files without a version field are treated as version `0` (sketches
+    /// exported before the versioning was introduced). Files of newer (unknown) versions are
+    /// rejected.
+    pub fn from_versioned_json_str(json_str: &str) -> Result<SketchData, String> {
+        let mut json_value: Value = serde_json::from_str(json_str).map_err(|e| e.to_string())?;
+        let Value::Object(fields) = &mut json_value else {
+            return Err("Sketch file must contain a JSON object.".to_string());
+        };
+        let version = match fields.remove(VERSION_FIELD) {
+            None => 0,
+            Some(version) => version.as_u64().ok_or(format!(
+                "Invalid schema version `{version}` of the sketch file."
+            ))?,
+        };
+        if version > SKETCH_SCHEMA_VERSION {
+            return Err(format!(
+                "Sketch file has schema version {version}, but only versions up to \
+                {SKETCH_SCHEMA_VERSION} are supported. Update the application to load it."
+            ));
+        }
+
+        for migration in &MIGRATIONS[version as usize..] {
+            migration(&mut json_value)?;
+        }
+        serde_json::from_value(json_value)
+            .map_err(|e| format!("Invalid sketch file (schema version {version}): {e}"))
+    }
+}
+
+/// **(internal)** Migrate sketch from version `0` (no version field) to version `1`.
+///
+/// The structure of the data did not change, version `1` only introduced the version field.
+fn migrate_v0_to_v1(_json_value: &mut Value) -> Result<(), String> {
+    Ok(())
+}
+
+/// **(internal)** Migrate sketch from version `1` to version `2`.
+///
+/// Version `2` added the (optional) binarization data to datasets, which is empty for all
+/// datasets of older sketches.
+fn migrate_v1_to_v2(json_value: &mut Value) -> Result<(), String> {
+    let datasets = json_value
+        .get_mut("datasets")
+        .and_then(|d| d.as_array_mut())
+        .ok_or("Invalid sketch file: missing list of datasets.".to_string())?;
+    for dataset in datasets {
+        let Value::Object(fields) = dataset else {
+            return Err("Invalid sketch file: dataset must be a JSON object.".to_string());
+        };
+        fields.insert("binarization".to_string(), Value::Null);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::data_structs::{SketchData, SKETCH_SCHEMA_VERSION};
+    use crate::sketchbook::model::ModelState;
+    use crate::sketchbook::observations::{Dataset, ObservationManager};
+    use crate::sketchbook::properties::PropertyManager;
+    use crate::sketchbook::JsonSerde;
+
+    #[test]
+    /// Test exporting and importing versioned sketch data, including the migration of legacy
+    /// files without version, and rejecting files of unknown newer versions.
+    fn test_versioned_sketch_data() {
+        let model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        let dataset = Dataset::new_unspecified(vec![], vec!["a", "b"]).unwrap();
+        let observations = ObservationManager::from_datasets(vec![("d", dataset)]).unwrap();
+        let sketch_data = SketchData::new(&model, &observations, &PropertyManager::new_empty());
+
+        let versioned_str = sketch_data.to_versioned_json_str();
+        assert!(versioned_str.contains(&format!("\"schema_version\": {SKETCH_SCHEMA_VERSION}")));
+        let imported = SketchData::from_versioned_json_str(&versioned_str).unwrap();
+        assert_eq!(imported, sketch_data);
+
+        // legacy files without version are still loadable
+        let legacy_str = sketch_data.to_pretty_json_str();
+        let imported = SketchData::from_versioned_json_str(&legacy_str).unwrap();
+        assert_eq!(imported, sketch_data);
+
+        // files of version 1 have no binarization data in datasets
+        let mut v1_json = serde_json::to_value(&sketch_data).unwrap();
+        v1_json["datasets"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("binarization");
+        v1_json["schema_version"] = serde_json::Value::from(1);
+        let imported = SketchData::from_versioned_json_str(&v1_json.to_string()).unwrap();
+        assert_eq!(imported, sketch_data);
+
+        let newer_str = versioned_str.replace(
+            &format!("\"schema_version\": {SKETCH_SCHEMA_VERSION}"),
+            &format!("\"schema_version\": {}", SKETCH_SCHEMA_VERSION + 1),
+        );
+        let error = SketchData::from_versioned_json_str(&newer_str).unwrap_err();
+        assert!(error.contains("Update the application"));
+        assert!(SketchData::from_versioned_json_str("[1, 2]").is_err());
+    }
 }
@@ -0,0 +1,12 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data about a regulatory graph figure to export as SVG. This includes
+/// a path to the SVG file, and ID of the layout used to place the nodes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SvgExportData {
+    pub path: String,
+    pub layout_id: String,
+}
+
+impl<'de> JsonSerde<'de> for SvgExportData {}
@@ -15,7 +15,20 @@ pub struct VariableData {
     pub update_fn: String,
 }
 
+/// Structure for receiving a new update function from the frontend, together with the option
+/// to add all regulations that the function needs, but that are missing in the model.
+///
+/// The `set_update_fn` event accepts either this structure (encoded as JSON), or just the
+/// expression as a plain string (in which case no regulations are added).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UpdateFnChangeData {
+    pub expression: String,
+    #[serde(default)]
+    pub add_regulations: bool,
+}
+
 impl<'de> JsonSerde<'de> for VariableData {}
+impl<'de> JsonSerde<'de> for UpdateFnChangeData {}
 
 impl VariableData {
     /// Create new `VariableData` object given a variable's `name` and `id` string slices.
@@ -42,3 +55,26 @@ impl VariableData {
         Variable::new(self.name.as_str())
     }
 }
+
+impl UpdateFnChangeData {
+    /// Create new `UpdateFnChangeData` object given the new expression and whether missing
+    /// regulations should be added.
+    pub fn new(expression: &str, add_regulations: bool) -> UpdateFnChangeData {
+        UpdateFnChangeData {
+            expression: expression.to_string(),
+            add_regulations,
+        }
+    }
+
+    /// Parse the payload of the `set_update_fn` event, which is either a JSON-encoded
+    /// `UpdateFnChangeData`, or just a plain expression.
+    ///
+    /// Expressions never start with `{`, so the two formats cannot be confused.
+    pub fn from_payload(payload: &str) -> Result<UpdateFnChangeData, String> {
+        if payload.trim_start().starts_with('{') {
+            UpdateFnChangeData::from_json_str(payload)
+        } else {
+            Ok(UpdateFnChangeData::new(payload, false))
+        }
+    }
+}
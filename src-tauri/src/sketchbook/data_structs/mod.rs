@@ -1,5 +1,9 @@
+/// **(internal)** Definition and utility methods for `AutoLayoutData`.
+mod _auto_layout_data;
 /// **(internal)** Definition and utility methods for `DatasetData` and `DatasetMetaData`.
 mod _dataset_data;
+/// **(internal)** Definition and utility methods for `DiagnosticData` and `DiagnosticSeverity`.
+mod _diagnostic_data;
 /// **(internal)** Definition and utility methods for all kinds of dynamic properties.
 mod _dynamic_prop_data;
 /// **(internal)** Definition and utility methods for `ChangeArgMonotoneData`
@@ -7,35 +11,56 @@ mod _dynamic_prop_data;
 mod _fn_arg_change_data;
 /// **(internal)** Definition and utility methods for `ChangeIdData`.
 mod _id_change_data;
+/// **(internal)** Definition and utility methods for `InteractionImportData`
+/// and `SketchImportData`.
+mod _interaction_import_data;
+/// **(internal)** Definition and utility methods for `InvalidPropertyData`.
+mod _invalid_property_data;
 /// **(internal)** Definition and utility methods for `LayoutData` and `LayoutMetaData`.
 mod _layout_data;
 /// **(internal)** Definition and utility methods for `LayoutNodeData`.
 mod _layout_node_data;
+/// **(internal)** Definition and utility methods for `MergeConflictData` and `SketchMergeData`.
+mod _merge_conflict_data;
 /// **(internal)** Definition and utility methods for `ModelData`.
 mod _model_data;
 /// **(internal)** Definition and utility methods for `ObservationData`.
 mod _observation_data;
 /// **(internal)** Definition and utility methods for `RegulationData`.
 mod _regulation_data;
+/// **(internal)** Definition and utility methods for `RegulationMismatchData`.
+mod _regulation_mismatch_data;
+/// **(internal)** Definition and utility methods for `BundleExportData` and `SketchBundleData`.
+mod _sketch_bundle_data;
 /// **(internal)** Definition and utility methods for `SketchData`.
 mod _sketch_data;
 /// **(internal)** Definition and utility methods for all kinds of static properties.
 mod _static_prop_data;
+/// **(internal)** Definition and utility methods for `SvgExportData`.
+mod _svg_export_data;
 /// **(internal)** Definition and utility methods for `UninterpretedFnData`.
 mod _uninterpreted_fn_data;
-/// **(internal)** Definition and utility methods for `VariableData`.
+/// **(internal)** Definition and utility methods for `VariableData` and `UpdateFnChangeData`.
 mod _variable_data;
 
+pub use _auto_layout_data::AutoLayoutData;
 pub use _dataset_data::{DatasetData, DatasetLoadData, DatasetMetaData};
+pub use _diagnostic_data::{DiagnosticData, DiagnosticSeverity};
 pub use _dynamic_prop_data::{DynPropertyData, DynPropertyDefaultData};
 pub use _fn_arg_change_data::{ChangeArgEssentialData, ChangeArgMonotoneData};
 pub use _id_change_data::ChangeIdData;
+pub use _interaction_import_data::{InteractionImportData, SketchImportData};
+pub use _invalid_property_data::InvalidPropertyData;
 pub use _layout_data::{LayoutData, LayoutMetaData};
 pub use _layout_node_data::LayoutNodeData;
+pub use _merge_conflict_data::{ConflictData, MergeConflictData, SketchMergeData};
 pub use _model_data::ModelData;
 pub use _observation_data::ObservationData;
 pub use _regulation_data::RegulationData;
-pub use _sketch_data::SketchData;
+pub use _regulation_mismatch_data::RegulationMismatchData;
+pub use _sketch_bundle_data::{BundleExportData, SketchBundleData};
+pub use _sketch_data::{SketchData, SKETCH_SCHEMA_VERSION};
 pub use _static_prop_data::{StatPropertyData, StatPropertyDefaultData};
+pub use _svg_export_data::SvgExportData;
 pub use _uninterpreted_fn_data::UninterpretedFnData;
-pub use _variable_data::VariableData;
+pub use _variable_data::{UpdateFnChangeData, VariableData};
@@ -0,0 +1,16 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Possible algorithms for automatically placing nodes of the regulatory graph in a `Layout`.
+///
+/// - `ForceDirected` simulates regulations as springs between repelling nodes
+/// - `Hierarchical` places nodes into layers (rows), so that most regulations point downwards
+/// - `Circular` places nodes evenly on a circle
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum LayoutAlgorithm {
+    ForceDirected,
+    Hierarchical,
+    Circular,
+}
+
+impl<'de> JsonSerde<'de> for LayoutAlgorithm {}
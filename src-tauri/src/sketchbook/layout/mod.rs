@@ -2,12 +2,15 @@ use crate::sketchbook::ids::VarId;
 
 /// **(internal)** Utility methods for `Layout`.
 mod _layout;
+/// **(internal)** Definition of `LayoutAlgorithm`.
+mod _layout_algorithm;
 /// **(internal)**  Utility methods for `NodeLayout`.
 mod _node_layout;
 /// **(internal)**  Utility methods for `NodePosition`.
 mod _node_position;
 
 pub use _layout::Layout;
+pub use _layout_algorithm::LayoutAlgorithm;
 pub use _node_layout::LayoutNode;
 pub use _node_position::NodePosition;
 
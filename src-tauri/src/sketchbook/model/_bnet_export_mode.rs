@@ -0,0 +1,17 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Possible ways of exporting partially specified update functions into the `.bnet` format,
+/// which can only express fully specified Boolean networks.
+///
+/// - `Strict` means that unspecified update functions and update functions with uninterpreted
+///   functions are rejected (with an error)
+/// - `FreeInputs` means that the corresponding variables are exported as free inputs (with
+///   an identity update function)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum BnetExportMode {
+    Strict,
+    FreeInputs,
+}
+
+impl<'de> JsonSerde<'de> for BnetExportMode {}
@@ -0,0 +1,23 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Possible forms into which the expression of an update function or uninterpreted function
+/// can be converted (see `FnTree::to_normal_form`).
+///
+/// - `Simplified` means constant propagation and removal of double negations
+/// - `Dnf` means disjunctive normal form (a disjunction of conjunctions of literals)
+/// - `Cnf` means conjunctive normal form (a conjunction of disjunctions of literals)
+/// - `Canonical` means a minimized DNF computed for each fully specified subtree, which is the
+///   same for all equivalent subtrees
+///
+/// In the normal forms, applications of uninterpreted functions are treated as atoms (their
+/// arguments are converted separately).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum FnNormalForm {
+    Simplified,
+    Dnf,
+    Cnf,
+    Canonical,
+}
+
+impl<'de> JsonSerde<'de> for FnNormalForm {}
@@ -1,12 +1,13 @@
 use crate::sketchbook::ids::{UninterpretedFnId, VarId};
-use crate::sketchbook::model::{BinaryOp, ModelState, UninterpretedFn};
+use crate::sketchbook::model::{BinaryOp, Essentiality, ModelState, Monotonicity, UninterpretedFn};
+use biodivine_lib_bdd::{Bdd, BddVariableSet};
 use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// Syntactic tree of a partially defined Boolean function.
 /// This might specify an update function, or a partially defined uninterpreted fn.
-#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub enum FnTree {
     /// A true/false constant.
     Const(bool),
@@ -236,6 +237,152 @@ impl FnTree {
         params
     }
 
+    /// Deduce the monotonicity of all variables used in this function syntactically, from the
+    /// polarity of their occurrences.
+    ///
+    /// A variable occurring only positively (under an even number of negations) is an
+    /// `Activation`, a variable occurring only negatively is an `Inhibition`. Variables with
+    /// both kinds of occurrences, or occurring as arguments of uninterpreted functions or
+    /// in `Xor`/`Iff` operations, get `Unknown`. Note that this is only a syntactic
+    /// approximation, and the function does not even have to depend on the variables.
+    pub fn syntactic_monotonicity(&self) -> HashMap<VarId, Monotonicity> {
+        /// Collect the polarities of occurrences as (has positive, has negative) pairs.
+        fn r_polarity(
+            function: &FnTree,
+            positive: bool,
+            negative: bool,
+            polarities: &mut HashMap<VarId, (bool, bool)>,
+        ) {
+            match function {
+                FnTree::Const(_) => (),
+                FnTree::Var(id) | FnTree::PlaceholderVar(id) => {
+                    let entry = polarities.entry(id.clone()).or_insert((false, false));
+                    entry.0 |= positive;
+                    entry.1 |= negative;
+                }
+                FnTree::UninterpretedFn(_, args) => {
+                    for arg in args {
+                        r_polarity(arg, true, true, polarities);
+                    }
+                }
+                FnTree::Not(inner) => r_polarity(inner, negative, positive, polarities),
+                FnTree::Binary(op, l, r) => match op {
+                    BinaryOp::And | BinaryOp::Or => {
+                        r_polarity(l, positive, negative, polarities);
+                        r_polarity(r, positive, negative, polarities);
+                    }
+                    BinaryOp::Imp => {
+                        r_polarity(l, negative, positive, polarities);
+                        r_polarity(r, positive, negative, polarities);
+                    }
+                    BinaryOp::Xor | BinaryOp::Iff => {
+                        r_polarity(l, true, true, polarities);
+                        r_polarity(r, true, true, polarities);
+                    }
+                },
+            };
+        }
+        let mut polarities = HashMap::new();
+        r_polarity(self, true, false, &mut polarities);
+        polarities
+            .into_iter()
+            .map(|(var, polarity)| {
+                let monotonicity = match polarity {
+                    (true, false) => Monotonicity::Activation,
+                    (false, true) => Monotonicity::Inhibition,
+                    _ => Monotonicity::Unknown,
+                };
+                (var, monotonicity)
+            })
+            .collect()
+    }
+
+    /// Compute the actual (semantic) effect of each of the given variables on this function,
+    /// i.e., the monotonicity and essentiality the corresponding regulations must have.
+    ///
+    /// A variable is essential if the function value depends on it. An essential variable is
+    /// an `Activation` (`Inhibition`) if raising its value can never decrease (increase) the
+    /// function value, and `Dual` otherwise. Non-essential variables get `Unknown` monotonicity.
+    ///
+    /// The function must be fully specified. Returns `Err` if it contains uninterpreted
+    /// functions.
+    pub fn semantic_effects(
+        &self,
+        variables: &[VarId],
+    ) -> Result<HashMap<VarId, (Monotonicity, Essentiality)>, String> {
+        if !self.collect_fn_symbols().is_empty() {
+            return Err("Cannot compute effects for function with uninterpreted symbols.".into());
+        }
+        let mut bdd_var_names = self
+            .collect_variables()
+            .into_iter()
+            .chain(variables.iter().cloned())
+            .map(|v| v.to_string())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        bdd_var_names.sort();
+        let bdd_vars = BddVariableSet::new(
+            &bdd_var_names
+                .iter()
+                .map(|name| name.as_str())
+                .collect::<Vec<_>>(),
+        );
+        let bdd = self.to_bdd(&bdd_vars);
+
+        let mut effects = HashMap::new();
+        for var_id in variables {
+            let bdd_var = bdd_vars.var_by_name(var_id.as_str()).unwrap();
+            let low = bdd.var_restrict(bdd_var, false);
+            let high = bdd.var_restrict(bdd_var, true);
+            let effect = if low == high {
+                (Monotonicity::Unknown, Essentiality::False)
+            } else {
+                let monotonicity =
+                    match (low.and_not(&high).is_false(), high.and_not(&low).is_false()) {
+                        (true, _) => Monotonicity::Activation,
+                        (_, true) => Monotonicity::Inhibition,
+                        _ => Monotonicity::Dual,
+                    };
+                (monotonicity, Essentiality::True)
+            };
+            effects.insert(var_id.clone(), effect);
+        }
+        Ok(effects)
+    }
+
+    /// **(internal)** Build a BDD representing this function, using the given set of BDD
+    /// variables (named by the IDs of the function's variables).
+    ///
+    /// The function must not contain uninterpreted functions (this is not checked).
+    pub(super) fn to_bdd(&self, bdd_vars: &BddVariableSet) -> Bdd {
+        match self {
+            FnTree::Const(value) => {
+                if *value {
+                    bdd_vars.mk_true()
+                } else {
+                    bdd_vars.mk_false()
+                }
+            }
+            FnTree::Var(id) | FnTree::PlaceholderVar(id) => bdd_vars.mk_var_by_name(id.as_str()),
+            FnTree::UninterpretedFn(..) => {
+                unreachable!("Uninterpreted functions are not supported.")
+            }
+            FnTree::Not(inner) => inner.to_bdd(bdd_vars).not(),
+            FnTree::Binary(op, l, r) => {
+                let l = l.to_bdd(bdd_vars);
+                let r = r.to_bdd(bdd_vars);
+                match op {
+                    BinaryOp::And => l.and(&r),
+                    BinaryOp::Or => l.or(&r),
+                    BinaryOp::Xor => l.xor(&r),
+                    BinaryOp::Iff => l.iff(&r),
+                    BinaryOp::Imp => l.imp(&r),
+                }
+            }
+        }
+    }
+
     /// Use this function as a template to create a new one, but substitute a given network
     /// variable's ID with a new one.
     ///
@@ -269,6 +416,36 @@ impl FnTree {
         }
     }
 
+    /// Use this function as a template to create a new one, but substitute all occurrences of
+    /// a given network variable with a Boolean constant `value`.
+    pub fn substitute_var_with_const(&self, var_id: &VarId, value: bool) -> FnTree {
+        match self {
+            FnTree::Const(_) | FnTree::PlaceholderVar(_) => self.clone(),
+            FnTree::Var(id) => {
+                if id == var_id {
+                    FnTree::Const(value)
+                } else {
+                    self.clone()
+                }
+            }
+            FnTree::UninterpretedFn(id, args) => {
+                let new_args = args
+                    .iter()
+                    .map(|it| it.substitute_var_with_const(var_id, value))
+                    .collect::<Vec<_>>();
+                FnTree::UninterpretedFn(id.clone(), new_args)
+            }
+            FnTree::Not(inner) => {
+                FnTree::Not(Box::new(inner.substitute_var_with_const(var_id, value)))
+            }
+            FnTree::Binary(op, l, r) => FnTree::Binary(
+                *op,
+                Box::new(l.substitute_var_with_const(var_id, value)),
+                Box::new(r.substitute_var_with_const(var_id, value)),
+            ),
+        }
+    }
+
     /// Use this function as a template to create a new one, but substitute a given uninterpreted
     /// function's ID with a new one.
     pub fn substitute_fn_symbol(
@@ -303,7 +480,7 @@ impl FnTree {
 
 #[cfg(test)]
 mod tests {
-    use crate::sketchbook::model::{FnTree, ModelState};
+    use crate::sketchbook::model::{Essentiality, FnTree, ModelState, Monotonicity};
     use std::collections::HashSet;
 
     #[test]
@@ -407,6 +584,11 @@ mod tests {
         // function symbol substitution
         let modified_tree = fn_tree.substitute_fn_symbol(&f, &g);
         assert_eq!(modified_tree.to_string(&model, None), "a & g(a)");
+
+        // substitution of a variable with a constant
+        let fn_tree = FnTree::try_from_str("!a | f(a & b)", &model, None).unwrap();
+        let modified_tree = fn_tree.substitute_var_with_const(&a, true);
+        assert_eq!(modified_tree.to_string(&model, None), "!true | f(true & b)");
     }
 
     #[test]
@@ -438,4 +620,69 @@ mod tests {
         let expected_vars = HashSet::from([a, b]);
         assert_eq!(expected_vars, collected_vars);
     }
+
+    #[test]
+    /// Test deducing monotonicity of variables syntactically.
+    fn test_syntactic_monotonicity() {
+        let mut model =
+            ModelState::new_from_vars(vec![("a", "a"), ("b", "b"), ("c", "c")]).unwrap();
+        model
+            .add_multiple_variables(vec![("d", "d"), ("e", "e")])
+            .unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 1).unwrap();
+
+        let expression = "!(a & !b) | (c => a) | (d ^ d) | f(e)";
+        let fn_tree = FnTree::try_from_str(expression, &model, None).unwrap();
+        let monotonicity = fn_tree.syntactic_monotonicity();
+        let var = |id: &str| model.get_var_id(id).unwrap();
+        assert_eq!(monotonicity.len(), 5);
+        assert_eq!(monotonicity[&var("a")], Monotonicity::Unknown);
+        assert_eq!(monotonicity[&var("b")], Monotonicity::Activation);
+        assert_eq!(monotonicity[&var("c")], Monotonicity::Inhibition);
+        assert_eq!(monotonicity[&var("d")], Monotonicity::Unknown);
+        assert_eq!(monotonicity[&var("e")], Monotonicity::Unknown);
+    }
+
+    #[test]
+    /// Test computing the semantic effects of variables on a fully specified function.
+    fn test_semantic_effects() {
+        let model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b"), ("c", "c"), ("d", "d")])
+            .unwrap();
+        let var = |id: &str| model.get_var_id(id).unwrap();
+        let vars = vec![var("a"), var("b"), var("c"), var("d")];
+
+        // `a` is syntactically non-monotonic, but semantically an activation, `d` does not occur
+        let expression = "(a & !b) | (a & b) | (c ^ b)";
+        let fn_tree = FnTree::try_from_str(expression, &model, None).unwrap();
+        let effects = fn_tree.semantic_effects(&vars).unwrap();
+        assert_eq!(effects.len(), 4);
+        let activation = (Monotonicity::Activation, Essentiality::True);
+        assert_eq!(effects[&var("a")], activation);
+        assert_eq!(effects[&var("b")], (Monotonicity::Dual, Essentiality::True));
+        assert_eq!(effects[&var("c")], (Monotonicity::Dual, Essentiality::True));
+        assert_eq!(
+            effects[&var("d")],
+            (Monotonicity::Unknown, Essentiality::False)
+        );
+
+        // `b` occurs, but has no effect
+        let fn_tree = FnTree::try_from_str("!a | (b & !b)", &model, None).unwrap();
+        let effects = fn_tree.semantic_effects(&vars[..2]).unwrap();
+        let inhibition = (Monotonicity::Inhibition, Essentiality::True);
+        assert_eq!(effects[&var("a")], inhibition);
+        assert_eq!(
+            effects[&var("b")],
+            (Monotonicity::Unknown, Essentiality::False)
+        );
+    }
+
+    #[test]
+    /// Test that semantic effects cannot be computed for partially specified functions.
+    fn test_semantic_effects_uninterpreted() {
+        let mut model = ModelState::new_from_vars(vec![("a", "a")]).unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 1).unwrap();
+        let fn_tree = FnTree::try_from_str("f(a)", &model, None).unwrap();
+        let a = model.get_var_id("a").unwrap();
+        assert!(fn_tree.semantic_effects(&[a]).is_err());
+    }
 }
@@ -0,0 +1,427 @@
+use crate::sketchbook::model::{BinaryOp, FnNormalForm, FnTree};
+use biodivine_lib_bdd::{BddPartialValuation, BddVariableSet};
+use std::collections::{BTreeMap, HashSet};
+
+/// Maximal number of clauses of a normal form. Conversions whose (intermediate) results would
+/// be larger fail with an error instead, since normal forms can grow exponentially.
+pub const MAX_NORMAL_FORM_CLAUSES: usize = 1000;
+
+/// **(internal)** A literal of a normal form, i.e., an atom (a variable or an application of
+/// an uninterpreted function) together with its polarity.
+type Literal = (FnTree, bool);
+/// **(internal)** A clause of a normal form, i.e., a list of literals. Depending on the form,
+/// it represents either a conjunction (DNF), or a disjunction (CNF).
+type Clause = Vec<Literal>;
+
+/// Simplification of function trees and their conversion into normal forms.
+///
+/// None of the methods modifies the tree, a new (equivalent) tree is always returned.
+impl FnTree {
+    /// Convert this function into the given normal form (see [FnNormalForm]).
+    ///
+    /// Returns an error if the normal form would have more than [MAX_NORMAL_FORM_CLAUSES]
+    /// clauses.
+    pub fn to_normal_form(&self, form: FnNormalForm) -> Result<FnTree, String> {
+        match form {
+            FnNormalForm::Simplified => Ok(self.simplify()),
+            FnNormalForm::Dnf => self.to_dnf(),
+            FnNormalForm::Cnf => self.to_cnf(),
+            FnNormalForm::Canonical => self.to_canonical(),
+        }
+    }
+
+    /// Simplify this function by propagating constants, removing double negations, and
+    /// reducing binary operations with two syntactically equal operands (e.g., `a & a` or
+    /// `a ^ a`). The tree is otherwise kept as it is.
+    pub fn simplify(&self) -> FnTree {
+        match self {
+            FnTree::Const(_) | FnTree::Var(_) | FnTree::PlaceholderVar(_) => self.clone(),
+            FnTree::UninterpretedFn(id, args) => {
+                let new_args = args.iter().map(|it| it.simplify()).collect();
+                FnTree::UninterpretedFn(id.clone(), new_args)
+            }
+            FnTree::Not(inner) => mk_not(inner.simplify()),
+            FnTree::Binary(op, l, r) => mk_binary(*op, l.simplify(), r.simplify()),
+        }
+    }
+
+    /// Convert this function into a disjunctive normal form. Applications of uninterpreted
+    /// functions are treated as atoms, and their arguments are converted into DNF separately.
+    ///
+    /// Contradictory conjunctions and duplicate literals are removed, but the result is not
+    /// minimized otherwise (see [FnTree::to_canonical]). Since the result might be exponentially
+    /// larger than the original function, an error is returned if the DNF (or any intermediate
+    /// result) would have more than [MAX_NORMAL_FORM_CLAUSES] clauses.
+    pub fn to_dnf(&self) -> Result<FnTree, String> {
+        let clauses = self.dnf_clauses(true, FnNormalForm::Dnf)?;
+        Ok(mk_normal_form(clauses, BinaryOp::And, BinaryOp::Or))
+    }
+
+    /// Convert this function into a conjunctive normal form. Applications of uninterpreted
+    /// functions are treated as atoms, and their arguments are converted into CNF separately.
+    ///
+    /// Tautological disjunctions and duplicate literals are removed, but the result is not
+    /// minimized otherwise. Same as with [FnTree::to_dnf], an error is returned if the CNF
+    /// would have more than [MAX_NORMAL_FORM_CLAUSES] clauses.
+    pub fn to_cnf(&self) -> Result<FnTree, String> {
+        // the CNF is the negation of a DNF of the negated function (De Morgan)
+        let clauses = self
+            .dnf_clauses(false, FnNormalForm::Cnf)?
+            .into_iter()
+            .map(|clause| clause.into_iter().map(|(atom, p)| (atom, !p)).collect())
+            .collect();
+        Ok(mk_normal_form(clauses, BinaryOp::Or, BinaryOp::And))
+    }
+
+    /// Convert all fully specified subtrees of this function (the maximal ones, without
+    /// uninterpreted functions) into a canonical minimized DNF.
+    ///
+    /// The canonical form is computed from a BDD (with variables ordered by their IDs), so it
+    /// is the same for all equivalent fully specified functions, and they can be compared
+    /// structurally. The parts of the tree with uninterpreted functions are only simplified.
+    ///
+    /// Returns an error if any of the canonical DNFs would have more than
+    /// [MAX_NORMAL_FORM_CLAUSES] clauses.
+    pub fn to_canonical(&self) -> Result<FnTree, String> {
+        if self.collect_fn_symbols().is_empty() {
+            return self.canonical_dnf();
+        }
+        let tree = match self {
+            FnTree::UninterpretedFn(id, args) => {
+                let new_args = args
+                    .iter()
+                    .map(|it| it.to_canonical())
+                    .collect::<Result<Vec<_>, _>>()?;
+                FnTree::UninterpretedFn(id.clone(), new_args)
+            }
+            FnTree::Not(inner) => FnTree::Not(Box::new(inner.to_canonical()?)),
+            FnTree::Binary(op, l, r) => FnTree::Binary(
+                *op,
+                Box::new(l.to_canonical()?),
+                Box::new(r.to_canonical()?),
+            ),
+            // leaves contain no uninterpreted functions, they are handled above
+            _ => self.clone(),
+        };
+        Ok(tree.simplify())
+    }
+
+    /// **(internal)** Compute the canonical minimized DNF of a fully specified function.
+    ///
+    /// The DNF is computed directly from the BDD, and the computation is interrupted once it
+    /// exceeds [MAX_NORMAL_FORM_CLAUSES] clauses.
+    fn canonical_dnf(&self) -> Result<FnTree, String> {
+        let mut leaves = BTreeMap::new();
+        self.collect_leaves(&mut leaves);
+        let names = leaves.keys().map(|name| name.as_str()).collect::<Vec<_>>();
+        let bdd_vars = BddVariableSet::new(&names);
+        let bdd = self.to_bdd(&bdd_vars);
+        // sort the cubes, so that positive literals of variables with smaller IDs go first
+        let check_size = |dnf: &[BddPartialValuation]| check_clause_count(dnf.len());
+        let mut cubes = bdd
+            ._to_optimized_dnf(true, &check_size)?
+            .into_iter()
+            .map(|cube| cube.to_values())
+            .collect::<Vec<_>>();
+        cubes.sort_by_key(|cube| cube.iter().map(|(v, val)| (*v, !*val)).collect::<Vec<_>>());
+        let clauses = cubes
+            .into_iter()
+            .map(|cube| {
+                cube.into_iter()
+                    .map(|(var, value)| (leaves[&bdd_vars.name_of(var)].clone(), value))
+                    .collect()
+            })
+            .collect();
+        Ok(mk_normal_form(clauses, BinaryOp::And, BinaryOp::Or))
+    }
+
+    /// **(internal)** Collect all variable leaves of this function, indexed by their IDs.
+    fn collect_leaves(&self, leaves: &mut BTreeMap<String, FnTree>) {
+        match self {
+            FnTree::Const(_) => (),
+            FnTree::Var(id) | FnTree::PlaceholderVar(id) => {
+                leaves.insert(id.to_string(), self.clone());
+            }
+            FnTree::UninterpretedFn(_, args) => {
+                args.iter().for_each(|it| it.collect_leaves(leaves));
+            }
+            FnTree::Not(inner) => inner.collect_leaves(leaves),
+            FnTree::Binary(_, l, r) => {
+                l.collect_leaves(leaves);
+                r.collect_leaves(leaves);
+            }
+        }
+    }
+
+    /// **(internal)** Compute the clauses of a DNF of this function (if `positive`), or of its
+    /// negation (otherwise). Arguments of uninterpreted functions are converted into `form`.
+    ///
+    /// Fails if any intermediate list of clauses exceeds [MAX_NORMAL_FORM_CLAUSES].
+    fn dnf_clauses(&self, positive: bool, form: FnNormalForm) -> Result<Vec<Clause>, String> {
+        match self {
+            FnTree::Const(value) => {
+                if *value == positive {
+                    Ok(vec![Vec::new()])
+                } else {
+                    Ok(Vec::new())
+                }
+            }
+            FnTree::Var(_) | FnTree::PlaceholderVar(_) => Ok(vec![vec![(self.clone(), positive)]]),
+            FnTree::UninterpretedFn(id, args) => {
+                let new_args = args
+                    .iter()
+                    .map(|it| it.to_normal_form(form))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let atom = FnTree::UninterpretedFn(id.clone(), new_args);
+                Ok(vec![vec![(atom, positive)]])
+            }
+            FnTree::Not(inner) => inner.dnf_clauses(!positive, form),
+            FnTree::Binary(op, l, r) => {
+                let dnf = |tree: &FnTree, positive: bool| tree.dnf_clauses(positive, form);
+                match (op, positive) {
+                    (BinaryOp::And, true) => clause_product(&dnf(l, true)?, &dnf(r, true)?),
+                    (BinaryOp::And, false) => clause_union(dnf(l, false)?, dnf(r, false)?),
+                    (BinaryOp::Or, true) => clause_union(dnf(l, true)?, dnf(r, true)?),
+                    (BinaryOp::Or, false) => clause_product(&dnf(l, false)?, &dnf(r, false)?),
+                    // `l => r` is `!l | r`, its negation is `l & !r`
+                    (BinaryOp::Imp, true) => clause_union(dnf(l, false)?, dnf(r, true)?),
+                    (BinaryOp::Imp, false) => clause_product(&dnf(l, true)?, &dnf(r, false)?),
+                    // `l ^ r` is `(l & !r) | (!l & r)`, and it is also the negation of `l <=> r`
+                    (BinaryOp::Xor, true) | (BinaryOp::Iff, false) => clause_union(
+                        clause_product(&dnf(l, true)?, &dnf(r, false)?)?,
+                        clause_product(&dnf(l, false)?, &dnf(r, true)?)?,
+                    ),
+                    // `l <=> r` is `(l & r) | (!l & !r)`, and it is also the negation of `l ^ r`
+                    (BinaryOp::Iff, true) | (BinaryOp::Xor, false) => clause_union(
+                        clause_product(&dnf(l, true)?, &dnf(r, true)?)?,
+                        clause_product(&dnf(l, false)?, &dnf(r, false)?)?,
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// **(internal)** Make a negation of the given tree, propagating constants and removing
+/// double negations.
+fn mk_not(tree: FnTree) -> FnTree {
+    match tree {
+        FnTree::Const(value) => FnTree::Const(!value),
+        FnTree::Not(inner) => *inner,
+        _ => FnTree::Not(Box::new(tree)),
+    }
+}
+
+/// **(internal)** Make a binary operation with the given operands, propagating constants and
+/// reducing operations with two syntactically equal operands.
+fn mk_binary(op: BinaryOp, l: FnTree, r: FnTree) -> FnTree {
+    use BinaryOp::{And, Iff, Imp, Or, Xor};
+    use FnTree::Const;
+    match (op, &l, &r) {
+        (And, Const(true), _) | (Or, Const(false), _) | (Xor, Const(false), _) => r,
+        (Iff, Const(true), _) | (Imp, Const(true), _) => r,
+        (And, _, Const(true)) | (Or, _, Const(false)) | (Xor, _, Const(false)) => l,
+        (Iff, _, Const(true)) => l,
+        (And, Const(false), _) | (And, _, Const(false)) => Const(false),
+        (Or, Const(true), _) | (Or, _, Const(true)) => Const(true),
+        (Imp, Const(false), _) | (Imp, _, Const(true)) => Const(true),
+        (Xor, Const(true), _) | (Iff, Const(false), _) => mk_not(r),
+        (Xor, _, Const(true)) | (Iff, _, Const(false)) | (Imp, _, Const(false)) => mk_not(l),
+        _ if l == r => match op {
+            And | Or => l,
+            Xor => Const(false),
+            Iff | Imp => Const(true),
+        },
+        _ => FnTree::Binary(op, Box::new(l), Box::new(r)),
+    }
+}
+
+/// **(internal)** Make a tree of a normal form given by its `clauses`. The literals of each
+/// clause are joined by `inner_op`, and the clauses are joined by `outer_op`.
+///
+/// Empty clauses and empty lists of clauses are converted to the neutral constants of the
+/// respective operators (`true` for `And`, `false` for `Or`).
+fn mk_normal_form(clauses: Vec<Clause>, inner_op: BinaryOp, outer_op: BinaryOp) -> FnTree {
+    let join = |op: BinaryOp, items: Vec<FnTree>| {
+        let neutral = FnTree::Const(op == BinaryOp::And);
+        items
+            .into_iter()
+            .reduce(|acc, item| mk_binary(op, acc, item))
+            .unwrap_or(neutral)
+    };
+    let clause_trees = clauses
+        .into_iter()
+        .map(|clause| {
+            let literals = clause
+                .into_iter()
+                .map(|(atom, positive)| if positive { atom } else { mk_not(atom) })
+                .collect();
+            join(inner_op, literals)
+        })
+        .collect();
+    join(outer_op, clause_trees)
+}
+
+/// **(internal)** Check that a normal form with `count` clauses does not exceed
+/// [MAX_NORMAL_FORM_CLAUSES].
+fn check_clause_count(count: usize) -> Result<(), String> {
+    if count > MAX_NORMAL_FORM_CLAUSES {
+        Err(format!(
+            "Normal form would have more than {MAX_NORMAL_FORM_CLAUSES} clauses."
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// **(internal)** Compute the conjunction of two DNFs given by their clauses (distributing
+/// the clauses). Contradictory clauses and duplicate literals or clauses are removed.
+///
+/// Fails if the result would have more than [MAX_NORMAL_FORM_CLAUSES] clauses.
+fn clause_product(left: &[Clause], right: &[Clause]) -> Result<Vec<Clause>, String> {
+    let mut result = Vec::new();
+    let mut seen = HashSet::new();
+    for l_clause in left {
+        'clauses: for r_clause in right {
+            let mut clause = l_clause.clone();
+            for (atom, positive) in r_clause {
+                if clause.contains(&(atom.clone(), !positive)) {
+                    continue 'clauses;
+                }
+                if !clause.contains(&(atom.clone(), *positive)) {
+                    clause.push((atom.clone(), *positive));
+                }
+            }
+            if seen.insert(clause.clone()) {
+                result.push(clause);
+                check_clause_count(result.len())?;
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// **(internal)** Compute the disjunction of two DNFs given by their clauses. Duplicate
+/// clauses are removed, and if any of the clauses is empty (i.e., `true`), the result is
+/// just that clause.
+///
+/// Fails if the result would have more than [MAX_NORMAL_FORM_CLAUSES] clauses.
+fn clause_union(left: Vec<Clause>, right: Vec<Clause>) -> Result<Vec<Clause>, String> {
+    let mut result: Vec<Clause> = Vec::new();
+    let mut seen = HashSet::new();
+    for clause in left.into_iter().chain(right) {
+        if clause.is_empty() {
+            return Ok(vec![clause]);
+        }
+        if seen.insert(clause.clone()) {
+            result.push(clause);
+        }
+    }
+    check_clause_count(result.len())?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::{FnNormalForm, FnTree, ModelState};
+
+    /// Prepare a model with variables `a`, `b`, `c`, and a unary uninterpreted function `f`.
+    fn prepare_model() -> ModelState {
+        let vars = vec![("a", "a"), ("b", "b"), ("c", "c")];
+        let mut model = ModelState::new_from_vars(vars).unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 1).unwrap();
+        model
+    }
+
+    /// Parse the expression, convert it into the given form, and print the result.
+    fn convert(model: &ModelState, expression: &str, form: FnNormalForm) -> String {
+        let fn_tree = FnTree::try_from_str(expression, model, None).unwrap();
+        fn_tree.to_normal_form(form).unwrap().to_string(model, None)
+    }
+
+    #[test]
+    /// Test constant propagation and removal of double negations.
+    fn test_simplify() {
+        let model = prepare_model();
+        let form = FnNormalForm::Simplified;
+        assert_eq!(convert(&model, "a & true | !(!b)", form), "a | b");
+        assert_eq!(convert(&model, "(a => false) ^ true", form), "a");
+        assert_eq!(convert(&model, "f(!!a & a) & (c | true)", form), "f(a)");
+        assert_eq!(convert(&model, "(b <=> b) & !(c ^ c)", form), "true");
+        // nothing to simplify
+        assert_eq!(convert(&model, "a & (b | !c)", form), "a & (b | !c)");
+    }
+
+    #[test]
+    /// Test conversion into disjunctive and conjunctive normal forms.
+    fn test_dnf_cnf() {
+        let model = prepare_model();
+        let (dnf, cnf) = (FnNormalForm::Dnf, FnNormalForm::Cnf);
+        assert_eq!(convert(&model, "a & (b | !c)", dnf), "(a & b) | (a & !c)");
+        assert_eq!(convert(&model, "a & (b | !c)", cnf), "a & (b | !c)");
+        assert_eq!(convert(&model, "a | (b & c)", dnf), "a | (b & c)");
+        assert_eq!(convert(&model, "a | (b & c)", cnf), "(a | b) & (a | c)");
+        assert_eq!(convert(&model, "a ^ b", dnf), "(a & !b) | (!a & b)");
+        assert_eq!(convert(&model, "a ^ b", cnf), "(!a | !b) & (a | b)");
+        assert_eq!(
+            convert(&model, "a => (b <=> c)", dnf),
+            "!a | (b & c) | (!b & !c)"
+        );
+        assert_eq!(
+            convert(&model, "a => (b <=> c)", cnf),
+            "(!a | !b | c) & (!a | b | !c)"
+        );
+        // contradictions and tautologies are removed
+        assert_eq!(convert(&model, "(a & !a) | b", dnf), "b");
+        assert_eq!(convert(&model, "(a | !a) & b", cnf), "b");
+        // applications of uninterpreted functions are atoms, arguments are converted too
+        assert_eq!(
+            convert(&model, "!(f(a | (b & c)) | c)", cnf),
+            "!f((a | b) & (a | c)) & !c"
+        );
+    }
+
+    #[test]
+    /// Test conversion into the canonical form, and that it is the same for equivalent functions.
+    fn test_canonical() {
+        let model = prepare_model();
+        let form = FnNormalForm::Canonical;
+        assert_eq!(convert(&model, "(a & b) | (a & !b)", form), "a");
+        assert_eq!(
+            convert(&model, "a => (b <=> c)", form),
+            "!a | (b & c) | (!b & !c)"
+        );
+        let equivalent = [
+            "!(a | b)",
+            "!a & !b",
+            "(a | b) ^ true",
+            "!(b | a) | (c & !c)",
+        ];
+        for expression in equivalent {
+            assert_eq!(convert(&model, expression, form), "!a & !b");
+        }
+        // only the fully specified subtrees are canonized
+        assert_eq!(convert(&model, "f(a & a) | (b & !b)", form), "f(a)");
+        assert_eq!(convert(&model, "f(b | a) & (c | !c)", form), "f(a | b)");
+    }
+
+    #[test]
+    /// Test that conversions into normal forms that would be too large fail.
+    fn test_normal_form_size_limit() {
+        let vars = (0..22).map(|i| format!("x{i}")).collect::<Vec<_>>();
+        let var_pairs = vars.iter().map(|v| (v.as_str(), v.as_str())).collect();
+        let model = ModelState::new_from_vars(var_pairs).unwrap();
+
+        // conjunction of 11 disjunctions `(x0 | x1) & (x2 | x3) & ...` has 2^11 DNF clauses
+        let expression = (0..11)
+            .map(|i| format!("(x{} | x{})", 2 * i, 2 * i + 1))
+            .collect::<Vec<_>>()
+            .join(" & ");
+        let fn_tree = FnTree::try_from_str(&expression, &model, None).unwrap();
+        assert!(fn_tree.to_normal_form(FnNormalForm::Dnf).is_err());
+        assert!(fn_tree.to_normal_form(FnNormalForm::Canonical).is_err());
+        // the CNF is small, and simplification is never limited
+        assert!(fn_tree.to_normal_form(FnNormalForm::Cnf).is_ok());
+        assert!(fn_tree.to_normal_form(FnNormalForm::Simplified).is_ok());
+    }
+}
@@ -0,0 +1,502 @@
+use crate::sketchbook::ids::{LayoutId, VarId};
+use crate::sketchbook::layout::{LayoutAlgorithm, NodePosition};
+use crate::sketchbook::model::ModelState;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
+
+/// **(internal)** Preferred distance between neighbouring nodes.
+const NODE_SPACING: f32 = 120.;
+/// **(internal)** Number of iterations of the force-directed simulation.
+const FORCE_ITERATIONS: usize = 300;
+/// **(internal)** Strength of the force pulling nodes towards the center in the force-directed
+/// simulation (so that disconnected components do not drift apart).
+const FORCE_GRAVITY: f32 = 0.05;
+/// **(internal)** Number of sweeps of the barycenter heuristic ordering nodes within layers.
+const BARYCENTER_SWEEPS: usize = 4;
+
+/// Methods for automatically placing the nodes of a `Layout` (see [LayoutAlgorithm]).
+///
+/// The selected nodes are placed around the center of their current positions, so that laying
+/// out only a part of the graph keeps it roughly in place. All the computation is deterministic.
+impl ModelState {
+    /// Compute new positions of the selected variables' nodes in the given layout, using the
+    /// given algorithm. If `selected` is `None`, all variables are placed. The positions of the
+    /// remaining nodes are not changed (they are pinned).
+    ///
+    /// The hierarchical and circular algorithms only consider regulations between the selected
+    /// variables, while the force-directed algorithm also considers regulations with the pinned
+    /// nodes (and keeps the selected nodes away from them).
+    ///
+    /// Returns the list of the new positions (sorted by variable IDs), without modifying the
+    /// layout. Returns `Err` if the layout or one of the variables does not exist.
+    pub fn compute_auto_layout(
+        &self,
+        layout_id: &LayoutId,
+        algorithm: LayoutAlgorithm,
+        selected: Option<&[VarId]>,
+    ) -> Result<Vec<(VarId, NodePosition)>, String> {
+        let layout = self.get_layout(layout_id)?;
+        let mut all_vars = self
+            .variables()
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        all_vars.sort();
+        let mut selected_vars = match selected {
+            None => all_vars.clone(),
+            Some(vars) => {
+                vars.iter()
+                    .try_for_each(|var_id| self.get_variable(var_id).map(|_| ()))?;
+                let unique: HashSet<_> = vars.iter().cloned().collect();
+                unique.into_iter().collect()
+            }
+        };
+        selected_vars.sort();
+        if selected_vars.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // move selected variables to the front, so that node indices `0..num_selected`
+        // correspond to the selected nodes
+        let selected_set: HashSet<_> = selected_vars.iter().collect();
+        let pinned_vars = all_vars.iter().filter(|v| !selected_set.contains(v));
+        let nodes = selected_vars
+            .iter()
+            .chain(pinned_vars)
+            .cloned()
+            .collect::<Vec<_>>();
+        let num_selected = selected_vars.len();
+        let indices: HashMap<&VarId, usize> =
+            nodes.iter().enumerate().map(|(i, v)| (v, i)).collect();
+        let mut edges = self
+            .regulations()
+            .map(|r| (indices[r.get_regulator()], indices[r.get_target()]))
+            .filter(|(u, v)| u != v)
+            .collect::<Vec<_>>();
+        edges.sort();
+
+        let positions = nodes
+            .iter()
+            .map(|var_id| {
+                let position = layout.get_node_position(var_id)?;
+                Ok((position.0, position.1))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let center = (
+            positions[..num_selected].iter().map(|p| p.0).sum::<f32>() / num_selected as f32,
+            positions[..num_selected].iter().map(|p| p.1).sum::<f32>() / num_selected as f32,
+        );
+        let selected_edges = edges
+            .iter()
+            .filter(|(u, v)| *u < num_selected && *v < num_selected)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let new_positions = match algorithm {
+            LayoutAlgorithm::Circular => {
+                let order = feedback_arc_order(num_selected, &selected_edges);
+                circular_layout(&order, center)
+            }
+            LayoutAlgorithm::Hierarchical => {
+                hierarchical_layout(num_selected, &selected_edges, center)
+            }
+            LayoutAlgorithm::ForceDirected => {
+                let order = feedback_arc_order(num_selected, &selected_edges);
+                let circular = circular_layout(&order, center);
+                force_directed_layout(&positions, num_selected, &edges, &circular, center)
+            }
+        };
+        Ok(selected_vars
+            .into_iter()
+            .zip(new_positions)
+            .map(|(var_id, (px, py))| (var_id, NodePosition(px, py)))
+            .collect())
+    }
+
+    /// Place the selected variables' nodes in the given layout using the given algorithm, and
+    /// update their positions. If `selected` is `None`, all variables are placed. See
+    /// [Self::compute_auto_layout] for details.
+    ///
+    /// Returns `Err` if the layout or one of the variables does not exist.
+    pub fn auto_layout(
+        &mut self,
+        layout_id: &LayoutId,
+        algorithm: LayoutAlgorithm,
+        selected: Option<&[VarId]>,
+    ) -> Result<(), String> {
+        let new_positions = self.compute_auto_layout(layout_id, algorithm, selected)?;
+        for (var_id, position) in new_positions {
+            self.update_position(layout_id, &var_id, position.0, position.1)?;
+        }
+        Ok(())
+    }
+}
+
+/// **(internal)** Order the nodes `0..num_nodes` so that as few edges as possible point
+/// backwards, using the greedy heuristic of Eades, Lin, and Smyth for the feedback arc set
+/// problem. Ties are broken by node indices, so the result is deterministic.
+fn feedback_arc_order(num_nodes: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut successors = vec![HashSet::new(); num_nodes];
+    let mut predecessors = vec![HashSet::new(); num_nodes];
+    for (u, v) in edges {
+        successors[*u].insert(*v);
+        predecessors[*v].insert(*u);
+    }
+
+    let mut remaining: Vec<usize> = (0..num_nodes).collect();
+    let (mut front, mut back) = (Vec::new(), Vec::new());
+    while !remaining.is_empty() {
+        // sinks go to the end, sources to the front, and otherwise, the node with the most
+        // outgoing (and least incoming) edges goes to the front
+        let sink = remaining.iter().find(|n| successors[**n].is_empty());
+        let source = remaining.iter().find(|n| predecessors[**n].is_empty());
+        let (node, is_sink) = match (sink, source) {
+            (Some(sink), _) => (*sink, true),
+            (None, Some(source)) => (*source, false),
+            (None, None) => {
+                let node = remaining.iter().max_by_key(|n| {
+                    let delta = successors[**n].len() as i64 - predecessors[**n].len() as i64;
+                    (delta, -(**n as i64))
+                });
+                (*node.unwrap(), false)
+            }
+        };
+        if is_sink {
+            back.push(node);
+        } else {
+            front.push(node);
+        }
+
+        // remove the node from the graph
+        remaining.retain(|n| *n != node);
+        for succ in std::mem::take(&mut successors[node]) {
+            predecessors[succ].remove(&node);
+        }
+        for pred in std::mem::take(&mut predecessors[node]) {
+            successors[pred].remove(&node);
+        }
+    }
+    back.reverse();
+    front.extend(back);
+    front
+}
+
+/// **(internal)** Place the nodes evenly on a circle around `center`, in the given order.
+/// Returns positions indexed by nodes (not by the order).
+fn circular_layout(order: &[usize], center: (f32, f32)) -> Vec<(f32, f32)> {
+    let num_nodes = order.len();
+    let radius = if num_nodes <= 1 {
+        0.
+    } else {
+        // neighbouring nodes are exactly `NODE_SPACING` apart
+        NODE_SPACING / (2. * (PI / num_nodes as f32).sin())
+    };
+    let mut positions = vec![(0., 0.); num_nodes];
+    for (i, node) in order.iter().enumerate() {
+        let angle = 2. * PI * i as f32 / num_nodes as f32 - PI / 2.;
+        positions[*node] = (
+            center.0 + radius * angle.cos(),
+            center.1 + radius * angle.sin(),
+        );
+    }
+    positions
+}
+
+/// **(internal)** Place the nodes `0..num_nodes` into layers (rows), so that edges point
+/// downwards. Edges of the feedback arc set (see [feedback_arc_order]) are reversed, the layers
+/// are given by the longest paths, and the order within layers is improved by the barycenter
+/// heuristic to reduce crossings. The resulting layout is centered at `center`.
+fn hierarchical_layout(
+    num_nodes: usize,
+    edges: &[(usize, usize)],
+    center: (f32, f32),
+) -> Vec<(f32, f32)> {
+    let order = feedback_arc_order(num_nodes, edges);
+    let mut rank = vec![0; num_nodes];
+    for (i, node) in order.iter().enumerate() {
+        rank[*node] = i;
+    }
+    // edges of the acyclic graph, all pointing forward in the order
+    let dag_edges = edges
+        .iter()
+        .map(|(u, v)| {
+            if rank[*u] < rank[*v] {
+                (*u, *v)
+            } else {
+                (*v, *u)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // longest-path layering (nodes are processed in a topological order)
+    let mut layer_of = vec![0; num_nodes];
+    for node in order.iter() {
+        layer_of[*node] = dag_edges
+            .iter()
+            .filter(|(_, v)| v == node)
+            .map(|(u, _)| layer_of[*u] + 1)
+            .max()
+            .unwrap_or(0);
+    }
+    let num_layers = layer_of.iter().max().map_or(0, |l| l + 1);
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); num_layers];
+    for node in order.iter() {
+        layers[layer_of[*node]].push(*node);
+    }
+
+    // barycenter heuristic, alternating downward and upward sweeps
+    let mut index_in_layer = vec![0.; num_nodes];
+    let update_indices = |layers: &[Vec<usize>], index_in_layer: &mut [f32]| {
+        for layer in layers {
+            for (i, node) in layer.iter().enumerate() {
+                index_in_layer[*node] = i as f32;
+            }
+        }
+    };
+    update_indices(&layers, &mut index_in_layer);
+    for sweep in 0..BARYCENTER_SWEEPS {
+        let downward = sweep % 2 == 0;
+        let layer_range: Vec<usize> = if downward {
+            (1..num_layers).collect()
+        } else {
+            (0..num_layers.saturating_sub(1)).rev().collect()
+        };
+        for l in layer_range {
+            let adjacent_layer = if downward { l - 1 } else { l + 1 };
+            let barycenter = |node: usize| -> f32 {
+                let neighbours = dag_edges
+                    .iter()
+                    .filter_map(|(u, v)| match (*u == node, *v == node) {
+                        (true, _) => Some(*v),
+                        (_, true) => Some(*u),
+                        _ => None,
+                    })
+                    .filter(|n| layer_of[*n] == adjacent_layer)
+                    .map(|n| index_in_layer[n])
+                    .collect::<Vec<_>>();
+                if neighbours.is_empty() {
+                    index_in_layer[node]
+                } else {
+                    neighbours.iter().sum::<f32>() / neighbours.len() as f32
+                }
+            };
+            let mut keyed = layers[l]
+                .iter()
+                .map(|n| (barycenter(*n), *n))
+                .collect::<Vec<_>>();
+            keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+            layers[l] = keyed.into_iter().map(|(_, n)| n).collect();
+            update_indices(&layers, &mut index_in_layer);
+        }
+    }
+
+    let height = (num_layers.saturating_sub(1)) as f32 * NODE_SPACING;
+    let mut positions = vec![(0., 0.); num_nodes];
+    for (l, layer) in layers.iter().enumerate() {
+        let width = (layer.len() - 1) as f32 * NODE_SPACING;
+        for (i, node) in layer.iter().enumerate() {
+            positions[*node] = (
+                center.0 + i as f32 * NODE_SPACING - width / 2.,
+                center.1 + l as f32 * NODE_SPACING - height / 2.,
+            );
+        }
+    }
+    positions
+}
+
+/// **(internal)** Place the first `num_selected` nodes using the force-directed algorithm of
+/// Fruchterman and Reingold, treating edges as undirected springs. Other nodes are pinned at
+/// their `positions`, but they still affect the selected ones.
+///
+/// The simulation starts from the current positions of the selected nodes. Nodes that overlap
+/// with some other node (such as nodes at the default position) start at the `fallback`
+/// positions instead. Returns the positions of the selected nodes.
+fn force_directed_layout(
+    positions: &[(f32, f32)],
+    num_selected: usize,
+    edges: &[(usize, usize)],
+    fallback: &[(f32, f32)],
+    center: (f32, f32),
+) -> Vec<(f32, f32)> {
+    let mut positions = positions.to_vec();
+    for i in 0..num_selected {
+        let overlaps =
+            (0..positions.len()).any(|j| j != i && distance(positions[i], positions[j]) < 1.);
+        if overlaps {
+            positions[i] = fallback[i];
+        }
+    }
+    let springs: HashSet<(usize, usize)> = edges
+        .iter()
+        .map(|(u, v)| (*u.min(v), *u.max(v)))
+        .filter(|(u, _)| *u < num_selected)
+        .collect();
+    let mut springs = springs.into_iter().collect::<Vec<_>>();
+    springs.sort();
+
+    let k = NODE_SPACING;
+    let initial_temperature = NODE_SPACING * (num_selected as f32).sqrt();
+    for iteration in 0..FORCE_ITERATIONS {
+        let mut displacement = vec![(0., 0.); num_selected];
+        for (i, disp) in displacement.iter_mut().enumerate() {
+            for (j, other) in positions.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                let (mut dx, mut dy) = (positions[i].0 - other.0, positions[i].1 - other.1);
+                let mut dist = (dx * dx + dy * dy).sqrt();
+                if dist < 0.01 {
+                    // push coinciding nodes apart in a deterministic direction
+                    let angle = (i * positions.len() + j) as f32;
+                    (dx, dy, dist) = (angle.cos(), angle.sin(), 1.);
+                }
+                let force = k * k / dist;
+                disp.0 += dx / dist * force;
+                disp.1 += dy / dist * force;
+            }
+            disp.0 += (center.0 - positions[i].0) * FORCE_GRAVITY;
+            disp.1 += (center.1 - positions[i].1) * FORCE_GRAVITY;
+        }
+        for (u, v) in springs.iter() {
+            let (dx, dy) = (
+                positions[*u].0 - positions[*v].0,
+                positions[*u].1 - positions[*v].1,
+            );
+            let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+            let force = dist * dist / k;
+            displacement[*u].0 -= dx / dist * force;
+            displacement[*u].1 -= dy / dist * force;
+            if *v < num_selected {
+                displacement[*v].0 += dx / dist * force;
+                displacement[*v].1 += dy / dist * force;
+            }
+        }
+
+        // move the nodes, limiting the step by the (linearly decreasing) temperature
+        let temperature =
+            initial_temperature * (1. - iteration as f32 / FORCE_ITERATIONS as f32) + 1.;
+        for (i, (dx, dy)) in displacement.into_iter().enumerate() {
+            let length = (dx * dx + dy * dy).sqrt();
+            if length > 0. {
+                let step = length.min(temperature);
+                positions[i].0 += dx / length * step;
+                positions[i].1 += dy / length * step;
+            }
+        }
+    }
+    positions.truncate(num_selected);
+    positions
+}
+
+/// **(internal)** Euclidean distance of two points.
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NODE_SPACING;
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::layout::{LayoutAlgorithm, NodePosition};
+    use crate::sketchbook::model::ModelState;
+
+    /// Prepare a model with a cycle `a -> b -> c -> a`, and a regulation `c -> d`. All nodes
+    /// are at the default position.
+    fn prepare_model() -> ModelState {
+        let vars = vec![("a", "a"), ("b", "b"), ("c", "c"), ("d", "d")];
+        let mut model = ModelState::new_from_vars(vars).unwrap();
+        let regulations = vec!["a -> b", "b -> c", "c -| a", "c -> d"];
+        model.add_multiple_regulations(regulations).unwrap();
+        model
+    }
+
+    /// Check that all the positions are at least `min_distance` apart.
+    fn assert_spread(positions: &[(VarId, NodePosition)], min_distance: f32) {
+        for (i, (_, p1)) in positions.iter().enumerate() {
+            for (_, p2) in positions[i + 1..].iter() {
+                let distance = ((p1.0 - p2.0).powi(2) + (p1.1 - p2.1).powi(2)).sqrt();
+                assert!(distance >= min_distance);
+            }
+        }
+    }
+
+    #[test]
+    fn test_circular_layout() {
+        let model = prepare_model();
+        let layout_id = ModelState::get_default_layout_id();
+        let positions = model
+            .compute_auto_layout(&layout_id, LayoutAlgorithm::Circular, None)
+            .unwrap();
+        assert_eq!(positions.len(), 4);
+        assert_spread(&positions, NODE_SPACING * 0.99);
+
+        // all nodes are on a circle around the original center
+        for (_, position) in positions {
+            let radius = (position.0.powi(2) + position.1.powi(2)).sqrt();
+            assert!((radius - NODE_SPACING / 2f32.sqrt()).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_hierarchical_layout() {
+        let mut model = prepare_model();
+        let layout_id = ModelState::get_default_layout_id();
+        model
+            .auto_layout(&layout_id, LayoutAlgorithm::Hierarchical, None)
+            .unwrap();
+        let position = |id: &str| {
+            let var_id = model.get_var_id(id).unwrap();
+            model
+                .get_node_position(&layout_id, &var_id)
+                .unwrap()
+                .clone()
+        };
+        // the feedback regulation `c -| a` is ignored, so the cycle goes downwards
+        assert!(position("a").1 < position("b").1);
+        assert!(position("b").1 < position("c").1);
+        assert!(position("c").1 < position("d").1);
+        assert_eq!(position("a").1 + position("d").1, 0.);
+    }
+
+    #[test]
+    fn test_force_directed_layout() {
+        let mut model = prepare_model();
+        let layout_id = ModelState::get_default_layout_id();
+        let var_d = model.get_var_id("d").unwrap();
+        model
+            .update_position(&layout_id, &var_d, 500., 500.)
+            .unwrap();
+
+        // only place `a`, `b`, and `c`, while `d` stays pinned
+        let selected = ["a", "b", "c"].map(|id| model.get_var_id(id).unwrap());
+        let positions = model
+            .compute_auto_layout(&layout_id, LayoutAlgorithm::ForceDirected, Some(&selected))
+            .unwrap();
+        assert_eq!(positions.len(), 3);
+        assert_spread(&positions, NODE_SPACING / 2.);
+
+        // the computation is deterministic, and the positions are applied as computed
+        model
+            .auto_layout(&layout_id, LayoutAlgorithm::ForceDirected, Some(&selected))
+            .unwrap();
+        for (var_id, position) in positions {
+            assert_eq!(model.get_node_position(&layout_id, &var_id), Ok(&position));
+        }
+        let position_d = model.get_node_position(&layout_id, &var_d).unwrap();
+        assert_eq!(position_d, &NodePosition(500., 500.));
+    }
+
+    #[test]
+    fn test_auto_layout_errors() {
+        let model = prepare_model();
+        let layout_id = ModelState::get_default_layout_id();
+        let invalid_var = VarId::new("x").unwrap();
+        let result =
+            model.compute_auto_layout(&layout_id, LayoutAlgorithm::Circular, Some(&[invalid_var]));
+        assert!(result.is_err());
+        let invalid_layout = model.generate_layout_id("invalid");
+        let result = model.compute_auto_layout(&invalid_layout, LayoutAlgorithm::Circular, None);
+        assert!(result.is_err());
+        let result = model.compute_auto_layout(&layout_id, LayoutAlgorithm::Circular, Some(&[]));
+        assert_eq!(result, Ok(Vec::new()));
+    }
+}
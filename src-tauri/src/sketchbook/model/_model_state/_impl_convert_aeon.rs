@@ -0,0 +1,194 @@
+use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity, Regulation};
+use biodivine_lib_param_bn::BooleanNetwork;
+
+/// Prefix of the `.aeon` annotation lines describing node positions.
+const POSITION_PREFIX: &str = "#position:";
+/// Prefix of the `.aeon` annotation lines describing variable names (that differ from IDs).
+const VAR_NAME_PREFIX: &str = "#!variable_name:";
+/// Prefix of the `.aeon` annotation lines describing regulations that cannot be expressed in
+/// the `.aeon` format directly (dual or non-essential), in the standard regulation format.
+const REGULATION_PREFIX: &str = "#!regulation:";
+
+/// Methods for converting between `ModelState` and the `.aeon` format.
+impl ModelState {
+    /// Parse a `ModelState` from a string in the `.aeon` format.
+    ///
+    /// The model covers variables, regulations, update functions, and uninterpreted functions
+    /// (see [Self::from_bn]). Node positions given by the `#position:ID:X,Y` annotations are
+    /// loaded into the default layout (nodes without annotation are placed at 0,0).
+    ///
+    /// Variable names given by the `#!variable_name:ID:NAME` annotations, and regulations given
+    /// by the `#!regulation:REGULATION` annotations (overriding the sign and essentiality of
+    /// the regulation in the `.aeon` part) are loaded as well. See [Self::to_aeon].
+    ///
+    /// Variables without any regulations and update functions cannot be declared in the `.aeon`
+    /// format directly, so a position annotation of an unknown variable adds a new variable.
+    pub fn from_aeon(aeon_str: &str) -> Result<ModelState, String> {
+        let bn = BooleanNetwork::try_from(aeon_str)?;
+        let mut model = ModelState::from_bn(&bn)?;
+
+        let layout_id = ModelState::get_default_layout_id();
+        for line in aeon_str.lines().map(|l| l.trim()) {
+            if let Some(position_str) = line.strip_prefix(POSITION_PREFIX) {
+                let (var_id, px, py) = Self::parse_aeon_position(position_str)
+                    .ok_or(format!("Invalid position annotation `{line}`."))?;
+                if !model.is_valid_var_id_str(var_id) {
+                    model.add_var_by_str(var_id, var_id)?;
+                }
+                let var_id = model.get_var_id(var_id)?;
+                model.update_position(&layout_id, &var_id, px, py)?;
+            } else if let Some(name_str) = line.strip_prefix(VAR_NAME_PREFIX) {
+                let (var_id, name) = name_str
+                    .split_once(':')
+                    .ok_or(format!("Invalid variable name annotation `{line}`."))?;
+                model.set_var_name_by_str(var_id.trim(), name)?;
+            } else if let Some(regulation_str) = line.strip_prefix(REGULATION_PREFIX) {
+                let regulation = Regulation::try_from_string(regulation_str)?;
+                let (regulator, target) = (regulation.get_regulator(), regulation.get_target());
+                model.change_regulation_sign(regulator, target, regulation.get_sign())?;
+                let essentiality = regulation.get_essentiality();
+                model.change_regulation_essentiality(regulator, target, essentiality)?;
+            }
+        }
+        Ok(model)
+    }
+
+    /// Convert the `ModelState` into a string in the `.aeon` format.
+    ///
+    /// The result contains variables, regulations, update functions, and uninterpreted functions
+    /// (see [Self::to_bn]), and node positions of the default layout in the form of
+    /// `#position:ID:X,Y` annotations.
+    ///
+    /// Details that the `.aeon` format cannot express are stored as annotations as well:
+    /// variable names that differ from their IDs (`#!variable_name:ID:NAME`), and dual or
+    /// non-essential regulations (`#!regulation:REGULATION`, e.g., `#!regulation:a -*X b`).
+    pub fn to_aeon(&self) -> String {
+        let mut aeon_str = self.to_bn().to_string();
+        let mut regulations = self
+            .regulations()
+            .filter(|r| {
+                r.get_sign() == &Monotonicity::Dual || r.get_essentiality() == &Essentiality::False
+            })
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>();
+        regulations.sort();
+        for regulation in regulations {
+            aeon_str.push_str(&format!("{REGULATION_PREFIX}{regulation}\n"));
+        }
+
+        let layout_id = ModelState::get_default_layout_id();
+        let mut var_ids = self.variables().map(|(id, _)| id).collect::<Vec<_>>();
+        var_ids.sort();
+        for var_id in var_ids {
+            // every variable has a name, we can unwrap
+            let name = self.get_var_name(var_id).unwrap();
+            if name != var_id.as_str() {
+                aeon_str.push_str(&format!("{VAR_NAME_PREFIX}{var_id}:{name}\n"));
+            }
+            // every variable has a node in the default layout, we can unwrap
+            let position = self.get_node_position(&layout_id, var_id).unwrap();
+            aeon_str.push_str(&format!(
+                "{POSITION_PREFIX}{var_id}:{},{}\n",
+                position.0, position.1
+            ));
+        }
+        aeon_str
+    }
+
+    /// **(internal)** Parse the `ID:X,Y` part of the position annotation.
+    fn parse_aeon_position(position_str: &str) -> Option<(&str, f32, f32)> {
+        let (var_id, coordinates) = position_str.split_once(':')?;
+        let (px, py) = coordinates.split_once(',')?;
+        let px = px.trim().parse::<f32>().ok()?;
+        let py = py.trim().parse::<f32>().ok()?;
+        Some((var_id.trim(), px, py))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::layout::NodePosition;
+    use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+
+    #[test]
+    fn test_from_aeon() {
+        let aeon_str = r#"
+            #name:test_model
+            a -> b
+            b -|? a
+            a -? a
+            $a: f(a, b)
+            $b: a
+            #position:a:10.5,-20
+            #position:b:0,3.25
+        "#;
+        let model = ModelState::from_aeon(aeon_str).unwrap();
+        let var_a = model.get_var_id("a").unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        assert_eq!(model.num_vars(), 2);
+        assert_eq!(model.num_regulations(), 3);
+        assert_eq!(model.num_uninterpreted_fns(), 1);
+        assert_eq!(model.get_update_fn_string(&var_a).unwrap(), "f(a, b)");
+
+        let regulation = model.get_regulation(&var_b, &var_a).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+        assert_eq!(regulation.get_essentiality(), &Essentiality::Unknown);
+
+        let layout_id = ModelState::get_default_layout_id();
+        let position = model.get_node_position(&layout_id, &var_a).unwrap();
+        assert_eq!(position, &NodePosition(10.5, -20.));
+        let position = model.get_node_position(&layout_id, &var_b).unwrap();
+        assert_eq!(position, &NodePosition(0., 3.25));
+
+        // position annotations of unknown variables add new (isolated) variables
+        let model = ModelState::from_aeon("a -> a\n#position:c:1,2\n").unwrap();
+        assert_eq!(model.num_vars(), 2);
+        assert!(model.is_valid_var_id_str("c"));
+
+        let invalid_str = "a -> a\n#position:a:1;2\n";
+        assert!(ModelState::from_aeon(invalid_str).is_err());
+    }
+
+    #[test]
+    fn test_aeon_round_trip() {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -> b", "b -| a", "a -?? a"])
+            .unwrap();
+        model.add_uninterpreted_fn_by_str("g", "g", 1).unwrap();
+        let var_a = model.get_var_id("a").unwrap();
+        model.set_update_fn(&var_a, "!b & g(a)").unwrap();
+        let layout_id = ModelState::get_default_layout_id();
+        model.update_position(&layout_id, &var_a, 1.5, 2.).unwrap();
+
+        let aeon_str = model.to_aeon();
+        assert!(aeon_str.contains("#position:a:1.5,2\n"));
+        let model_from_aeon = ModelState::from_aeon(&aeon_str).unwrap();
+        assert_eq!(model, model_from_aeon);
+    }
+
+    #[test]
+    /// Test that details not expressible in the `.aeon` format survive the round trip.
+    fn test_aeon_round_trip_annotations() {
+        let mut model =
+            ModelState::new_from_vars(vec![("a", "Gene A"), ("b", "b: kinase"), ("c", "c")])
+                .unwrap();
+        model
+            .add_multiple_regulations(vec!["a -*? b", "b -|X a", "a ->X c", "c -?? c"])
+            .unwrap();
+
+        let aeon_str = model.to_aeon();
+        assert!(aeon_str.contains("#!variable_name:a:Gene A\n"));
+        assert!(aeon_str.contains("#!variable_name:b:b: kinase\n"));
+        assert!(!aeon_str.contains("#!variable_name:c:"));
+        assert!(aeon_str.contains("#!regulation:a -*? b\n"));
+        assert!(aeon_str.contains("#!regulation:b -|X a\n"));
+        assert!(!aeon_str.contains("#!regulation:c"));
+        let model_from_aeon = ModelState::from_aeon(&aeon_str).unwrap();
+        assert_eq!(model, model_from_aeon);
+
+        // annotations must refer to existing regulations
+        let invalid_str = "a -> a\n#!regulation:a -*X b\n";
+        assert!(ModelState::from_aeon(invalid_str).is_err());
+    }
+}
@@ -62,6 +62,32 @@ impl ModelState {
         }
         bn
     }
+
+    /// Convert the `BooleanNetwork` into the corresponding `ModelState` object. The resulting
+    /// model contains all variables and regulations (see [Self::from_reg_graph]), uninterpreted
+    /// functions (parameters, with their names used both as IDs and names), and update functions.
+    ///
+    /// Note that only the default layout (all nodes at 0,0) is created for the `ModelState`.
+    pub fn from_bn(bn: &BooleanNetwork) -> Result<ModelState, String> {
+        let mut model = ModelState::from_reg_graph(bn.as_graph().clone())?;
+
+        for param_id in bn.parameters() {
+            let param = bn.get_parameter(param_id);
+            model.add_uninterpreted_fn_by_str(
+                param.get_name(),
+                param.get_name(),
+                param.get_arity() as usize,
+            )?;
+        }
+
+        for bn_var in bn.variables() {
+            if let Some(update_fn) = bn.get_update_function(bn_var) {
+                let var_id = model.get_var_id(bn.get_variable_name(bn_var))?;
+                model.set_update_fn(&var_id, &update_fn.to_string(bn))?;
+            }
+        }
+        Ok(model)
+    }
 }
 
 #[cfg(test)]
@@ -111,6 +137,26 @@ mod tests {
         assert_eq!(bn.get_update_function(var_b), &None);
     }
 
+    #[test]
+    fn test_from_bn() {
+        let model = prepare_test_model_full();
+        let bn = model.to_bn();
+        let model_from_bn = ModelState::from_bn(&bn).unwrap();
+        let var_a = model.get_var_id("a").unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        assert_eq!(model_from_bn.num_vars(), 2);
+        assert_eq!(model_from_bn.num_regulations(), 3);
+        assert_eq!(model_from_bn.num_uninterpreted_fns(), 1);
+        assert_eq!(
+            model_from_bn.get_update_fn_string(&var_a).unwrap(),
+            model.get_update_fn_string(&var_a).unwrap(),
+        );
+        assert!(model_from_bn
+            .get_update_fn(&var_b)
+            .unwrap()
+            .is_unspecified());
+    }
+
     #[test]
     fn test_to_fake_bn() {
         let model = prepare_test_model_full();
@@ -0,0 +1,175 @@
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::{BinaryOp, BnetExportMode, Essentiality, FnTree, ModelState};
+use biodivine_lib_param_bn::BooleanNetwork;
+
+/// Methods for converting between `ModelState` and the `.bnet` format (used by BoolNet and
+/// PyBoolNet).
+impl ModelState {
+    /// Parse a `ModelState` from a string in the `.bnet` format.
+    ///
+    /// The format does not describe regulations, so these are inferred from the update functions.
+    /// The monotonicity of each regulation is deduced syntactically from the update function (see
+    /// [FnTree::syntactic_monotonicity]), and its essentiality is `Unknown` (a variable might
+    /// appear in the expression without having an effect).
+    ///
+    /// Variables without an update function (free inputs) get an unspecified update function.
+    pub fn from_bnet(bnet_str: &str) -> Result<ModelState, String> {
+        let bn = BooleanNetwork::try_from_bnet(bnet_str)?;
+        let mut model = ModelState::new_empty();
+        for bn_var in bn.variables() {
+            let name = bn.get_variable_name(bn_var);
+            model.add_var_by_str(name, name)?;
+        }
+
+        for bn_var in bn.variables() {
+            let Some(fn_update) = bn.get_update_function(bn_var) else {
+                continue;
+            };
+            let target = model.get_var_id(bn.get_variable_name(bn_var))?;
+            model.set_update_fn(&target, &fn_update.to_string(&bn))?;
+
+            // the function was just set, so it has a syntactic tree
+            let update_fn = model.get_update_fn(&target)?;
+            let fn_tree = update_fn.get_fn_tree().as_ref().unwrap();
+            let mut regulators = fn_tree
+                .syntactic_monotonicity()
+                .into_iter()
+                .collect::<Vec<_>>();
+            regulators.sort_by(|(id1, _), (id2, _)| id1.cmp(id2));
+            for (regulator, monotonicity) in regulators {
+                model.add_regulation(
+                    regulator,
+                    target.clone(),
+                    Essentiality::Unknown,
+                    monotonicity,
+                )?;
+            }
+        }
+        Ok(model)
+    }
+
+    /// Convert the `ModelState` into a string in the `.bnet` format.
+    ///
+    /// The format can only express fully specified update functions. Variables with unspecified
+    /// update functions or with update functions containing uninterpreted functions are either
+    /// rejected (returning `Err` with the details), or exported as free inputs, depending on the
+    /// `mode`.
+    ///
+    /// Since `.bnet` has no constants, constant functions are expressed using a tautology or
+    /// contradiction on the target variable (e.g., `a | !a`).
+    pub fn to_bnet(&self, mode: BnetExportMode) -> Result<String, String> {
+        let mut var_ids = self.variables().map(|(id, _)| id).collect::<Vec<_>>();
+        var_ids.sort();
+
+        let mut bnet_str = "targets, factors\n".to_string();
+        for var_id in var_ids {
+            let update_fn = self.get_update_fn(var_id)?;
+            let mut fn_symbols = update_fn
+                .collect_fn_symbols()
+                .into_iter()
+                .collect::<Vec<_>>();
+            fn_symbols.sort();
+
+            let fn_str = match (update_fn.get_fn_tree(), fn_symbols.first(), mode) {
+                (Some(fn_tree), None, _) => Self::fn_tree_to_bnet(fn_tree, var_id),
+                (_, _, BnetExportMode::FreeInputs) => var_id.to_string(),
+                (None, _, BnetExportMode::Strict) => {
+                    return Err(format!(
+                        "Variable `{var_id}` cannot be exported to .bnet, since its update function is unspecified."
+                    ));
+                }
+                (Some(_), Some(fn_id), BnetExportMode::Strict) => {
+                    return Err(format!(
+                        "Variable `{var_id}` cannot be exported to .bnet, since its update function uses uninterpreted function `{fn_id}`."
+                    ));
+                }
+            };
+            bnet_str.push_str(&format!("{var_id}, {fn_str}\n"));
+        }
+        Ok(bnet_str)
+    }
+
+    /// **(internal)** Convert a (fully specified) update function of variable `target` into
+    /// an expression in the `.bnet` format.
+    fn fn_tree_to_bnet(fn_tree: &FnTree, target: &VarId) -> String {
+        match fn_tree {
+            FnTree::Const(true) => format!("({target} | !{target})"),
+            FnTree::Const(false) => format!("({target} & !{target})"),
+            FnTree::Var(id) | FnTree::PlaceholderVar(id) => id.to_string(),
+            FnTree::UninterpretedFn(..) => {
+                unreachable!("Functions with uninterpreted functions cannot be exported.")
+            }
+            FnTree::Not(inner) => format!("!{}", Self::fn_tree_to_bnet(inner, target)),
+            FnTree::Binary(op, l, r) => {
+                let l = Self::fn_tree_to_bnet(l, target);
+                let r = Self::fn_tree_to_bnet(r, target);
+                match op {
+                    BinaryOp::And => format!("({l} & {r})"),
+                    BinaryOp::Or => format!("({l} | {r})"),
+                    BinaryOp::Imp => format!("(!{l} | {r})"),
+                    BinaryOp::Iff => format!("(({l} & {r}) | (!{l} & !{r}))"),
+                    BinaryOp::Xor => format!("(({l} & !{r}) | (!{l} & {r}))"),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::{BnetExportMode, Essentiality, ModelState, Monotonicity};
+
+    #[test]
+    fn test_from_bnet() {
+        let bnet_str = "targets, factors\na, !b & c\nb, a | !a\nc, (a & b) | (!a & !b)\n";
+        let model = ModelState::from_bnet(bnet_str).unwrap();
+        let var = |id: &str| model.get_var_id(id).unwrap();
+        assert_eq!(model.num_vars(), 3);
+        assert_eq!(model.num_regulations(), 5);
+
+        let regulation = model.get_regulation(&var("b"), &var("a")).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+        assert_eq!(regulation.get_essentiality(), &Essentiality::Unknown);
+        let regulation = model.get_regulation(&var("c"), &var("a")).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Activation);
+        let regulation = model.get_regulation(&var("a"), &var("b")).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Unknown);
+        assert!(model.get_regulation(&var("b"), &var("c")).is_ok());
+        assert_eq!(model.get_update_fn_string(&var("a")).unwrap(), "!b & c");
+    }
+
+    #[test]
+    fn test_to_bnet() {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 1).unwrap();
+        let (var_a, var_b) = (
+            model.get_var_id("a").unwrap(),
+            model.get_var_id("b").unwrap(),
+        );
+        model.set_update_fn(&var_a, "b => (a ^ true)").unwrap();
+
+        // `b` has unspecified update function
+        let error = model.to_bnet(BnetExportMode::Strict).unwrap_err();
+        assert!(error.contains("`b` cannot be exported"));
+        assert!(error.contains("unspecified"));
+        let bnet_str = model.to_bnet(BnetExportMode::FreeInputs).unwrap();
+        assert_eq!(
+            bnet_str,
+            "targets, factors\na, (!b | ((a & !(a | !a)) | (!a & (a | !a))))\nb, b\n"
+        );
+
+        // `b` uses an uninterpreted function
+        model.set_update_fn(&var_b, "f(a)").unwrap();
+        let error = model.to_bnet(BnetExportMode::Strict).unwrap_err();
+        assert!(error.contains("uses uninterpreted function `f`"));
+        let bnet_str = model.to_bnet(BnetExportMode::FreeInputs).unwrap();
+        assert!(bnet_str.ends_with("b, b\n"));
+
+        // round trip of fully specified model
+        model.set_update_fn(&var_b, "a & !b").unwrap();
+        let bnet_str = model.to_bnet(BnetExportMode::Strict).unwrap();
+        let imported = ModelState::from_bnet(&bnet_str).unwrap();
+        assert_eq!(imported.num_vars(), 2);
+        assert_eq!(imported.get_update_fn_string(&var_b).unwrap(), "a & !b");
+    }
+}
@@ -0,0 +1,195 @@
+use crate::sketchbook::ids::LayoutId;
+use crate::sketchbook::model::ModelState;
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+
+use super::_impl_merge_interactions::Interaction;
+
+/// Names of GraphML node attributes that might carry the node's label.
+const LABEL_ATTRS: [&str; 3] = ["name", "label", "shared name"];
+/// Names of GraphML edge attributes that might carry the interaction type.
+const INTERACTION_ATTRS: [&str; 6] = [
+    "interaction",
+    "shared interaction",
+    "sign",
+    "effect",
+    "type",
+    "edge type",
+];
+
+/// Methods for importing interaction networks in the GraphML format into a `ModelState`.
+impl ModelState {
+    /// Parse a `ModelState` from a string in the GraphML format. See [Self::merge_graphml] for
+    /// details on the format and the returned warnings.
+    pub fn from_graphml(graphml_str: &str) -> Result<(ModelState, Vec<String>), String> {
+        let mut model = ModelState::new_empty();
+        let warnings = model.merge_graphml(graphml_str)?;
+        Ok((model, warnings))
+    }
+
+    /// Merge an interaction network in the GraphML format (e.g., exported from Cytoscape or
+    /// yEd) into this model.
+    ///
+    /// Node labels are taken from `name` or `label` attributes (or node IDs, if there are none),
+    /// and interaction types from attributes like `interaction` or `sign`. Nodes are matched
+    /// with existing variables by ID or name, and new variables are added for the rest.
+    /// Interactions are added as regulations with unknown essentiality, and with monotonicity
+    /// given by the interaction type (e.g., `activates` or `inhibits`).
+    ///
+    /// If the nodes have coordinates (`x` and `y` attributes, or yEd geometry), these are
+    /// loaded into a new layout.
+    ///
+    /// Returns the list of warnings about duplicate or conflicting interactions, and about
+    /// renamed nodes. The model is not modified if the input cannot be parsed.
+    pub fn merge_graphml(&mut self, graphml_str: &str) -> Result<Vec<String>, String> {
+        let doc = Document::parse(graphml_str)
+            .map_err(|e| format!("Cannot import the GraphML file: {e}"))?;
+
+        // map attribute keys to their names
+        let keys: HashMap<&str, String> = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("key"))
+            .filter_map(|n| {
+                let name = n.attribute("attr.name")?;
+                Some((n.attribute("id")?, name.trim().to_lowercase()))
+            })
+            .collect();
+
+        let mut labels: HashMap<&str, String> = HashMap::new();
+        let mut nodes = Vec::new();
+        let mut positions = Vec::new();
+        for node in doc.descendants().filter(|n| n.has_tag_name("node")) {
+            let id = node
+                .attribute("id")
+                .ok_or("GraphML node is missing an `id` attribute.".to_string())?;
+            let data = Self::graphml_data(&node, &keys);
+            let label = LABEL_ATTRS
+                .iter()
+                .find_map(|attr| data.get(*attr).filter(|l| !l.is_empty()))
+                .cloned()
+                .unwrap_or(id.to_string());
+            if let Some(position) = Self::graphml_position(&node, &data) {
+                positions.push((label.clone(), position));
+            }
+            labels.insert(id, label.clone());
+            nodes.push(label);
+        }
+
+        let mut interactions = Vec::new();
+        for edge in doc.descendants().filter(|n| n.has_tag_name("edge")) {
+            let endpoint = |attr: &str| -> Result<String, String> {
+                let node_id = edge
+                    .attribute(attr)
+                    .ok_or(format!("GraphML edge is missing a `{attr}` attribute."))?;
+                labels
+                    .get(node_id)
+                    .cloned()
+                    .ok_or(format!("GraphML edge references unknown node `{node_id}`."))
+            };
+            let (source, target) = (endpoint("source")?, endpoint("target")?);
+            let data = Self::graphml_data(&edge, &keys);
+            let kind = INTERACTION_ATTRS
+                .iter()
+                .find_map(|attr| data.get(*attr))
+                .cloned()
+                .unwrap_or_default();
+            interactions.push(Interaction {
+                source,
+                target,
+                kind,
+            });
+        }
+
+        // merge into a copy, so that the model is untouched if something fails
+        let mut model = self.clone();
+        let (var_mapping, warnings) = model.merge_interactions(&nodes, &interactions)?;
+        if !positions.is_empty() {
+            let layout_id: LayoutId = model.generate_layout_id("graphml_layout");
+            model.add_layout_simple(layout_id.clone(), "GraphML layout")?;
+            for (label, (px, py)) in positions {
+                model.update_position(&layout_id, &var_mapping[&label], px, py)?;
+            }
+        }
+        *self = model;
+        Ok(warnings)
+    }
+
+    /// **(internal)** Collect the `data` elements of a GraphML node or edge, mapping the
+    /// attribute names (lowercase) to their (trimmed) values.
+    fn graphml_data(element: &Node, keys: &HashMap<&str, String>) -> HashMap<String, String> {
+        element
+            .children()
+            .filter(|n| n.has_tag_name("data"))
+            .filter_map(|n| {
+                let key = n.attribute("key")?;
+                let name = keys.get(key).cloned().unwrap_or(key.to_lowercase());
+                let value = n.text().unwrap_or_default().trim().to_string();
+                Some((name, value))
+            })
+            .collect()
+    }
+
+    /// **(internal)** Get the position of a GraphML node, either from its `x` and `y` data
+    /// attributes, or from the yEd `Geometry` element.
+    fn graphml_position(node: &Node, data: &HashMap<String, String>) -> Option<(f32, f32)> {
+        let parse = |x: Option<&str>, y: Option<&str>| -> Option<(f32, f32)> {
+            Some((x?.parse::<f32>().ok()?, y?.parse::<f32>().ok()?))
+        };
+        let data_position = parse(
+            data.get("x").map(|x| x.as_str()),
+            data.get("y").map(|y| y.as_str()),
+        );
+        data_position.or_else(|| {
+            let geometry = node
+                .descendants()
+                .find(|n| n.tag_name().name() == "Geometry")?;
+            parse(geometry.attribute("x"), geometry.attribute("y"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::layout::NodePosition;
+    use crate::sketchbook::model::{ModelState, Monotonicity};
+
+    #[test]
+    fn test_from_graphml() {
+        let graphml_str = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+                <key id="k0" for="node" attr.name="name" attr.type="string"/>
+                <key id="k1" for="node" attr.name="x" attr.type="double"/>
+                <key id="k2" for="node" attr.name="y" attr.type="double"/>
+                <key id="k3" for="edge" attr.name="interaction" attr.type="string"/>
+                <graph edgedefault="directed">
+                    <node id="n0">
+                        <data key="k0">A</data>
+                        <data key="k1">10.5</data>
+                        <data key="k2">-3</data>
+                    </node>
+                    <node id="n1"><data key="k0">B</data></node>
+                    <edge source="n0" target="n1"><data key="k3">activates</data></edge>
+                    <edge source="n1" target="n0"><data key="k3">inhibits</data></edge>
+                    <edge source="n1" target="n0"><data key="k3">inhibits</data></edge>
+                </graph>
+            </graphml>"#;
+        let (model, warnings) = ModelState::from_graphml(graphml_str).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Duplicate interaction `B` -> `A`"));
+        assert_eq!(model.num_vars(), 2);
+        assert_eq!(model.num_regulations(), 2);
+        let var = |id: &str| model.get_var_id(id).unwrap();
+        let regulation = model.get_regulation(&var("B"), &var("A")).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+
+        // coordinates are loaded into a new layout
+        assert_eq!(model.num_layouts(), 2);
+        let layout_id = model.get_layout_id("graphml_layout").unwrap();
+        let position = model.get_node_position(&layout_id, &var("A")).unwrap();
+        assert_eq!(position, &NodePosition(10.5, -3.));
+
+        let invalid_str = r#"<graphml><graph><edge source="a" target="b"/></graph></graphml>"#;
+        let error = ModelState::from_graphml(invalid_str).unwrap_err();
+        assert_eq!(error, "GraphML edge references unknown node `a`.");
+    }
+}
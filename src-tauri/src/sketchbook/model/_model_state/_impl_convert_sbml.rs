@@ -0,0 +1,136 @@
+use crate::sketchbook::model::ModelState;
+use biodivine_lib_param_bn::sbml::Layout as SbmlLayout;
+use biodivine_lib_param_bn::BooleanNetwork;
+use std::collections::HashSet;
+
+/// Methods for converting between `ModelState` and the SBML-qual format.
+impl ModelState {
+    /// Parse a `ModelState` from a string in the SBML-qual format.
+    ///
+    /// Qualitative species are converted to variables, transitions to regulations and update
+    /// functions (see [Self::from_bn]), and the positions of species glyphs from the layout
+    /// extension are loaded into the default layout.
+    ///
+    /// Returns `Err` if the model cannot be represented, for instance, if it contains multi-valued
+    /// species. Otherwise, the model is returned together with a list of warnings (such as
+    /// renamed species) produced during the parsing.
+    pub fn from_sbml(sbml_str: &str) -> Result<(ModelState, Vec<String>), String> {
+        let mut warnings = Vec::new();
+        let (bn, sbml_layout) = BooleanNetwork::try_from_sbml_strict(sbml_str, &mut warnings)
+            .map_err(|e| format!("Cannot import the SBML-qual model: {e}"))?;
+        let mut model = ModelState::from_bn(&bn)?;
+
+        let layout_id = ModelState::get_default_layout_id();
+        for (var_name, (px, py)) in sbml_layout {
+            let var_id = model.get_var_id(&var_name)?;
+            model.update_position(&layout_id, &var_id, px as f32, py as f32)?;
+        }
+        Ok((model, warnings))
+    }
+
+    /// Convert the `ModelState` into a string in the SBML-qual format. Node positions of the
+    /// default layout are exported using the layout extension.
+    ///
+    /// SBML-qual cannot fully express uninterpreted functions. Uninterpreted functions used in
+    /// update functions are exported as (non-standard) `csymbol` MathML elements, and unused
+    /// ones are not exported at all. In both cases, details like function expressions or
+    /// argument constraints are lost. A warning is produced for every such function, and the
+    /// list of warnings is returned together with the SBML string.
+    pub fn to_sbml(&self) -> (String, Vec<String>) {
+        let layout_id = ModelState::get_default_layout_id();
+        // the default layout always exists, we can unwrap
+        let layout = self.get_layout(&layout_id).unwrap();
+        let sbml_layout: SbmlLayout = layout
+            .layout_nodes()
+            .map(|(var_id, node)| {
+                let position = (node.get_px() as f64, node.get_py() as f64);
+                (var_id.to_string(), position)
+            })
+            .collect();
+
+        let used_fns: HashSet<_> = self
+            .update_fns
+            .values()
+            .flat_map(|update_fn| update_fn.collect_fn_symbols())
+            .collect();
+        let mut fn_ids = self.uninterpreted_fns.keys().collect::<Vec<_>>();
+        fn_ids.sort();
+        let warnings = fn_ids
+            .into_iter()
+            .map(|fn_id| {
+                if used_fns.contains(fn_id) {
+                    format!(
+                        "Uninterpreted function `{fn_id}` is exported as a non-standard `csymbol` \
+                        element, and its expression and argument constraints are lost."
+                    )
+                } else {
+                    format!(
+                        "Uninterpreted function `{fn_id}` is not used in any update function, \
+                        and it is not exported."
+                    )
+                }
+            })
+            .collect();
+
+        (self.to_bn().to_sbml(Some(&sbml_layout)), warnings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::layout::NodePosition;
+    use crate::sketchbook::model::ModelState;
+
+    #[test]
+    fn test_sbml_round_trip() {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -> b", "b -| a", "a -?? a"])
+            .unwrap();
+        model
+            .add_multiple_uninterpreted_fns(vec![("f", "f", 1), ("g", "g", 2)])
+            .unwrap();
+        let var_a = model.get_var_id("a").unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        model.set_update_fn(&var_a, "!b & f(a)").unwrap();
+        model.set_update_fn(&var_b, "a").unwrap();
+        let layout_id = ModelState::get_default_layout_id();
+        model
+            .update_position(&layout_id, &var_a, 10., -5.5)
+            .unwrap();
+
+        let (sbml_str, warnings) = model.to_sbml();
+        // one warning for the used fn `f`, one for the unused fn `g`
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("`f` is exported as a non-standard"));
+        assert!(warnings[1].contains("`g` is not used"));
+
+        let (imported, _) = ModelState::from_sbml(&sbml_str).unwrap();
+        assert_eq!(imported.num_vars(), 2);
+        assert_eq!(imported.num_regulations(), 3);
+        assert_eq!(imported.num_uninterpreted_fns(), 1);
+        assert_eq!(
+            imported.get_update_fn_string(&var_a).unwrap(),
+            model.get_update_fn_string(&var_a).unwrap(),
+        );
+        let position = imported.get_node_position(&layout_id, &var_a).unwrap();
+        assert_eq!(position, &NodePosition(10., -5.5));
+    }
+
+    #[test]
+    fn test_sbml_multivalued_species() {
+        let sbml_str = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <sbml xmlns="http://www.sbml.org/sbml/level3/version1/core" level="3" version="1"
+                xmlns:qual="http://www.sbml.org/sbml/level3/version1/qual/version1"
+                qual:required="true">
+            <model>
+                <qual:listOfQualitativeSpecies>
+                    <qual:qualitativeSpecies qual:id="a" qual:maxLevel="2" qual:constant="false"/>
+                </qual:listOfQualitativeSpecies>
+                <qual:listOfTransitions/>
+            </model>
+            </sbml>"#;
+        let error = ModelState::from_sbml(sbml_str).unwrap_err();
+        assert!(error.contains("is not Boolean"));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::sketchbook::model::ModelState;
+
+use super::_impl_merge_interactions::Interaction;
+
+/// Methods for importing interaction networks in the SIF (simple interaction format) into
+/// a `ModelState`.
+impl ModelState {
+    /// Parse a `ModelState` from a string in the SIF format. See [Self::merge_sif] for
+    /// details on the format and the returned warnings.
+    pub fn from_sif(sif_str: &str) -> Result<(ModelState, Vec<String>), String> {
+        let mut model = ModelState::new_empty();
+        let warnings = model.merge_sif(sif_str)?;
+        Ok((model, warnings))
+    }
+
+    /// Merge an interaction network in the SIF format into this model.
+    ///
+    /// Each line has the form `source type target1 target2 ...` (values separated by tabs, or
+    /// by whitespace if there are no tabs), or contains a single isolated node. Empty lines and
+    /// lines starting with `#` are skipped.
+    ///
+    /// Nodes are matched with existing variables by ID or name, and new variables are added for
+    /// the rest. Interactions are added as regulations with unknown essentiality, and with
+    /// monotonicity given by the interaction type (e.g., `activates` or `inhibits`).
+    ///
+    /// Returns the list of warnings about duplicate or conflicting interactions, and about
+    /// renamed nodes. The model is not modified if the input cannot be parsed.
+    pub fn merge_sif(&mut self, sif_str: &str) -> Result<Vec<String>, String> {
+        let mut nodes = Vec::new();
+        let mut interactions = Vec::new();
+        for (line_idx, line) in sif_str.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let values: Vec<&str> = if line.contains('\t') {
+                line.split('\t').map(|v| v.trim()).collect()
+            } else {
+                line.split_whitespace().collect()
+            };
+            match values.as_slice() {
+                [node] => nodes.push(node.to_string()),
+                [source, kind, targets @ ..] if !targets.is_empty() => {
+                    for target in targets {
+                        interactions.push(Interaction {
+                            source: source.to_string(),
+                            target: target.to_string(),
+                            kind: kind.to_string(),
+                        });
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "Invalid SIF line {}: `{line}`. Expected `source type target`.",
+                        line_idx + 1
+                    ))
+                }
+            }
+        }
+
+        // merge into a copy, so that the model is untouched if something fails
+        let mut model = self.clone();
+        let (_, warnings) = model.merge_interactions(&nodes, &interactions)?;
+        *self = model;
+        Ok(warnings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+
+    #[test]
+    fn test_from_sif() {
+        let sif_str = "# comment\nA\tactivates\tB\tC\nB inhibits A\nC -1 C\nD\n";
+        let (model, warnings) = ModelState::from_sif(sif_str).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(model.num_vars(), 4);
+        assert_eq!(model.num_regulations(), 4);
+        let var = |id: &str| model.get_var_id(id).unwrap();
+        let regulation = model.get_regulation(&var("A"), &var("C")).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Activation);
+        assert_eq!(regulation.get_essentiality(), &Essentiality::Unknown);
+        let regulation = model.get_regulation(&var("C"), &var("C")).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Inhibition);
+
+        let error = ModelState::from_sif("A\tactivates\n").unwrap_err();
+        assert!(error.starts_with("Invalid SIF line 1"));
+    }
+
+    #[test]
+    fn test_merge_sif() {
+        let mut model = ModelState::new_from_vars(vec![("a", "A"), ("b", "b")]).unwrap();
+        model.add_regulation_by_str("a -> b").unwrap();
+
+        // `A` is matched by name, `b` by ID, `p-53` gets a new ID
+        let sif_str =
+            "A inhibits b\nb activates p-53\nb binds p-53\nb stimulates a\nb activates a\n";
+        let warnings = model.merge_sif(sif_str).unwrap();
+        assert_eq!(model.num_vars(), 3);
+        assert_eq!(model.num_regulations(), 3);
+        assert_eq!(warnings.len(), 4);
+        assert_eq!(warnings[0], "Node `p-53` was imported as variable `p53`.");
+        assert!(warnings[1].contains("`b` -> `p-53` have conflicting types"));
+        assert!(warnings[2].contains("Duplicate interaction `b` -> `a`"));
+        assert!(warnings[3].contains("`a` -> `b` already exists with a different monotonicity"));
+
+        let var = |id: &str| model.get_var_id(id).unwrap();
+        let regulation = model.get_regulation(&var("a"), &var("b")).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Activation);
+        let regulation = model.get_regulation(&var("b"), &var("p53")).unwrap();
+        assert_eq!(regulation.get_sign(), &Monotonicity::Unknown);
+    }
+}
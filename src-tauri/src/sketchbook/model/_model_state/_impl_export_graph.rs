@@ -0,0 +1,331 @@
+use crate::sketchbook::ids::{LayoutId, VarId};
+use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity, Regulation};
+use std::collections::HashMap;
+
+/// **(internal)** Approximate width of a single character of a node label (in the SVG units).
+const SVG_CHAR_WIDTH: f32 = 8.;
+/// **(internal)** Height of a node in the SVG figure.
+const SVG_NODE_HEIGHT: f32 = 26.;
+/// **(internal)** Padding around the label of a node, and around the whole SVG figure.
+const SVG_PADDING: f32 = 12.;
+/// **(internal)** Distance between the control point of a curved edge and the straight line.
+const SVG_CURVE_OFFSET: f32 = 30.;
+
+/// Methods for exporting the regulatory graph of a `ModelState` into graphics formats
+/// (Graphviz DOT and SVG).
+///
+/// In both formats, edges are styled the same way as in the editor. Activations are green with
+/// normal arrows, inhibitions red with `tee` arrows, dual regulations blue with diamond arrows,
+/// and regulations with unknown monotonicity grey. Essential regulations are drawn as solid
+/// lines, non-essential as dashed lines, and those with unknown essentiality as dotted lines.
+impl ModelState {
+    /// Convert the regulatory graph of the `ModelState` into a string in the Graphviz DOT
+    /// format. Nodes are identified by variable IDs and labeled by variable names.
+    ///
+    /// The DOT format leaves the positioning of the nodes to Graphviz, layouts are not exported.
+    pub fn to_dot(&self) -> String {
+        let mut dot_str = "digraph regulatory_graph {\n".to_string();
+        dot_str.push_str(
+            "    node [shape=box, style=\"rounded,filled\", fillcolor=\"#dddddd\", color=\"#bbbbbb\", fontname=\"FiraMono\"];\n",
+        );
+
+        let mut var_ids = self.variables().map(|(id, _)| id).collect::<Vec<_>>();
+        var_ids.sort();
+        for var_id in var_ids {
+            // all the listed variables are valid, we can unwrap
+            let name = self.get_var_name(var_id).unwrap();
+            let name = name.replace('\\', "\\\\").replace('"', "\\\"");
+            dot_str.push_str(&format!("    \"{var_id}\" [label=\"{name}\"];\n"));
+        }
+
+        for regulation in self.sorted_regulations() {
+            let sign = regulation.get_sign();
+            let arrowhead = match sign {
+                Monotonicity::Activation | Monotonicity::Unknown => "normal",
+                Monotonicity::Inhibition => "tee",
+                Monotonicity::Dual => "diamond",
+            };
+            dot_str.push_str(&format!(
+                "    \"{}\" -> \"{}\" [color=\"{}\", arrowhead={arrowhead}, style={}];\n",
+                regulation.get_regulator(),
+                regulation.get_target(),
+                Self::monotonicity_color(sign),
+                Self::essentiality_line_style(regulation.get_essentiality()),
+            ));
+        }
+        dot_str.push_str("}\n");
+        dot_str
+    }
+
+    /// Render the regulatory graph of the `ModelState` into a self-contained SVG image, with
+    /// nodes placed according to the given layout. Each node is drawn as a rounded rectangle
+    /// labeled by the variable's name, centered at its position.
+    ///
+    /// Regulations are drawn as straight lines, except for self-regulations (drawn as loops
+    /// above the node) and pairs of mutual regulations (drawn as two curves, so that they do
+    /// not overlap).
+    ///
+    /// Returns `Err` if the layout does not exist.
+    pub fn to_svg(&self, layout_id: &LayoutId) -> Result<String, String> {
+        let layout = self.get_layout(layout_id)?;
+        let mut var_ids = self.variables().map(|(id, _)| id).collect::<Vec<_>>();
+        var_ids.sort();
+
+        // compute the node boxes (center and half-sizes), and the bounding box of the figure
+        let mut nodes: HashMap<&VarId, SvgNode> = HashMap::new();
+        let (mut min_x, mut min_y) = (f32::INFINITY, f32::INFINITY);
+        let (mut max_x, mut max_y) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for var_id in var_ids.iter() {
+            let position = layout.get_node_position(var_id)?;
+            let name = self.get_var_name(var_id)?;
+            let node = SvgNode {
+                x: position.0,
+                y: position.1,
+                half_width: (name.chars().count() as f32 * SVG_CHAR_WIDTH + 2. * SVG_PADDING) / 2.,
+                half_height: SVG_NODE_HEIGHT / 2.,
+            };
+            min_x = min_x.min(node.x - node.half_width);
+            max_x = max_x.max(node.x + node.half_width);
+            // leave space for possible self-loops above the node
+            min_y = min_y.min(node.y - node.half_height - SVG_NODE_HEIGHT * 1.5);
+            max_y = max_y.max(node.y + node.half_height);
+            nodes.insert(var_id, node);
+        }
+        if nodes.is_empty() {
+            (min_x, min_y, max_x, max_y) = (0., 0., 0., 0.);
+        }
+        let (view_x, view_y) = (min_x - SVG_PADDING, min_y - SVG_PADDING);
+        let width = max_x - min_x + 2. * SVG_PADDING;
+        let height = max_y - min_y + 2. * SVG_PADDING;
+
+        let mut svg_str = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.1}\" height=\"{height:.1}\" viewBox=\"{view_x:.1} {view_y:.1} {width:.1} {height:.1}\">\n"
+        );
+        svg_str.push_str(&Self::svg_marker_defs());
+
+        // edges are drawn first, so that they are below the nodes
+        svg_str.push_str("  <g fill=\"none\" stroke-width=\"2\">\n");
+        for regulation in self.sorted_regulations() {
+            let regulator = &nodes[regulation.get_regulator()];
+            let target = &nodes[regulation.get_target()];
+            let path = if regulation.get_regulator() == regulation.get_target() {
+                // self-loop above the node
+                let (x, y) = (regulator.x, regulator.y - regulator.half_height);
+                let (dx, dy) = (regulator.half_width.min(20.), SVG_NODE_HEIGHT * 1.5);
+                format!(
+                    "M {:.1} {y:.1} C {:.1} {:.1}, {:.1} {:.1}, {:.1} {y:.1}",
+                    x - dx / 2.,
+                    x - dx,
+                    y - dy,
+                    x + dx,
+                    y - dy,
+                    x + dx / 2.,
+                )
+            } else {
+                // mutual regulations are curved (each to a different side), to not overlap
+                let is_mutual = self
+                    .get_regulation(regulation.get_target(), regulation.get_regulator())
+                    .is_ok();
+                let offset = if is_mutual { SVG_CURVE_OFFSET } else { 0. };
+                let (dx, dy) = (target.x - regulator.x, target.y - regulator.y);
+                let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+                let control = (
+                    (regulator.x + target.x) / 2. - dy / length * offset,
+                    (regulator.y + target.y) / 2. + dx / length * offset,
+                );
+                let start = regulator.boundary_point(control);
+                let end = target.boundary_point(control);
+                format!(
+                    "M {:.1} {:.1} Q {:.1} {:.1}, {:.1} {:.1}",
+                    start.0, start.1, control.0, control.1, end.0, end.1
+                )
+            };
+            let sign = regulation.get_sign();
+            let dash = match regulation.get_essentiality() {
+                Essentiality::True => "",
+                Essentiality::False => " stroke-dasharray=\"8 3\"",
+                Essentiality::Unknown => " stroke-dasharray=\"2 3\"",
+            };
+            svg_str.push_str(&format!(
+                "    <path d=\"{path}\" stroke=\"{}\"{dash} marker-end=\"url(#{})\"/>\n",
+                Self::monotonicity_color(sign),
+                sign.as_str_full().to_lowercase(),
+            ));
+        }
+        svg_str.push_str("  </g>\n");
+
+        svg_str.push_str("  <g font-family=\"FiraMono, monospace\" font-size=\"12\">\n");
+        for var_id in var_ids {
+            let node = &nodes[var_id];
+            let name = Self::escape_xml(self.get_var_name(var_id)?);
+            svg_str.push_str(&format!(
+                "    <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"5\" fill=\"#dddddd\" stroke=\"#bbbbbb\"/>\n",
+                node.x - node.half_width,
+                node.y - node.half_height,
+                2. * node.half_width,
+                2. * node.half_height,
+            ));
+            svg_str.push_str(&format!(
+                "    <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\">{name}</text>\n",
+                node.x, node.y,
+            ));
+        }
+        svg_str.push_str("  </g>\n</svg>\n");
+        Ok(svg_str)
+    }
+
+    /// **(internal)** List all regulations, sorted by regulator and target IDs (to get a
+    /// deterministic output).
+    fn sorted_regulations(&self) -> Vec<&Regulation> {
+        let mut regulations = self.regulations().collect::<Vec<_>>();
+        regulations.sort_by(|r1, r2| {
+            (r1.get_regulator(), r1.get_target()).cmp(&(r2.get_regulator(), r2.get_target()))
+        });
+        regulations
+    }
+
+    /// **(internal)** Color of edges with the given monotonicity (same as in the editor).
+    fn monotonicity_color(monotonicity: &Monotonicity) -> &'static str {
+        match monotonicity {
+            Monotonicity::Activation => "#4abd73",
+            Monotonicity::Inhibition => "#d05d5d",
+            Monotonicity::Dual => "#1e87f0",
+            Monotonicity::Unknown => "#797979",
+        }
+    }
+
+    /// **(internal)** DOT line style of edges with the given essentiality.
+    fn essentiality_line_style(essentiality: &Essentiality) -> &'static str {
+        match essentiality {
+            Essentiality::True => "solid",
+            Essentiality::False => "dashed",
+            Essentiality::Unknown => "dotted",
+        }
+    }
+
+    /// **(internal)** SVG definitions of arrow markers for each kind of monotonicity. Markers
+    /// are identified by lowercase names of the monotonicity variants.
+    fn svg_marker_defs() -> String {
+        let mut defs_str = "  <defs>\n".to_string();
+        for sign in [
+            Monotonicity::Activation,
+            Monotonicity::Inhibition,
+            Monotonicity::Dual,
+            Monotonicity::Unknown,
+        ] {
+            let shape = match sign {
+                Monotonicity::Activation | Monotonicity::Unknown => {
+                    "<path d=\"M 0 0 L 10 5 L 0 10 z\"/>"
+                }
+                Monotonicity::Inhibition => "<rect x=\"7\" y=\"0\" width=\"3\" height=\"10\"/>",
+                Monotonicity::Dual => "<path d=\"M 0 5 L 5 0 L 10 5 L 5 10 z\"/>",
+            };
+            defs_str.push_str(&format!(
+                "    <marker id=\"{}\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"5\" markerHeight=\"5\" orient=\"auto\" fill=\"{}\">{shape}</marker>\n",
+                sign.as_str_full().to_lowercase(),
+                Self::monotonicity_color(&sign),
+            ));
+        }
+        defs_str.push_str("  </defs>\n");
+        defs_str
+    }
+
+    /// **(internal)** Escape characters with a special meaning in XML.
+    fn escape_xml(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
+
+/// **(internal)** A box of a node in the rendered SVG, given by its center and half-sizes.
+struct SvgNode {
+    x: f32,
+    y: f32,
+    half_width: f32,
+    half_height: f32,
+}
+
+impl SvgNode {
+    /// Point where the line from the center of the node towards `point` crosses the border
+    /// of the node's box.
+    fn boundary_point(&self, point: (f32, f32)) -> (f32, f32) {
+        let (dx, dy) = (point.0 - self.x, point.1 - self.y);
+        if dx == 0. && dy == 0. {
+            return (self.x, self.y);
+        }
+        let scale_x = if dx == 0. {
+            f32::INFINITY
+        } else {
+            self.half_width / dx.abs()
+        };
+        let scale_y = if dy == 0. {
+            f32::INFINITY
+        } else {
+            self.half_height / dy.abs()
+        };
+        let scale = scale_x.min(scale_y);
+        (self.x + dx * scale, self.y + dy * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::ModelState;
+
+    /// Prepare a simple model with an activation `a -> b`, a non-essential inhibition `b -| a`,
+    /// and a self-regulation of `b`.
+    fn prepare_model() -> ModelState {
+        let mut model = ModelState::new_from_vars(vec![("a", "A"), ("b", "B<1>")]).unwrap();
+        let regulations = vec!["a -> b", "b -|X a", "b -*? b"];
+        model.add_multiple_regulations(regulations).unwrap();
+        let layout_id = ModelState::get_default_layout_id();
+        let (var_a, var_b) = (
+            model.get_var_id("a").unwrap(),
+            model.get_var_id("b").unwrap(),
+        );
+        model.update_position(&layout_id, &var_a, 0., 0.).unwrap();
+        model
+            .update_position(&layout_id, &var_b, 100., 50.)
+            .unwrap();
+        model
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let model = prepare_model();
+        let dot_str = model.to_dot();
+        assert!(dot_str.starts_with("digraph regulatory_graph {\n"));
+        assert!(dot_str.contains("    \"b\" [label=\"B<1>\"];\n"));
+        assert!(dot_str
+            .contains("    \"a\" -> \"b\" [color=\"#4abd73\", arrowhead=normal, style=solid];\n"));
+        assert!(dot_str
+            .contains("    \"b\" -> \"a\" [color=\"#d05d5d\", arrowhead=tee, style=dashed];\n"));
+        assert!(dot_str.contains(
+            "    \"b\" -> \"b\" [color=\"#1e87f0\", arrowhead=diamond, style=dotted];\n"
+        ));
+        assert!(dot_str.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let mut model = prepare_model();
+        let layout_id = ModelState::get_default_layout_id();
+        let svg_str = model.to_svg(&layout_id).unwrap();
+        assert!(svg_str.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg_str.ends_with("</svg>\n"));
+        // one node box per variable, one path per regulation (+ 3 arrow shapes in markers)
+        assert_eq!(svg_str.matches("<rect").count(), 2 + 1);
+        assert_eq!(svg_str.matches("<path").count(), 3 + 3);
+        assert!(svg_str.contains(">B&lt;1&gt;</text>"));
+        assert!(svg_str.contains("stroke-dasharray=\"8 3\" marker-end=\"url(#inhibition)\""));
+
+        // every variable must have a position in the layout
+        let new_layout_id = model.generate_layout_id("layout");
+        model.add_layout_simple(new_layout_id.clone(), "L").unwrap();
+        assert!(model.to_svg(&new_layout_id).is_ok());
+        let invalid_layout_id = model.generate_layout_id("invalid");
+        assert!(model.to_svg(&invalid_layout_id).is_err());
+    }
+}
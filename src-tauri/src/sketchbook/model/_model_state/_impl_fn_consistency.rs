@@ -0,0 +1,99 @@
+use crate::sketchbook::data_structs::RegulationMismatchData;
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::{Essentiality, ModelState};
+
+/// Methods for checking whether the annotations of regulations are consistent with the update
+/// functions of their targets.
+impl ModelState {
+    /// Compute the list of regulations whose monotonicity or essentiality annotation does not
+    /// match the actual effect of the regulator on a fully specified update function of the target.
+    ///
+    /// Only targets with non-empty update functions without uninterpreted function symbols are
+    /// considered. If the regulator turns out to be non-essential, its annotated monotonicity
+    /// is kept (any sign is valid then). Annotations `Unknown` are also reported, if the actual
+    /// value is known.
+    ///
+    /// The result is sorted by the regulations' targets and then regulators.
+    pub fn regulation_mismatches(&self) -> Vec<RegulationMismatchData> {
+        let mut mismatches = Vec::new();
+        let mut targets = self.variables.keys().collect::<Vec<_>>();
+        targets.sort();
+        for target in targets {
+            let Some(fn_tree) = self.update_fns[target].get_fn_tree() else {
+                continue;
+            };
+            let mut regulations = self
+                .regulations
+                .iter()
+                .filter(|r| r.get_target() == target)
+                .collect::<Vec<_>>();
+            regulations.sort_by_key(|r| r.get_regulator());
+            let regulators = regulations
+                .iter()
+                .map(|r| r.get_regulator().clone())
+                .collect::<Vec<VarId>>();
+
+            // partially specified functions are skipped
+            let Ok(effects) = fn_tree.semantic_effects(&regulators) else {
+                continue;
+            };
+            for regulation in regulations {
+                let (mut sign, essential) = effects[regulation.get_regulator()];
+                if essential == Essentiality::False {
+                    sign = *regulation.get_sign();
+                }
+                if sign != *regulation.get_sign() || essential != *regulation.get_essentiality() {
+                    mismatches.push(RegulationMismatchData::new(regulation, sign, essential));
+                }
+            }
+        }
+        mismatches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+
+    #[test]
+    /// Test finding regulations inconsistent with update functions.
+    fn test_regulation_mismatches() {
+        let mut model =
+            ModelState::new_from_vars(vec![("a", "a"), ("b", "b"), ("c", "c")]).unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 1).unwrap();
+        let regulations = vec!["a -> c", "b -|? c", "c -? c", "a -> b", "c -| a"];
+        model.add_multiple_regulations(regulations).unwrap();
+        let var = |id: &str| model.get_var_id(id).unwrap();
+        let (a, b, c) = (var("a"), var("b"), var("c"));
+
+        // no function is specified yet
+        assert!(model.regulation_mismatches().is_empty());
+
+        // `a -> c` and `c -? c` are inconsistent (both are essential inhibitions), and
+        // `b -|? c` is inconsistent too (non-essential, but its annotated sign is kept)
+        model.set_update_fn(&c, "!a | (b & !b) | (c ^ a)").unwrap();
+        // partially specified functions are skipped
+        model.set_update_fn(&b, "f(a)").unwrap();
+        // `c -| a` is consistent
+        model.set_update_fn(&a, "!c").unwrap();
+
+        let mismatches = model.regulation_mismatches();
+        let summary = mismatches
+            .iter()
+            .map(|m| {
+                let reg = (m.regulator.as_str(), m.target.as_str());
+                (reg, m.inferred_sign, m.inferred_essential)
+            })
+            .collect::<Vec<_>>();
+        let expected = vec![
+            (("a", "c"), Monotonicity::Inhibition, Essentiality::True),
+            (("b", "c"), Monotonicity::Inhibition, Essentiality::False),
+            (("c", "c"), Monotonicity::Inhibition, Essentiality::True),
+        ];
+        assert_eq!(summary, expected);
+        assert_eq!(
+            mismatches[0].message,
+            "Regulation `a` -> `c`: monotonicity is `Activation`, but the update function implies `Inhibition`."
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+use std::collections::HashMap;
+
+/// **(internal)** A single interaction (directed edge) of an imported interaction network,
+/// given by labels of its source and target nodes, and by the (free-form) interaction type.
+pub(super) struct Interaction {
+    pub source: String,
+    pub target: String,
+    pub kind: String,
+}
+
+/// Methods for merging interaction networks (such as those given by SIF or GraphML files)
+/// into a `ModelState`.
+impl ModelState {
+    /// **(internal)** Merge nodes and interactions of an interaction network into this model.
+    ///
+    /// Each node is mapped to an existing variable with the same ID or name, or a new variable
+    /// is created (with an ID derived from the node's label). Each interaction becomes a
+    /// regulation with `Unknown` essentiality, and monotonicity given by the interaction type
+    /// (see [Self::interaction_monotonicity]).
+    ///
+    /// Duplicate interactions, interactions with conflicting types (which are imported with
+    /// `Unknown` monotonicity), and interactions already present in the model (which are kept
+    /// unchanged) are reported in the returned list of warnings, together with nodes whose
+    /// labels are not valid identifiers. The mapping of node labels to variables is returned
+    /// as well.
+    pub(super) fn merge_interactions(
+        &mut self,
+        nodes: &[String],
+        interactions: &[Interaction],
+    ) -> Result<(HashMap<String, VarId>, Vec<String>), String> {
+        let mut warnings = Vec::new();
+        let mut var_mapping: HashMap<String, VarId> = HashMap::new();
+        let edge_nodes = interactions.iter().flat_map(|i| [&i.source, &i.target]);
+        for label in nodes.iter().chain(edge_nodes) {
+            if !var_mapping.contains_key(label) {
+                let var_id = self.resolve_interaction_node(label, &mut warnings)?;
+                var_mapping.insert(label.clone(), var_id);
+            }
+        }
+
+        // collect interactions for each pair of nodes (in the order of appearance)
+        let mut regulations: Vec<(VarId, VarId, Monotonicity)> = Vec::new();
+        for interaction in interactions {
+            let (source, target) = (&interaction.source, &interaction.target);
+            let regulator = var_mapping[source].clone();
+            let target_var = var_mapping[target].clone();
+            let monotonicity = Self::interaction_monotonicity(&interaction.kind);
+            let existing = regulations
+                .iter_mut()
+                .find(|(r, t, _)| r == &regulator && t == &target_var);
+            match existing {
+                None => regulations.push((regulator, target_var, monotonicity)),
+                Some((_, _, sign)) if *sign == monotonicity => warnings.push(format!(
+                    "Duplicate interaction `{source}` -> `{target}` was ignored."
+                )),
+                Some((_, _, sign)) => {
+                    warnings.push(format!(
+                        "Interactions `{source}` -> `{target}` have conflicting types, \
+                        the regulation is imported with unknown monotonicity."
+                    ));
+                    *sign = Monotonicity::Unknown;
+                }
+            }
+        }
+
+        for (regulator, target, monotonicity) in regulations {
+            if let Ok(regulation) = self.get_regulation(&regulator, &target) {
+                let warning = if regulation.get_sign() == &monotonicity {
+                    format!("Regulation `{regulator}` -> `{target}` already exists.")
+                } else {
+                    format!(
+                        "Regulation `{regulator}` -> `{target}` already exists with a different \
+                        monotonicity, the existing regulation is kept."
+                    )
+                };
+                warnings.push(warning);
+                continue;
+            }
+            self.add_regulation(regulator, target, Essentiality::Unknown, monotonicity)?;
+        }
+        Ok((var_mapping, warnings))
+    }
+
+    /// **(internal)** Map the interaction type (such as `activates`, `inhibits`, `+`, or `-1`)
+    /// to the corresponding monotonicity. Unrecognized types are mapped to `Unknown`.
+    fn interaction_monotonicity(kind: &str) -> Monotonicity {
+        const INHIBITING: [&str; 6] = [
+            "inhibit",
+            "inactivat",
+            "repress",
+            "suppress",
+            "down-regulat",
+            "downregulat",
+        ];
+        const ACTIVATING: [&str; 5] = ["activat", "stimulat", "induc", "up-regulat", "upregulat"];
+        let kind = kind.trim().to_lowercase();
+        match kind.as_str() {
+            "+" | "1" | "+1" | "->" | "pos" | "positive" | "up" => Monotonicity::Activation,
+            "-" | "-1" | "-|" | "neg" | "negative" | "down" => Monotonicity::Inhibition,
+            _ if INHIBITING.iter().any(|k| kind.contains(k)) => Monotonicity::Inhibition,
+            _ if ACTIVATING.iter().any(|k| kind.contains(k)) => Monotonicity::Activation,
+            _ => Monotonicity::Unknown,
+        }
+    }
+
+    /// **(internal)** Find the variable with ID or name matching the node `label`, or add a new
+    /// variable for the node (reporting when its ID must differ from the label).
+    fn resolve_interaction_node(
+        &mut self,
+        label: &str,
+        warnings: &mut Vec<String>,
+    ) -> Result<VarId, String> {
+        if let Ok(var_id) = self.get_var_id(label) {
+            return Ok(var_id);
+        }
+        if let Some((var_id, _)) = self.variables().find(|(_, v)| v.get_name() == label) {
+            return Ok(var_id.clone());
+        }
+        let var_id = self.generate_var_id(label);
+        if var_id.as_str() != label {
+            warnings.push(format!(
+                "Node `{label}` was imported as variable `{var_id}`."
+            ));
+        }
+        self.add_var(var_id.clone(), label)?;
+        Ok(var_id)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::app::event::Event;
 use crate::app::state::{Consumed, SessionHelper};
 use crate::app::DynError;
-use crate::sketchbook::data_structs::{LayoutData, LayoutNodeData};
+use crate::sketchbook::data_structs::{AutoLayoutData, LayoutData, LayoutNodeData};
 use crate::sketchbook::event_utils::{make_reversible, mk_model_event, mk_model_state_change};
 use crate::sketchbook::ids::LayoutId;
 use crate::sketchbook::layout::NodePosition;
@@ -68,6 +68,43 @@ impl ModelState {
             let mut reverse_event = event.clone();
             reverse_event.payload = Some(orig_pos_data.to_json_str());
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with("auto_layout", at_path).is_some() {
+            // get payload components (json for AutoLayoutData)
+            let payload = Self::clone_payload_str(event, component_name)?;
+            let auto_layout_data = AutoLayoutData::from_json_str(payload.as_str())?;
+            let selected = auto_layout_data
+                .variables
+                .iter()
+                .map(|id| self.get_var_id(id))
+                .collect::<Result<Vec<_>, String>>()?;
+            let selected = if selected.is_empty() {
+                None
+            } else {
+                Some(selected.as_slice())
+            };
+            let new_positions =
+                self.compute_auto_layout(&layout_id, auto_layout_data.algorithm, selected)?;
+
+            // positions are updated via individual `update_position` events (all together
+            // forming a single reversible action), nodes that do not move are skipped
+            let at_path = ["layout", layout_id.as_str(), "update_position"];
+            let mut event_list = Vec::new();
+            for (var_id, position) in new_positions {
+                if self.get_node_position(&layout_id, &var_id)? == &position {
+                    continue;
+                }
+                let node_data = LayoutNodeData::new(
+                    layout_id.as_str(),
+                    var_id.as_str(),
+                    position.0,
+                    position.1,
+                );
+                event_list.push(mk_model_event(&at_path, Some(&node_data.to_json_str())));
+            }
+            if event_list.is_empty() {
+                return Ok(Consumed::NoChange);
+            }
+            Ok(Consumed::Restart(event_list))
         } else if Self::starts_with("remove", at_path).is_some() {
             Self::assert_payload_empty(event, component_name)?;
 
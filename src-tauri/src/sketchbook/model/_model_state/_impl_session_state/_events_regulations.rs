@@ -6,7 +6,7 @@ use crate::sketchbook::event_utils::{
     make_reversible, mk_model_event, mk_model_state_change, mk_stat_prop_event,
 };
 use crate::sketchbook::ids::{StatPropertyId, VarId};
-use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+use crate::sketchbook::model::{Essentiality, FnNormalForm, ModelState, Monotonicity};
 use crate::sketchbook::properties::StatProperty;
 use crate::sketchbook::JsonSerde;
 
@@ -79,6 +79,35 @@ impl ModelState {
         Ok(make_reversible(state_change, event, reverse_event))
     }
 
+    /// Perform event of synchronizing the monotonicity and essentiality of all regulations with
+    /// the fully specified update functions of their targets (see `regulation_mismatches`).
+    ///
+    /// This breaks the event down into `set_sign` and `set_essentiality` events for each
+    /// inconsistent regulation, so that the corresponding static properties are updated too,
+    /// and the whole synchronization can be undone at once.
+    pub(super) fn event_sync_regulation_annotations(&mut self) -> Result<Consumed, DynError> {
+        let mut event_list = Vec::new();
+        for mismatch in self.regulation_mismatches() {
+            let (regulator, target) = (mismatch.regulator.as_str(), mismatch.target.as_str());
+            if mismatch.sign != mismatch.inferred_sign {
+                let payload = mismatch.inferred_sign.to_json_str();
+                let event_path = ["regulation", regulator, target, "set_sign"];
+                event_list.push(mk_model_event(&event_path, Some(&payload)));
+            }
+            if mismatch.essential != mismatch.inferred_essential {
+                let payload = mismatch.inferred_essential.to_json_str();
+                let event_path = ["regulation", regulator, target, "set_essentiality"];
+                event_list.push(mk_model_event(&event_path, Some(&payload)));
+            }
+        }
+
+        if event_list.is_empty() {
+            Ok(Consumed::NoChange)
+        } else {
+            Ok(Consumed::Restart(event_list))
+        }
+    }
+
     /// Perform event of modifying or removing existing `regulation` component of this `ModelState`.
     pub(super) fn event_modify_regulation(
         &mut self,
@@ -271,11 +300,53 @@ impl ModelState {
             let mut reverse_event = event.clone();
             reverse_event.payload = Some(orig_essentiality.to_json_str());
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with("remove_with_fn_rewrite", at_path).is_some() {
+            // get the payload - a Boolean constant to substitute for the regulator
+            let value_str = Self::clone_payload_str(event, component_name)?;
+            let value = serde_json::from_str::<bool>(&value_str)?;
+            self.event_remove_regulation_with_fn_rewrite(&regulator_id, &target_id, value)
         } else {
             Self::invalid_path_error_specific(at_path, component_name)
         }
     }
 
+    /// Perform event of removing a regulation, while also rewriting the update function of
+    /// the target so that it does not use the regulator anymore. All occurrences of the
+    /// regulator in the function are substituted with the Boolean constant `value`, and the
+    /// resulting function is simplified (see [FnNormalForm::Simplified]) to get rid of the
+    /// constants.
+    ///
+    /// This breaks the event down into the standard `remove` event, followed by a `set_update_fn`
+    /// event (only if the function uses the regulator). Since the restarted events are executed
+    /// from last to first, the function is rewritten before the regulation is removed, and the
+    /// whole change can be undone at once.
+    fn event_remove_regulation_with_fn_rewrite(
+        &mut self,
+        regulator_id: &VarId,
+        target_id: &VarId,
+        value: bool,
+    ) -> Result<Consumed, DynError> {
+        // make sure the regulation exists
+        self.get_regulation(regulator_id, target_id)?;
+
+        let reg_event_path = [
+            "regulation",
+            regulator_id.as_str(),
+            target_id.as_str(),
+            "remove",
+        ];
+        let mut event_list = vec![mk_model_event(&reg_event_path, None)];
+        let mut update_fn = self.get_update_fn(target_id)?.clone();
+        if update_fn.collect_variables().contains(regulator_id) {
+            update_fn.substitute_var_with_const(regulator_id, value, self);
+            update_fn.convert_to_normal_form(FnNormalForm::Simplified, self)?;
+            let fn_event_path = ["variable", target_id.as_str(), "set_update_fn"];
+            let payload = update_fn.get_fn_expression();
+            event_list.push(mk_model_event(&fn_event_path, Some(payload)));
+        }
+        Ok(Consumed::Restart(event_list))
+    }
+
     /// Perform events related to `regulations` component of this `ModelState`.
     pub(super) fn perform_regulation_event(
         &mut self,
@@ -286,6 +357,7 @@ impl ModelState {
 
         // there is either adding of a new regulation, or editing/removing of an existing one
         // when adding new regulation, the `at_path` is just ["add"]
+        // when syncing annotations with update functions, the `at_path` is ["sync_annotations"]
         // when editing existing variable, the `at_path` is ["regulator", "target", "<action>"]
 
         if Self::starts_with("add", at_path).is_some() {
@@ -294,6 +366,10 @@ impl ModelState {
         } else if Self::starts_with("add_raw", at_path).is_some() {
             Self::assert_path_length(at_path, 1, component_name)?;
             self.event_add_regulation_raw(event)
+        } else if Self::starts_with("sync_annotations", at_path).is_some() {
+            Self::assert_path_length(at_path, 1, component_name)?;
+            Self::assert_payload_empty(event, component_name)?;
+            self.event_sync_regulation_annotations()
         } else {
             Self::assert_path_length(at_path, 3, component_name)?;
             let regulator_id_str = at_path.first().unwrap();
@@ -6,7 +6,7 @@ use crate::sketchbook::data_structs::{
 };
 use crate::sketchbook::event_utils::{make_reversible, mk_model_event, mk_model_state_change};
 use crate::sketchbook::ids::UninterpretedFnId;
-use crate::sketchbook::model::ModelState;
+use crate::sketchbook::model::{FnNormalForm, ModelState};
 use crate::sketchbook::JsonSerde;
 
 /// Implementation for events related to `uninterpreted functions` of the model.
@@ -165,6 +165,22 @@ impl ModelState {
             let mut reverse_event = event.clone();
             reverse_event.payload = Some(original_expression);
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with("normalize_expression", at_path).is_some() {
+            // get the payload - json string encoding the `FnNormalForm`
+            let form_str = Self::clone_payload_str(event, component_name)?;
+            let form = FnNormalForm::from_json_str(&form_str)?;
+            let mut uninterpreted_fn = self.get_uninterpreted_fn(&fn_id)?.clone();
+            if let Err(message) = uninterpreted_fn.convert_to_normal_form(form, self) {
+                return Ok(Consumed::InputError(Box::new(AeonError::new(
+                    message, None,
+                ))));
+            }
+
+            // the change itself is a standard `set_expression` event
+            let expr_event_path = ["uninterpreted_fn", fn_id.as_str(), "set_expression"];
+            let payload = uninterpreted_fn.get_fn_expression();
+            let expr_event = mk_model_event(&expr_event_path, Some(payload));
+            Ok(Consumed::Restart(vec![expr_event]))
         } else if Self::starts_with("set_monotonicity", at_path).is_some() {
             // get the payload and parse it
             let payload = Self::clone_payload_str(event, component_name)?;
@@ -1,12 +1,15 @@
 use crate::app::event::Event;
 use crate::app::state::{Consumed, SessionHelper};
 use crate::app::{AeonError, DynError};
-use crate::sketchbook::data_structs::{ChangeIdData, LayoutNodeData, VariableData};
+use crate::sketchbook::data_structs::{
+    ChangeIdData, LayoutNodeData, RegulationData, UpdateFnChangeData, VariableData,
+};
 use crate::sketchbook::event_utils::{make_reversible, mk_model_event, mk_model_state_change};
 use crate::sketchbook::ids::VarId;
 use crate::sketchbook::layout::NodePosition;
-use crate::sketchbook::model::ModelState;
+use crate::sketchbook::model::{Essentiality, FnNormalForm, ModelState, Monotonicity, UpdateFn};
 use crate::sketchbook::JsonSerde;
+use std::collections::HashMap;
 
 /// Implementation for events related to `variables` of the model.
 impl ModelState {
@@ -140,8 +143,13 @@ impl ModelState {
             let reverse_event = mk_model_event(&reverse_at_path, Some(var_id.as_str()));
             Ok(make_reversible(state_change, event, reverse_event))
         } else if Self::starts_with("set_update_fn", at_path).is_some() {
-            // get the payload - string for "new_expression"
-            let new_expression = Self::clone_payload_str(event, component_name)?;
+            // get the payload - string for "new_expression", or json encoding `UpdateFnChangeData`
+            let payload = Self::clone_payload_str(event, component_name)?;
+            let change_data = UpdateFnChangeData::from_payload(&payload)?;
+            let new_expression = change_data.expression;
+            if change_data.add_regulations {
+                return self.event_set_update_fn_with_regulations(&var_id, &new_expression);
+            }
             let original_expression = self.get_update_fn(&var_id)?.to_string();
             // actually, this check is not that relevant, as the expressions might be "normalized" during parsing
             if new_expression == original_expression {
@@ -162,11 +170,72 @@ impl ModelState {
             let mut reverse_event = event.clone();
             reverse_event.payload = Some(original_expression);
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with("normalize_update_fn", at_path).is_some() {
+            // get the payload - json string encoding the `FnNormalForm`
+            let form_str = Self::clone_payload_str(event, component_name)?;
+            let form = FnNormalForm::from_json_str(&form_str)?;
+            let mut update_fn = self.get_update_fn(&var_id)?.clone();
+            if let Err(message) = update_fn.convert_to_normal_form(form, self) {
+                return Ok(Consumed::InputError(Box::new(AeonError::new(
+                    message, None,
+                ))));
+            }
+
+            // the change itself is a standard `set_update_fn` event
+            let fn_event_path = ["variable", var_id.as_str(), "set_update_fn"];
+            let payload = update_fn.get_fn_expression();
+            let fn_event = mk_model_event(&fn_event_path, Some(payload));
+            Ok(Consumed::Restart(vec![fn_event]))
         } else {
             Self::invalid_path_error_specific(at_path, component_name)
         }
     }
 
+    /// Perform event of setting an update function of variable `var_id` (with the
+    /// `add_regulations` option), while also adding all the regulations that are missing (i.e.,
+    /// the expression uses a variable that is not a regulator of `var_id` yet).
+    ///
+    /// This breaks the event down into the standard `set_update_fn` event (with just the plain
+    /// expression), followed by `add` events for the missing regulations. Since the restarted
+    /// events are executed from last to first, the regulations are added before the function
+    /// is set, and the whole change can be undone at once. Signs of the new regulations are
+    /// deduced syntactically from the expression (see `FnTree::syntactic_monotonicity`), their
+    /// essentiality is `Unknown`.
+    fn event_set_update_fn_with_regulations(
+        &mut self,
+        var_id: &VarId,
+        new_expression: &str,
+    ) -> Result<Consumed, DynError> {
+        // parse the expression first to find the variables it uses
+        let new_update_fn = UpdateFn::try_from_str(new_expression, self)?;
+        let regulators = self.regulators(var_id)?;
+        let mut missing_regulators = new_update_fn
+            .collect_variables()
+            .into_iter()
+            .filter(|v| !regulators.contains(&v))
+            .collect::<Vec<_>>();
+        missing_regulators.sort();
+
+        let signs = match new_update_fn.get_fn_tree() {
+            Some(tree) => tree.syntactic_monotonicity(),
+            None => HashMap::new(),
+        };
+        let fn_event_path = ["variable", var_id.as_str(), "set_update_fn"];
+        let mut event_list = vec![mk_model_event(&fn_event_path, Some(new_expression))];
+        for regulator in missing_regulators {
+            let sign = signs.get(&regulator).unwrap_or(&Monotonicity::Unknown);
+            let reg_data = RegulationData::new(
+                regulator.as_str(),
+                var_id.as_str(),
+                Essentiality::Unknown,
+                *sign,
+            );
+            let payload = reg_data.to_json_str();
+            event_list.push(mk_model_event(&["regulation", "add"], Some(&payload)));
+        }
+        Ok(Consumed::Restart(event_list))
+    }
+
     /// Perform events related to `variables` component of this `ModelState`.
     pub(super) fn perform_variable_event(
         &mut self,
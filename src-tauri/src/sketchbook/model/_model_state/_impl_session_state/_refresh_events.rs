@@ -69,6 +69,16 @@ impl ModelState {
         make_refresh_event(full_path, regulation_list)
     }
 
+    /// Get a list of all regulations whose annotations are inconsistent with the update
+    /// functions of their targets.
+    pub(super) fn refresh_regulation_mismatches(
+        &self,
+        full_path: &[String],
+    ) -> Result<Event, DynError> {
+        // the list is already sorted, so that it is deterministic
+        make_refresh_event(full_path, self.regulation_mismatches())
+    }
+
     /// Get a list of all layouts (just basic information like IDs and names).
     pub(super) fn refresh_layouts(&self, full_path: &[String]) -> Result<Event, DynError> {
         let mut layout_list: Vec<LayoutData> = self
@@ -34,6 +34,7 @@ impl SessionState for ModelState {
             Some(&"get_variables") => self.refresh_variables(full_path),
             Some(&"get_uninterpreted_fns") => self.refresh_uninterpreted_fns(full_path),
             Some(&"get_regulations") => self.refresh_regulations(full_path),
+            Some(&"get_regulation_mismatches") => self.refresh_regulation_mismatches(full_path),
             Some(&"get_layouts") => self.refresh_layouts(full_path),
             Some(&"get_layout_nodes") => self.refresh_layout_nodes(full_path, &at_path[1..]),
             _ => Self::invalid_path_error_generic(at_path),
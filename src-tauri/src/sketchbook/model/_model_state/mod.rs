@@ -4,14 +4,32 @@ use crate::sketchbook::model::{Regulation, UninterpretedFn, UpdateFn, Variable};
 use crate::sketchbook::{JsonSerde, Manager};
 use std::collections::{HashMap, HashSet};
 
+/// **(internal)** Methods for automatically placing nodes of layouts.
+mod _impl_auto_layout;
+/// **(internal)** Methods for converting between `ModelState` and the `.aeon` format.
+mod _impl_convert_aeon;
 /// **(internal)** Methods for converting between `ModelState` and `BooleanNetwork`.
 mod _impl_convert_bn;
+/// **(internal)** Methods for converting between `ModelState` and the `.bnet` format.
+mod _impl_convert_bnet;
+/// **(internal)** Methods for importing GraphML interaction networks into `ModelState`.
+mod _impl_convert_graphml;
 /// **(internal)** Methods for converting between `ModelState` and `RegulatoryGraph`.
 mod _impl_convert_reg_graph;
+/// **(internal)** Methods for converting between `ModelState` and the SBML-qual format.
+mod _impl_convert_sbml;
+/// **(internal)** Methods for importing SIF interaction networks into `ModelState`.
+mod _impl_convert_sif;
 /// **(internal)** Methods for safely constructing or editing instances of `ModelState`.
 mod _impl_editing;
+/// **(internal)** Methods for exporting the regulatory graph of `ModelState` to DOT and SVG.
+mod _impl_export_graph;
+/// **(internal)** Methods for checking consistency of regulations with update functions.
+mod _impl_fn_consistency;
 /// **(internal)** Implementation of the safe identifier generating.
 mod _impl_id_generating;
+/// **(internal)** Methods for merging interaction networks into `ModelState`.
+mod _impl_merge_interactions;
 /// **(internal)** Methods for observing instances of `ModelState` (various getters, etc.).
 mod _impl_observing;
 /// **(internal)** Implementation of serialization traits [Serialize] and [Deserialize].
@@ -1,5 +1,7 @@
 use crate::sketchbook::ids::{UninterpretedFnId, VarId};
-use crate::sketchbook::model::{Essentiality, FnArgument, FnTree, ModelState, Monotonicity};
+use crate::sketchbook::model::{
+    Essentiality, FnArgument, FnNormalForm, FnTree, ModelState, Monotonicity,
+};
 use crate::sketchbook::utils::assert_name_valid;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -160,6 +162,11 @@ impl UninterpretedFn {
         &self.expression
     }
 
+    /// Get the syntactic tree of the function's expression, or `None` if it is unspecified.
+    pub fn get_fn_tree(&self) -> &Option<FnTree> {
+        &self.tree
+    }
+
     /// Set the function's expression to a given string.
     ///
     /// `model` is used to provide context regarding valid IDs.
@@ -262,6 +269,22 @@ impl UninterpretedFn {
         }
     }
 
+    /// Convert the syntactic tree (and the expression) into the given normal form.
+    ///
+    /// Returns an error (and keeps the function unchanged) if the normal form would be too large.
+    pub fn convert_to_normal_form(
+        &mut self,
+        form: FnNormalForm,
+        context: &ModelState,
+    ) -> Result<(), String> {
+        if let Some(tree) = &self.tree {
+            let new_tree = tree.to_normal_form(form)?;
+            self.expression = new_tree.to_string(context, Some(self.get_arity()));
+            self.tree = Some(new_tree);
+        }
+        Ok(())
+    }
+
     /// Substitute all occurrences of a given function symbol in the syntactic tree.
     pub fn substitute_fn_symbol(
         &mut self,
@@ -1,5 +1,5 @@
 use crate::sketchbook::ids::{UninterpretedFnId, VarId};
-use crate::sketchbook::model::{FnTree, ModelState};
+use crate::sketchbook::model::{FnNormalForm, FnTree, ModelState};
 use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -78,6 +78,11 @@ impl UpdateFn {
             .map(|tree| tree.to_fn_update_recursive(context))
     }
 
+    /// Get the syntactic tree of the function, or `None` if the function is unspecified.
+    pub fn get_fn_tree(&self) -> &Option<FnTree> {
+        &self.tree
+    }
+
     /// Return a set of all variables that are actually used as inputs in this function.
     pub fn collect_variables(&self) -> HashSet<VarId> {
         if let Some(tree) = &self.tree {
@@ -105,6 +110,32 @@ impl UpdateFn {
         }
     }
 
+    /// Substitute all occurrences of a given variable in the syntactic tree with a Boolean
+    /// constant `value`.
+    pub fn substitute_var_with_const(&mut self, var_id: &VarId, value: bool, context: &ModelState) {
+        if let Some(tree) = &self.tree {
+            let new_tree = tree.substitute_var_with_const(var_id, value);
+            self.expression = new_tree.to_string(context, None);
+            self.tree = Some(new_tree);
+        }
+    }
+
+    /// Convert the syntactic tree (and the expression) into the given normal form.
+    ///
+    /// Returns an error (and keeps the function unchanged) if the normal form would be too large.
+    pub fn convert_to_normal_form(
+        &mut self,
+        form: FnNormalForm,
+        context: &ModelState,
+    ) -> Result<(), String> {
+        if let Some(tree) = &self.tree {
+            let new_tree = tree.to_normal_form(form)?;
+            self.expression = new_tree.to_string(context, None);
+            self.tree = Some(new_tree);
+        }
+        Ok(())
+    }
+
     /// Substitute all occurrences of a given function symbol in the syntactic tree.
     pub fn substitute_fn_symbol(
         &mut self,
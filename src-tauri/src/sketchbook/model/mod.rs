@@ -3,10 +3,16 @@ use crate::sketchbook::layout::Layout;
 
 /// **(internal)** Definition and utility methods for `BinaryOp`.
 mod _binary_op;
+/// **(internal)** Definition of `BnetExportMode`.
+mod _bnet_export_mode;
 /// **(internal)** Definition and utility methods for `Essentiality`.
 mod _essentiality;
+/// **(internal)** Definition of `FnNormalForm`.
+mod _fn_normal_form;
 /// **(internal)** Definition and utility methods for `FnTree`.
 mod _function_tree;
+/// **(internal)** Simplification of `FnTree` and its conversion into normal forms.
+mod _function_tree_normal_forms;
 /// **(internal)** Definition and utility methods for the manager class `ModelState`.
 mod _model_state;
 /// **(internal)** Definition and utility methods for `Monotonicity`.
@@ -23,8 +29,11 @@ mod _update_function;
 mod _variable;
 
 pub use _binary_op::BinaryOp;
+pub use _bnet_export_mode::BnetExportMode;
 pub use _essentiality::Essentiality;
+pub use _fn_normal_form::FnNormalForm;
 pub use _function_tree::FnTree;
+pub use _function_tree_normal_forms::MAX_NORMAL_FORM_CLAUSES;
 pub use _model_state::ModelState;
 pub use _monotonicity::Monotonicity;
 pub use _regulation::Regulation;
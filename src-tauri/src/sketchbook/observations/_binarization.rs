@@ -0,0 +1,317 @@
+use crate::sketchbook::observations::VarValue;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Method used to binarize continuous (numeric) values of a variable.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BinarizationMethod {
+    /// The same fixed threshold for all variables. Values above the threshold are `1`,
+    /// the rest is `0`.
+    Threshold(f64),
+    /// Per-variable median as a threshold. Values above the median are `1`, the rest is `0`.
+    Median,
+    /// Per-variable clustering into two clusters using (one-dimensional) k-means. The threshold
+    /// lies in the middle between the cluster centres.
+    KMeans,
+    /// Per-variable split into three bands. Bounds of the middle band are given relative to the
+    /// range of the variable's values (e.g., `lower = 0.4` and `upper = 0.6` place the bounds
+    /// at 40% and 60% of the range). Values in the lower band are `0`, values in the upper band
+    /// are `1`, and the ambiguous values in the middle band are `*`.
+    ThreeWay { lower: f64, upper: f64 },
+}
+
+/// Thresholds used to binarize values of a single variable. Values above the `upper` threshold
+/// are `1`, values that are at most `lower` are `0`, and values in between are `*`.
+///
+/// If both thresholds are the same, each value is either `0` or `1`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VarThresholds {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Binarization of continuous data, keeping the raw values and the thresholds computed for each
+/// variable, so that the binarization can be revisited later (e.g., using a different method).
+///
+/// Raw values are given as a matrix with a row for each observation and a column for each
+/// variable. Missing values are `None`, and these are always binarized to `*`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Binarization {
+    method: BinarizationMethod,
+    thresholds: Vec<VarThresholds>,
+    raw_values: Vec<Vec<Option<f64>>>,
+}
+
+impl<'de> JsonSerde<'de> for BinarizationMethod {}
+impl<'de> JsonSerde<'de> for Binarization {}
+
+impl VarThresholds {
+    /// Binarize the given (possibly missing) value.
+    pub fn binarize(&self, value: Option<f64>) -> VarValue {
+        match value {
+            Some(value) if value > self.upper => VarValue::True,
+            Some(value) if value <= self.lower => VarValue::False,
+            _ => VarValue::Any,
+        }
+    }
+}
+
+impl Binarization {
+    /// Compute the binarization of given raw values (a row for each observation) with
+    /// `num_vars` variables, using the given method.
+    ///
+    /// All the rows must have `num_vars` values, and all the values must be finite numbers.
+    pub fn new(
+        raw_values: Vec<Vec<Option<f64>>>,
+        num_vars: usize,
+        method: BinarizationMethod,
+    ) -> Result<Binarization, String> {
+        if raw_values.iter().any(|row| row.len() != num_vars) {
+            return Err("Number of variables and length of raw observations differ.".to_string());
+        }
+        if raw_values
+            .iter()
+            .flatten()
+            .flatten()
+            .any(|v| !v.is_finite())
+        {
+            return Err("Raw values must be finite numbers.".to_string());
+        }
+        let thresholds = (0..num_vars)
+            .map(|var_idx| {
+                let mut values: Vec<f64> = raw_values.iter().filter_map(|r| r[var_idx]).collect();
+                values.sort_by(|a, b| a.total_cmp(b));
+                Self::compute_thresholds(&values, &method)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Binarization {
+            method,
+            thresholds,
+            raw_values,
+        })
+    }
+
+    /// Method used to compute the binarization.
+    pub fn get_method(&self) -> &BinarizationMethod {
+        &self.method
+    }
+
+    /// Thresholds computed for each of the variables.
+    pub fn get_thresholds(&self) -> &Vec<VarThresholds> {
+        &self.thresholds
+    }
+
+    /// Raw values of the binarized data (a row for each observation).
+    pub fn get_raw_values(&self) -> &Vec<Vec<Option<f64>>> {
+        &self.raw_values
+    }
+
+    /// Number of observations (rows) in the raw data.
+    pub fn num_observations(&self) -> usize {
+        self.raw_values.len()
+    }
+
+    /// Number of variables (columns) in the raw data.
+    pub fn num_variables(&self) -> usize {
+        self.thresholds.len()
+    }
+
+    /// Binarized values of the observation on the given index.
+    pub fn binarize_observation(&self, index: usize) -> Result<Vec<VarValue>, String> {
+        let row = self
+            .raw_values
+            .get(index)
+            .ok_or("Index is larger than number of observations.".to_string())?;
+        let values = row
+            .iter()
+            .zip(self.thresholds.iter())
+            .map(|(value, thresholds)| thresholds.binarize(*value))
+            .collect();
+        Ok(values)
+    }
+
+    /// Insert a row of missing values for a new observation on the given index.
+    pub fn insert_empty_observation(&mut self, index: usize) {
+        let num_vars = self.num_variables();
+        self.raw_values.insert(index, vec![None; num_vars]);
+    }
+
+    /// Remove the raw values of the observation on the given index.
+    pub fn remove_observation(&mut self, index: usize) {
+        self.raw_values.remove(index);
+    }
+
+    /// Insert a column of missing values for a new variable on the given index. Since there are
+    /// no values to compute the thresholds from, both thresholds are set to `0`.
+    pub fn insert_empty_variable(&mut self, index: usize) {
+        let thresholds = VarThresholds {
+            lower: 0.,
+            upper: 0.,
+        };
+        self.thresholds.insert(index, thresholds);
+        self.raw_values
+            .iter_mut()
+            .for_each(|r| r.insert(index, None));
+    }
+
+    /// Remove the raw values and thresholds of the variable on the given index.
+    pub fn remove_variable(&mut self, index: usize) {
+        self.thresholds.remove(index);
+        self.raw_values.iter_mut().for_each(|r| {
+            r.remove(index);
+        });
+    }
+
+    /// **(internal)** Compute thresholds for a variable with given (sorted, non-missing) values.
+    fn compute_thresholds(
+        values: &[f64],
+        method: &BinarizationMethod,
+    ) -> Result<VarThresholds, String> {
+        let threshold = match method {
+            BinarizationMethod::Threshold(threshold) => *threshold,
+            BinarizationMethod::Median => Self::median(values),
+            BinarizationMethod::KMeans => Self::two_means_threshold(values),
+            BinarizationMethod::ThreeWay { lower, upper } => {
+                if !(0. ..=1.).contains(lower) || !(0. ..=1.).contains(upper) || lower > upper {
+                    return Err(format!(
+                        "Invalid bounds of the middle band ({lower}, {upper}). Bounds must be \
+                        within 0 and 1, and the lower bound must not exceed the upper one."
+                    ));
+                }
+                let (min, max) = match (values.first(), values.last()) {
+                    (Some(min), Some(max)) => (*min, *max),
+                    _ => (0., 0.),
+                };
+                return Ok(VarThresholds {
+                    lower: min + lower * (max - min),
+                    upper: min + upper * (max - min),
+                });
+            }
+        };
+        Ok(VarThresholds {
+            lower: threshold,
+            upper: threshold,
+        })
+    }
+
+    /// **(internal)** Median of given sorted values (`0` if there are no values).
+    fn median(values: &[f64]) -> f64 {
+        let n = values.len();
+        match n {
+            0 => 0.,
+            _ if n % 2 == 1 => values[n / 2],
+            _ => (values[n / 2 - 1] + values[n / 2]) / 2.,
+        }
+    }
+
+    /// **(internal)** Threshold separating given sorted values into two clusters that minimize
+    /// the within-cluster sum of squares (optimal 2-means clustering).
+    ///
+    /// In one dimension, clusters of the optimal solution are formed by a prefix and suffix of
+    /// the sorted values, so we can check all the splits. The threshold is then the midpoint
+    /// between the two cluster means.
+    fn two_means_threshold(values: &[f64]) -> f64 {
+        let n = values.len();
+        if n == 0 {
+            return 0.;
+        }
+        let (total_sum, total_sq): (f64, f64) = values
+            .iter()
+            .fold((0., 0.), |(s, sq), v| (s + v, sq + v * v));
+        let sse = |sum: f64, sq: f64, count: usize| sq - sum * sum / count as f64;
+
+        // if all the values are the same, the threshold is this value
+        let (mut best_cost, mut best_threshold) = (f64::INFINITY, values[0]);
+        let (mut prefix_sum, mut prefix_sq) = (0., 0.);
+        for k in 1..n {
+            prefix_sum += values[k - 1];
+            prefix_sq += values[k - 1] * values[k - 1];
+            if values[k - 1] == values[k] {
+                // clusters cannot split equal values
+                continue;
+            }
+            let (suffix_sum, suffix_sq) = (total_sum - prefix_sum, total_sq - prefix_sq);
+            let cost = sse(prefix_sum, prefix_sq, k) + sse(suffix_sum, suffix_sq, n - k);
+            let threshold = (prefix_sum / k as f64 + suffix_sum / (n - k) as f64) / 2.;
+            if cost < best_cost {
+                (best_cost, best_threshold) = (cost, threshold);
+            }
+        }
+        best_threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::{Binarization, BinarizationMethod, VarValue};
+
+    /// Binarize all rows of the raw values with a single variable, and return the values as
+    /// a string.
+    fn binarize_column(raw_values: &[Option<f64>], method: BinarizationMethod) -> String {
+        let rows = raw_values.iter().map(|v| vec![*v]).collect();
+        let binarization = Binarization::new(rows, 1, method).unwrap();
+        (0..raw_values.len())
+            .map(|i| {
+                binarization.binarize_observation(i).unwrap()[0]
+                    .as_str()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    /// Test binarizing values using all the methods.
+    fn test_binarization_methods() {
+        let values = [Some(0.1), Some(0.2), None, Some(0.9), Some(1.0), Some(0.3)];
+        let threshold = BinarizationMethod::Threshold(0.25);
+        assert_eq!(binarize_column(&values, threshold), "00*111");
+        assert_eq!(
+            binarize_column(&values, BinarizationMethod::Median),
+            "00*110"
+        );
+        assert_eq!(
+            binarize_column(&values, BinarizationMethod::KMeans),
+            "00*110"
+        );
+        let three_way = BinarizationMethod::ThreeWay {
+            lower: 0.1,
+            upper: 0.6,
+        };
+        assert_eq!(binarize_column(&values, three_way), "0**11*");
+
+        // median splits the values in half, k-means finds the natural clusters
+        let values = [Some(1.), Some(2.), Some(3.), Some(10.)];
+        assert_eq!(binarize_column(&values, BinarizationMethod::Median), "0011");
+        assert_eq!(binarize_column(&values, BinarizationMethod::KMeans), "0001");
+        let constant = [Some(5.), Some(5.)];
+        assert_eq!(binarize_column(&constant, BinarizationMethod::KMeans), "00");
+    }
+
+    #[test]
+    /// Test that thresholds are kept, and that invalid inputs are rejected.
+    fn test_binarization_data() {
+        let raw_values = vec![vec![Some(1.), Some(4.)], vec![Some(3.), None]];
+        let mut binarization =
+            Binarization::new(raw_values.clone(), 2, BinarizationMethod::Median).unwrap();
+        assert_eq!(binarization.get_thresholds()[0].lower, 2.);
+        assert_eq!(binarization.get_thresholds()[1].upper, 4.);
+        assert_eq!(binarization.get_raw_values(), &raw_values);
+
+        binarization.insert_empty_variable(1);
+        assert_eq!(
+            binarization.binarize_observation(1).unwrap(),
+            vec![VarValue::True, VarValue::Any, VarValue::Any]
+        );
+        binarization.remove_observation(0);
+        assert_eq!(binarization.num_observations(), 1);
+
+        assert!(Binarization::new(raw_values.clone(), 3, BinarizationMethod::Median).is_err());
+        let infinite = vec![vec![Some(f64::INFINITY)]];
+        assert!(Binarization::new(infinite, 1, BinarizationMethod::Median).is_err());
+        let invalid_band = BinarizationMethod::ThreeWay {
+            lower: 0.7,
+            upper: 0.2,
+        };
+        assert!(Binarization::new(raw_values, 2, invalid_band).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use crate::sketchbook::ids::{ObservationId, VarId};
-use crate::sketchbook::observations::{DataCategory, Dataset, Observation, VarValue};
+use crate::sketchbook::observations::{
+    Binarization, BinarizationMethod, DataCategory, Dataset, Observation, VarValue,
+};
 use crate::sketchbook::utils::assert_ids_unique;
 use std::collections::HashMap;
 
@@ -38,9 +40,37 @@ impl Dataset {
             variables,
             category,
             index_map,
+            binarization: None,
         })
     }
 
+    /// Create new dataset by binarizing continuous data using given method. The raw values
+    /// (a row for each observation, `None` for missing values) and computed thresholds are kept
+    /// with the dataset.
+    ///
+    /// Number of observation IDs must match the number of rows of raw values, and number of
+    /// variables must match the length of each row.
+    pub fn new_binarized(
+        observation_ids: Vec<&str>,
+        var_names: Vec<&str>,
+        raw_values: Vec<Vec<Option<f64>>>,
+        method: BinarizationMethod,
+        category: DataCategory,
+    ) -> Result<Self, String> {
+        if observation_ids.len() != raw_values.len() {
+            return Err("Number of observations and rows of raw values differ.".to_string());
+        }
+        let binarization = Binarization::new(raw_values, var_names.len(), method)?;
+        let observations = observation_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| Observation::new(binarization.binarize_observation(i)?, id))
+            .collect::<Result<Vec<Observation>, String>>()?;
+        let mut dataset = Self::new(observations, var_names, category)?;
+        dataset.binarization = Some(binarization);
+        Ok(dataset)
+    }
+
     /// Shorthand to create new `empty` dataset from given variables and observations category.
     pub fn new_empty(var_names: Vec<&str>, category: DataCategory) -> Result<Self, String> {
         Self::new(Vec::new(), var_names, category)
@@ -77,6 +107,9 @@ impl Dataset {
         self.assert_no_observation(obs.get_id())?;
         self.index_map
             .insert(obs.get_id().clone(), self.observations.len());
+        if let Some(binarization) = self.binarization.as_mut() {
+            binarization.insert_empty_observation(self.observations.len());
+        }
         self.observations.push(obs);
         Ok(())
     }
@@ -86,6 +119,9 @@ impl Dataset {
     pub fn pop_observation(&mut self) {
         if let Some(obs) = self.observations.pop() {
             self.index_map.remove(obs.get_id());
+            if let Some(binarization) = self.binarization.as_mut() {
+                binarization.remove_observation(self.observations.len());
+            }
         }
     }
 
@@ -103,6 +139,9 @@ impl Dataset {
 
         let obs = self.observations.remove(idx);
         self.index_map.remove(obs.get_id());
+        if let Some(binarization) = self.binarization.as_mut() {
+            binarization.remove_observation(idx);
+        }
         Ok(())
     }
 
@@ -117,6 +156,9 @@ impl Dataset {
         }
 
         self.index_map.insert(obs.get_id().clone(), index);
+        if let Some(binarization) = self.binarization.as_mut() {
+            binarization.insert_empty_observation(index);
+        }
         self.observations.insert(index, obs);
         // re-index everything after the new observation
         self.observations.iter().enumerate().for_each(|(i, o)| {
@@ -135,6 +177,9 @@ impl Dataset {
         for obs in self.observations.iter_mut() {
             obs.remove_nth_value(idx)?;
         }
+        if let Some(binarization) = self.binarization.as_mut() {
+            binarization.remove_variable(idx);
+        }
         Ok(())
     }
 
@@ -157,6 +202,9 @@ impl Dataset {
         for obs in self.observations.iter_mut() {
             obs.add_value(index, VarValue::Any)?;
         }
+        if let Some(binarization) = self.binarization.as_mut() {
+            binarization.insert_empty_variable(index);
+        }
         Ok(())
     }
 
@@ -228,6 +276,39 @@ impl Dataset {
     pub fn set_category(&mut self, category: DataCategory) {
         self.category = category;
     }
+
+    /// Set the binarization (raw continuous data and thresholds) kept with this dataset, or
+    /// remove it (if `None`). The binarized values of observations are not changed.
+    ///
+    /// The dimensions of the raw data must match the dataset.
+    pub fn set_binarization(&mut self, binarization: Option<Binarization>) -> Result<(), String> {
+        if let Some(binarization) = &binarization {
+            if binarization.num_observations() != self.num_observations()
+                || binarization.num_variables() != self.num_variables()
+            {
+                return Err("Dimensions of raw data and the dataset differ.".to_string());
+            }
+        }
+        self.binarization = binarization;
+        Ok(())
+    }
+
+    /// Binarize the raw continuous data kept with this dataset again, using a given method.
+    /// Values of all observations are replaced by the newly binarized values.
+    ///
+    /// Returns `Err` if the dataset has no raw data (i.e., it was not binarized).
+    pub fn rebinarize(&mut self, method: BinarizationMethod) -> Result<(), String> {
+        let Some(binarization) = &self.binarization else {
+            return Err("Dataset has no raw data to binarize.".to_string());
+        };
+        let raw_values = binarization.get_raw_values().clone();
+        let binarization = Binarization::new(raw_values, self.num_variables(), method)?;
+        for (i, obs) in self.observations.iter_mut().enumerate() {
+            obs.set_all_values(binarization.binarize_observation(i)?)?;
+        }
+        self.binarization = Some(binarization);
+        Ok(())
+    }
 }
 
 /// Observing `Dataset` instances.
@@ -323,6 +404,12 @@ impl Dataset {
         &self.category
     }
 
+    /// Binarization (raw continuous data and thresholds) of the dataset, if the observations
+    /// were binarized.
+    pub fn binarization(&self) -> Option<&Binarization> {
+        self.binarization.as_ref()
+    }
+
     /// Make a string describing this `Dataset` in a human-readable format.
     /// If `list_all` is set to `true`, all observation vectors are listed. Otherwise, just
     /// a summary is given (number of observations).
@@ -15,7 +15,7 @@ impl Serialize for Dataset {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Dataset", 4)?;
+        let mut state = serializer.serialize_struct("Dataset", 5)?;
         state.serialize_field("observations", &self.observations)?;
         state.serialize_field("variables", &self.variables)?;
         state.serialize_field("category", &self.category)?;
@@ -23,6 +23,7 @@ impl Serialize for Dataset {
         // Serialize `index_map` field (HashMap with non-String keys) as a HashMap with String keys
         let index_map = stringify_and_order_keys(&self.index_map);
         state.serialize_field("index_map", &index_map)?;
+        state.serialize_field("binarization", &self.binarization)?;
 
         state.end()
     }
@@ -40,6 +41,7 @@ impl<'de> Deserialize<'de> for Dataset {
             Variables,
             Category,
             IndexMap,
+            Binarization,
         }
 
         impl<'de> Deserialize<'de> for Field {
@@ -53,8 +55,9 @@ impl<'de> Deserialize<'de> for Dataset {
                     type Value = Field;
 
                     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-                        formatter
-                            .write_str("`observations`, `variables`, `category` or `index_map`")
+                        formatter.write_str(
+                            "`observations`, `variables`, `category`, `index_map` or `binarization`",
+                        )
                     }
 
                     fn visit_str<E>(self, value: &str) -> Result<Field, E>
@@ -66,6 +69,7 @@ impl<'de> Deserialize<'de> for Dataset {
                             "variables" => Ok(Field::Variables),
                             "category" => Ok(Field::Category),
                             "index_map" => Ok(Field::IndexMap),
+                            "binarization" => Ok(Field::Binarization),
                             _ => Err(de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -91,6 +95,7 @@ impl<'de> Deserialize<'de> for Dataset {
                 let mut variables = None;
                 let mut category = None;
                 let mut index_map = None;
+                let mut binarization = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -119,6 +124,12 @@ impl<'de> Deserialize<'de> for Dataset {
                             let i_map: HashMap<String, usize> = map.next_value()?;
                             index_map = Some(parse_map_keys(i_map).map_err(de::Error::custom)?);
                         }
+                        Field::Binarization => {
+                            if binarization.is_some() {
+                                return Err(de::Error::duplicate_field("binarization"));
+                            }
+                            binarization = Some(map.next_value()?);
+                        }
                     }
                 }
 
@@ -132,11 +143,19 @@ impl<'de> Deserialize<'de> for Dataset {
                     variables,
                     category,
                     index_map,
+                    // datasets without binarization might omit the field
+                    binarization: binarization.unwrap_or_default(),
                 })
             }
         }
 
-        const FIELDS: &[&str] = &["observations", "variables", "category", "index_map"];
+        const FIELDS: &[&str] = &[
+            "observations",
+            "variables",
+            "category",
+            "index_map",
+            "binarization",
+        ];
         deserializer.deserialize_struct("Dataset", FIELDS, DatasetVisitor)
     }
 }
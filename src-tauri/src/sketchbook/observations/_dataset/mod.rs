@@ -1,5 +1,5 @@
 use crate::sketchbook::ids::{ObservationId, VarId};
-use crate::sketchbook::observations::{DataCategory, Observation};
+use crate::sketchbook::observations::{Binarization, DataCategory, Observation};
 use crate::sketchbook::{JsonSerde, Manager};
 use std::collections::HashMap;
 
@@ -18,7 +18,10 @@ mod _impl_serde;
 /// `Dataset` provides classical Rust API for modifications. It also manages its observations
 /// through event-based API. However, this API is limited, and only serves as an extension to that
 /// of the `ObservationManager`.
-#[derive(Clone, Debug, Eq, PartialEq)]
+///
+/// If the observations were binarized from continuous data, the dataset also keeps the
+/// [Binarization] (raw values and thresholds), so that the binarization can be revisited.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Dataset {
     /// List of binarized observations.
     observations: Vec<Observation>,
@@ -28,6 +31,8 @@ pub struct Dataset {
     category: DataCategory,
     /// Index map from observation IDs to their index in vector, for faster searching.
     index_map: HashMap<ObservationId, usize>,
+    /// Raw continuous data and thresholds, if the observations were binarized.
+    binarization: Option<Binarization>,
 }
 
 impl<'de> JsonSerde<'de> for Dataset {}
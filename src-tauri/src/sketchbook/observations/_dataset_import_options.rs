@@ -0,0 +1,56 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Options for loading datasets from delimiter-separated files (such as CSV or TSV).
+///
+/// The default options correspond to a CSV file with a header line of variable names, and
+/// each following line representing one observation, starting with its ID. Values are
+/// `0`, `1`, or `*` (unspecified).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DatasetImportOptions {
+    /// Character separating the values on a line (must be ASCII), such as `,`, `;`, or `\t`.
+    pub delimiter: char,
+    /// If `true`, each line of the file corresponds to a variable, and each column corresponds
+    /// to an observation (i.e., the header and ID column swap their roles).
+    pub transposed: bool,
+    /// Additional tokens that are interpreted as unspecified values (in addition to `*`),
+    /// such as `NA`, `-`, or an empty string for empty cells.
+    pub missing_tokens: Vec<String>,
+    /// Index of the column with observation IDs (or the line with observation IDs if the
+    /// data are transposed). If `None`, IDs are generated.
+    pub id_column: Option<usize>,
+    /// Whether the first line contains variable names (or, if the data are transposed,
+    /// whether the first column does). If `false`, variable names are generated.
+    pub has_header: bool,
+}
+
+impl<'de> JsonSerde<'de> for DatasetImportOptions {}
+
+impl Default for DatasetImportOptions {
+    /// Default options for comma-separated files with a header and IDs in the first column.
+    fn default() -> DatasetImportOptions {
+        DatasetImportOptions {
+            delimiter: ',',
+            transposed: false,
+            missing_tokens: Vec::new(),
+            id_column: Some(0),
+            has_header: true,
+        }
+    }
+}
+
+impl DatasetImportOptions {
+    /// Default options for tab-separated files (otherwise the same as [Self::default]).
+    pub fn tsv() -> DatasetImportOptions {
+        DatasetImportOptions {
+            delimiter: '\t',
+            ..Default::default()
+        }
+    }
+
+    /// Check whether given (trimmed) token represents an unspecified value.
+    pub fn is_missing_token(&self, token: &str) -> bool {
+        token == "*" || self.missing_tokens.iter().any(|t| t.trim() == token)
+    }
+}
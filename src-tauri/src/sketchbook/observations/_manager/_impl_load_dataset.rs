@@ -1,7 +1,15 @@
-use crate::sketchbook::observations::{Dataset, Observation, ObservationManager, VarValue};
-use std::fs::File;
+use crate::sketchbook::ids::{ObservationId, VarId};
+use crate::sketchbook::observations::{
+    BinarizationMethod, DataCategory, Dataset, DatasetImportOptions, Observation,
+    ObservationManager, VarValue,
+};
+use std::fs;
 use std::str::FromStr;
 
+/// **(internal)** Parsed tabular data - variable names, observation IDs, and rows of values
+/// (a row for each observation).
+type DataTable<T> = (Vec<String>, Vec<String>, Vec<Vec<T>>);
+
 impl ObservationManager {
     /// Load a dataset from given CSV file. The header line specifies variables, following lines
     /// represent individual observations (id and values).
@@ -11,37 +19,413 @@ impl ObservationManager {
     ///    Observation1,0,1,0,1,0,1
     ///    Observation2,1,0,*,1,0,*
     ///
+    /// To load files in other formats (different delimiters, transposed data, missing-value
+    /// tokens, ...), use [Self::load_dataset_with_options].
     pub fn load_dataset(csv_path: &str) -> Result<Dataset, String> {
-        let csv_file = File::open(csv_path).map_err(|e| e.to_string())?;
-        let mut rdr = csv::Reader::from_reader(csv_file);
+        Self::load_dataset_with_options(csv_path, &DatasetImportOptions::default())
+    }
 
-        // parse variable names from the header
-        let header = rdr.headers().map_err(|e| e.to_string())?.clone();
-        let variables = header.into_iter().skip(1).collect::<Vec<&str>>().clone();
+    /// Load a dataset from given delimiter-separated file, with the format described by the
+    /// `options` (see [DatasetImportOptions]).
+    pub fn load_dataset_with_options(
+        path: &str,
+        options: &DatasetImportOptions,
+    ) -> Result<Dataset, String> {
+        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Self::load_dataset_from_str_with_options(&contents, options)
+    }
 
-        // parse all raws as observations
-        let mut observations = Vec::new();
-        for result in rdr.records() {
-            let record = result.map_err(|e| e.to_string())?;
-            if record.is_empty() {
-                return Err("Cannot import empty observation.".to_string());
+    /// Load a dataset from given string in the CSV format. See [Self::load_dataset] for
+    /// details on the format.
+    pub fn load_dataset_from_str(csv_str: &str) -> Result<Dataset, String> {
+        Self::load_dataset_from_str_with_options(csv_str, &DatasetImportOptions::default())
+    }
+
+    /// Load a dataset from given string with delimiter-separated values, with the format
+    /// described by the `options` (see [DatasetImportOptions]).
+    ///
+    /// Errors refer to the lines and columns of the original input (numbered from 1).
+    pub fn load_dataset_from_str_with_options(
+        data_str: &str,
+        options: &DatasetImportOptions,
+    ) -> Result<Dataset, String> {
+        let parse_value = |token: &str, location: &str| {
+            if options.is_missing_token(token) {
+                return Ok(VarValue::Any);
             }
-            let id = record.get(0).unwrap();
-            let values: Vec<VarValue> = record
-                .iter()
-                .skip(1)
-                .map(VarValue::from_str)
-                .collect::<Result<Vec<VarValue>, String>>()?;
-            let observation = Observation::new(values, id)?;
-            observations.push(observation);
-        }
+            VarValue::from_str(token).map_err(|_| {
+                format!(
+                    "Invalid value `{token}` at {location}. Expected `0`, `1`, `*`, \
+                    or one of the missing-value tokens."
+                )
+            })
+        };
+        let (variables, ids, rows) = Self::parse_data_table(data_str, options, parse_value)?;
+
+        let observations = ids
+            .iter()
+            .zip(rows)
+            .map(|(id, values)| Observation::new(values, id))
+            .collect::<Result<Vec<Observation>, String>>()?;
+        let variables = variables.iter().map(|v| v.as_str()).collect();
         Dataset::new_unspecified(observations, variables)
     }
 
+    /// Load a dataset of continuous (numeric) values from given delimiter-separated file, with
+    /// the format described by the `options` (see [DatasetImportOptions]), and binarize it
+    /// using the given method.
+    ///
+    /// The raw values and computed thresholds are kept with the dataset (see `Binarization`).
+    pub fn load_continuous_dataset(
+        path: &str,
+        options: &DatasetImportOptions,
+        method: BinarizationMethod,
+    ) -> Result<Dataset, String> {
+        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        Self::load_continuous_dataset_from_str(&contents, options, method)
+    }
+
+    /// Load a dataset of continuous (numeric) values from given string, and binarize it.
+    /// See [Self::load_continuous_dataset] for details.
+    pub fn load_continuous_dataset_from_str(
+        data_str: &str,
+        options: &DatasetImportOptions,
+        method: BinarizationMethod,
+    ) -> Result<Dataset, String> {
+        let parse_value = |token: &str, location: &str| {
+            if options.is_missing_token(token) {
+                return Ok(None);
+            }
+            match token.parse::<f64>() {
+                Ok(value) if value.is_finite() => Ok(Some(value)),
+                _ => Err(format!(
+                    "Invalid value `{token}` at {location}. Expected a number \
+                    or one of the missing-value tokens."
+                )),
+            }
+        };
+        let (variables, ids, rows) = Self::parse_data_table(data_str, options, parse_value)?;
+
+        let ids = ids.iter().map(|id| id.as_str()).collect();
+        let variables = variables.iter().map(|v| v.as_str()).collect();
+        Dataset::new_binarized(ids, variables, rows, method, DataCategory::Unspecified)
+    }
+
+    /// Convert the dataset into a string in the CSV format, the same as is used when loading
+    /// datasets (see [Self::load_dataset]). The first column contains observation IDs.
+    pub fn dataset_to_csv_str(dataset: &Dataset) -> Result<String, String> {
+        let mut wtr = csv::Writer::from_writer(Vec::new());
+        let header = dataset.variables().iter().map(|v| v.as_str());
+        wtr.write_record(std::iter::once("ID").chain(header))
+            .map_err(|e| e.to_string())?;
+        for observation in dataset.observations() {
+            let values = observation.get_values().iter().map(|v| v.as_str());
+            wtr.write_record(std::iter::once(observation.get_id().as_str()).chain(values))
+                .map_err(|e| e.to_string())?;
+        }
+        let csv_bytes = wtr.into_inner().map_err(|e| e.to_string())?;
+        String::from_utf8(csv_bytes).map_err(|e| e.to_string())
+    }
+
+    /// Convert the raw continuous values of a binarized dataset into a string in the CSV format,
+    /// the same as is used when loading continuous datasets with default options (see
+    /// [Self::load_continuous_dataset]). Missing values are written as `*`.
+    ///
+    /// Returns `None` if the dataset was not binarized from continuous data.
+    pub fn raw_dataset_to_csv_str(dataset: &Dataset) -> Result<Option<String>, String> {
+        let Some(binarization) = dataset.binarization() else {
+            return Ok(None);
+        };
+        let mut wtr = csv::Writer::from_writer(Vec::new());
+        let header = dataset.variables().iter().map(|v| v.to_string());
+        wtr.write_record(std::iter::once("ID".to_string()).chain(header))
+            .map_err(|e| e.to_string())?;
+        let raw_rows = binarization.get_raw_values();
+        for (observation, raw_row) in dataset.observations().iter().zip(raw_rows) {
+            let values = raw_row.iter().map(|value| match value {
+                Some(value) => value.to_string(),
+                None => "*".to_string(),
+            });
+            wtr.write_record(std::iter::once(observation.get_id().to_string()).chain(values))
+                .map_err(|e| e.to_string())?;
+        }
+        let csv_bytes = wtr.into_inner().map_err(|e| e.to_string())?;
+        String::from_utf8(csv_bytes)
+            .map_err(|e| e.to_string())
+            .map(Some)
+    }
+
     /// Load a dataset from given CSV file, and add it to this `ObservationManager`. The header
     /// line specifies variables, following lines represent individual observations (id and values).
     pub fn load_and_add_dataset(&mut self, csv_path: &str, id: &str) -> Result<(), String> {
         let dataset = Self::load_dataset(csv_path)?;
         self.add_dataset_by_str(id, dataset)
     }
+
+    /// **(internal)** Parse delimiter-separated data with the format described by `options`
+    /// into variable names, observation IDs, and rows of values (a row for each observation).
+    ///
+    /// Values are parsed using `parse_value`, which gets the (trimmed) token and its location
+    /// in the original input (for error messages).
+    fn parse_data_table<T>(
+        data_str: &str,
+        options: &DatasetImportOptions,
+        parse_value: impl Fn(&str, &str) -> Result<T, String>,
+    ) -> Result<DataTable<T>, String> {
+        let (rows, lines) = Self::read_data_grid(data_str, options)?;
+        let width = rows[0].len();
+        let (num_rows, num_cols) = if options.transposed {
+            (width, rows.len())
+        } else {
+            (rows.len(), width)
+        };
+
+        // access cells and their locations in the "observations as rows" orientation
+        let cell = |row: usize, col: usize| -> (&str, String) {
+            let (file_row, file_col) = if options.transposed {
+                (col, row)
+            } else {
+                (row, col)
+            };
+            let location = format!("line {}, column {}", lines[file_row], file_col + 1);
+            (rows[file_row][file_col].as_str(), location)
+        };
+
+        if let Some(id_col) = options.id_column {
+            if id_col >= num_cols {
+                let kind = if options.transposed { "line" } else { "column" };
+                return Err(format!(
+                    "Index {id_col} of the ID {kind} is out of range, there are only {num_cols} {kind}s."
+                ));
+            }
+        }
+        let value_cols: Vec<usize> = (0..num_cols)
+            .filter(|c| Some(*c) != options.id_column)
+            .collect();
+        let first_data_row = usize::from(options.has_header);
+
+        // parse (or generate) variable names
+        let mut variables = Vec::new();
+        for (i, col) in value_cols.iter().enumerate() {
+            if options.has_header {
+                let (name, location) = cell(0, *col);
+                VarId::new(name)
+                    .map_err(|e| format!("Invalid variable name `{name}` at {location}: {e}"))?;
+                variables.push(name.to_string());
+            } else {
+                variables.push(format!("v{}", i + 1));
+            }
+        }
+
+        // parse (or generate) IDs and values of all observations
+        let mut ids = Vec::new();
+        let mut values = Vec::new();
+        for row in first_data_row..num_rows {
+            let id = match options.id_column {
+                Some(id_col) => {
+                    let (id, location) = cell(row, id_col);
+                    ObservationId::new(id)
+                        .map_err(|e| format!("Invalid observation ID `{id}` at {location}: {e}"))?;
+                    id.to_string()
+                }
+                None => format!("o{}", row - first_data_row + 1),
+            };
+            let row_values = value_cols
+                .iter()
+                .map(|col| {
+                    let (token, location) = cell(row, *col);
+                    parse_value(token, &location)
+                })
+                .collect::<Result<Vec<T>, String>>()?;
+            ids.push(id);
+            values.push(row_values);
+        }
+        Ok((variables, ids, values))
+    }
+
+    /// **(internal)** Read all the cells of the delimiter-separated data (trimmed), together
+    /// with the line number of each row. Check that the data are non-empty and all the rows
+    /// have the same number of cells.
+    fn read_data_grid(
+        data_str: &str,
+        options: &DatasetImportOptions,
+    ) -> Result<(Vec<Vec<String>>, Vec<u64>), String> {
+        if !options.delimiter.is_ascii() {
+            return Err(format!(
+                "Delimiter `{}` is not a valid ASCII character.",
+                options.delimiter
+            ));
+        }
+        let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(options.delimiter as u8)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(data_str.as_bytes());
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut lines = Vec::new();
+        for result in rdr.records() {
+            let record = result.map_err(|e| e.to_string())?;
+            // records read from a reader always have a position
+            let line = record.position().unwrap().line();
+            if let Some(first_row) = rows.first() {
+                if record.len() != first_row.len() {
+                    return Err(format!(
+                        "Line {line} has {} values, but line {} has {}.",
+                        record.len(),
+                        lines[0],
+                        first_row.len()
+                    ));
+                }
+            }
+            rows.push(record.iter().map(|v| v.to_string()).collect::<Vec<_>>());
+            lines.push(line);
+        }
+        if rows.is_empty() {
+            return Err("Cannot import empty dataset.".to_string());
+        }
+        Ok((rows, lines))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::{
+        BinarizationMethod, DatasetImportOptions, ObservationManager, VarValue,
+    };
+
+    #[test]
+    /// Test loading datasets in the default CSV format, and exporting them back.
+    fn test_load_default_csv() {
+        let csv_str = "ID,a,b\no1,0,1\no2,*,0\n";
+        let dataset = ObservationManager::load_dataset_from_str(csv_str).unwrap();
+        assert_eq!(dataset.num_observations(), 2);
+        assert_eq!(dataset.num_variables(), 2);
+        let exported = ObservationManager::dataset_to_csv_str(&dataset).unwrap();
+        assert_eq!(exported, csv_str);
+    }
+
+    #[test]
+    /// Test loading transposed TSV data with missing-value tokens and without an ID column.
+    fn test_load_with_options() {
+        let options = DatasetImportOptions {
+            transposed: true,
+            missing_tokens: vec!["NA".to_string(), "".to_string(), "-".to_string()],
+            id_column: None,
+            ..DatasetImportOptions::tsv()
+        };
+        let data_str = "a\t1\tNA\nb\t\t0\nc\t-\t*\n";
+        let dataset =
+            ObservationManager::load_dataset_from_str_with_options(data_str, &options).unwrap();
+        assert_eq!(dataset.num_variables(), 3);
+        assert_eq!(dataset.num_observations(), 2);
+        let obs = dataset.get_observation_on_idx(0).unwrap();
+        assert_eq!(obs.get_id().as_str(), "o1");
+        assert_eq!(obs.to_values_string(), "1**");
+        let obs = dataset.get_observation_on_idx(1).unwrap();
+        assert_eq!(
+            obs.get_values(),
+            &vec![VarValue::Any, VarValue::False, VarValue::Any]
+        );
+
+        // semicolon-separated data without header, IDs in the last column
+        let options = DatasetImportOptions {
+            delimiter: ';',
+            has_header: false,
+            id_column: Some(2),
+            ..Default::default()
+        };
+        let dataset =
+            ObservationManager::load_dataset_from_str_with_options("0;1;x\n", &options).unwrap();
+        assert_eq!(dataset.variables()[1].as_str(), "v2");
+        assert_eq!(
+            dataset.get_observation_on_idx(0).unwrap().get_id().as_str(),
+            "x"
+        );
+    }
+
+    #[test]
+    /// Test that errors point to the exact location in the original data.
+    fn test_load_errors() {
+        let error = ObservationManager::load_dataset_from_str("ID,a,b\no1,0,1\no2,NA,0\n");
+        assert_eq!(
+            error.unwrap_err(),
+            "Invalid value `NA` at line 3, column 2. Expected `0`, `1`, `*`, or one of the missing-value tokens."
+        );
+
+        // locations are given in the original (not transposed) data
+        let options = DatasetImportOptions {
+            transposed: true,
+            ..Default::default()
+        };
+        let data_str = "ID,o1,o2\na,0,1\nb,1,2\n";
+        let error = ObservationManager::load_dataset_from_str_with_options(data_str, &options);
+        assert!(error
+            .unwrap_err()
+            .starts_with("Invalid value `2` at line 3, column 3."));
+
+        let error = ObservationManager::load_dataset_from_str("ID,a,b\no1,0\n");
+        assert_eq!(error.unwrap_err(), "Line 2 has 2 values, but line 1 has 3.");
+        let error = ObservationManager::load_dataset_from_str("ID,a,b c\no1,0,1\n");
+        assert!(error
+            .unwrap_err()
+            .starts_with("Invalid variable name `b c` at line 1, column 3"));
+        let options = DatasetImportOptions {
+            id_column: Some(5),
+            ..Default::default()
+        };
+        let error = ObservationManager::load_dataset_from_str_with_options("ID,a\no,1\n", &options);
+        assert!(error.unwrap_err().contains("out of range"));
+    }
+
+    #[test]
+    /// Test loading and binarizing continuous data, keeping the raw values with the dataset.
+    fn test_load_continuous() {
+        let options = DatasetImportOptions {
+            missing_tokens: vec!["NA".to_string()],
+            ..Default::default()
+        };
+        let data_str = "ID,a,b\no1,0.5,12\no2,NA,3.5\no3,2.5e1,4\n";
+        let method = BinarizationMethod::Threshold(10.);
+        let dataset =
+            ObservationManager::load_continuous_dataset_from_str(data_str, &options, method)
+                .unwrap();
+        let values: Vec<String> = dataset
+            .observations()
+            .iter()
+            .map(|o| o.to_values_string())
+            .collect();
+        assert_eq!(values, vec!["01", "*0", "10"]);
+        let binarization = dataset.binarization().unwrap();
+        assert_eq!(binarization.get_raw_values()[1], vec![None, Some(3.5)]);
+        assert_eq!(binarization.get_thresholds()[0].upper, 10.);
+
+        // raw values can be exported (and loaded) again
+        let raw_str = ObservationManager::raw_dataset_to_csv_str(&dataset)
+            .unwrap()
+            .unwrap();
+        assert_eq!(raw_str, "ID,a,b\no1,0.5,12\no2,*,3.5\no3,25,4\n");
+        let reloaded = ObservationManager::load_continuous_dataset_from_str(
+            &raw_str,
+            &DatasetImportOptions::default(),
+            BinarizationMethod::Threshold(10.),
+        )
+        .unwrap();
+        assert_eq!(reloaded, dataset);
+        let binary = ObservationManager::load_dataset_from_str("ID,a\no1,1\n").unwrap();
+        assert!(ObservationManager::raw_dataset_to_csv_str(&binary)
+            .unwrap()
+            .is_none());
+
+        let error = ObservationManager::load_continuous_dataset_from_str(
+            "ID,a\no1,high\n",
+            &options,
+            BinarizationMethod::Median,
+        );
+        assert_eq!(
+            error.unwrap_err(),
+            "Invalid value `high` at line 2, column 2. Expected a number or one of the missing-value tokens."
+        );
+    }
 }
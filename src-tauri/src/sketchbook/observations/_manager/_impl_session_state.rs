@@ -8,8 +8,10 @@ use crate::sketchbook::event_utils::{
     make_refresh_event, make_reversible, mk_obs_event, mk_obs_state_change,
 };
 use crate::sketchbook::ids::{DatasetId, ObservationId};
-use crate::sketchbook::observations::{DataCategory, ObservationManager};
+use crate::sketchbook::observations::{BinarizationMethod, DataCategory, ObservationManager};
 use crate::sketchbook::JsonSerde;
+use std::fs::File;
+use std::io::Write;
 
 impl SessionHelper for ObservationManager {}
 
@@ -107,11 +109,16 @@ impl ObservationManager {
     pub(super) fn event_load_dataset(&mut self, event: &Event) -> Result<Consumed, DynError> {
         let component_name = "observations";
 
-        // get the payload - object with a path to a csv file with dataset, and with ID
+        // get the payload - object with a path to a file with dataset, ID, and import options
         let payload = Self::clone_payload_str(event, component_name)?;
         let load_data = DatasetLoadData::from_json_str(&payload)?;
         // load and add the dataset
-        let dataset = Self::load_dataset(&load_data.path)?;
+        let dataset = match load_data.binarization {
+            Some(method) => {
+                Self::load_continuous_dataset(&load_data.path, &load_data.options, method)?
+            }
+            None => Self::load_dataset_with_options(&load_data.path, &load_data.options)?,
+        };
         let dataset_id = DatasetId::new(&load_data.id)?;
         let dataset_data = DatasetData::from_dataset(&dataset_id, &dataset);
         self.add_dataset_by_str(&dataset_data.id, dataset)?;
@@ -189,6 +196,36 @@ impl ObservationManager {
             let payload = orig_dataset_data.to_json_str();
             let reverse_event = mk_obs_event(&reverse_at_path, Some(&payload));
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with("export", at_path).is_some() {
+            // get the payload - path to the resulting CSV file
+            let path = Self::clone_payload_str(event, component_name)?;
+            let dataset = self.get_dataset(&dataset_id)?;
+            let csv_str = Self::dataset_to_csv_str(dataset)?;
+            let mut file = File::create(path)?;
+            file.write_all(csv_str.as_bytes())?;
+            Ok(Consumed::NoChange)
+        } else if Self::starts_with("binarize", at_path).is_some() {
+            // get the payload - json string encoding the binarization method
+            let payload = Self::clone_payload_str(event, component_name)?;
+            let method = BinarizationMethod::from_json_str(&payload)?;
+            let orig_dataset = self.get_dataset(&dataset_id)?;
+            let mut new_dataset = orig_dataset.clone();
+            new_dataset.rebinarize(method)?;
+            if orig_dataset == &new_dataset {
+                return Ok(Consumed::NoChange);
+            }
+
+            // perform the event, prepare the state-change variant (same as for `set_content`)
+            let orig_dataset_data = DatasetData::from_dataset(&dataset_id, orig_dataset);
+            let new_dataset_data = DatasetData::from_dataset(&dataset_id, &new_dataset);
+            self.swap_dataset_content(&dataset_id, new_dataset)?;
+            let state_change = mk_obs_state_change(&["set_content"], &new_dataset_data);
+
+            // prepare the reverse event (setting the original content back)
+            let reverse_at_path = [dataset_id.as_str(), "set_content"];
+            let payload = orig_dataset_data.to_json_str();
+            let reverse_event = mk_obs_event(&reverse_at_path, Some(&payload));
+            Ok(make_reversible(state_change, event, reverse_event))
         } else if Self::starts_with("remove_var", at_path).is_some() {
             // get the payload - string encoding a new dataset data
             let var_id_str = Self::clone_payload_str(event, component_name)?;
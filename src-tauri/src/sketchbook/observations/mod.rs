@@ -1,9 +1,13 @@
 use crate::sketchbook::ids::DatasetId;
 
+/// **(internal)** Definition and methods for `Binarization` of continuous data.
+mod _binarization;
 /// **(internal)** Definition and methods for `DataCategory`.
 mod _data_category;
 /// **(internal)** Definition and methods for `Dataset`.
 mod _dataset;
+/// **(internal)** Definition and methods for `DatasetImportOptions`.
+mod _dataset_import_options;
 /// **(internal)** Definition and methods for `ObservationManager`.
 mod _manager;
 /// **(internal)** Definition and methods for `Observation`.
@@ -11,8 +15,10 @@ mod _observation;
 /// **(internal)** Definition and methods for `VarValue`.
 mod _var_value;
 
+pub use _binarization::{Binarization, BinarizationMethod, VarThresholds};
 pub use _data_category::DataCategory;
 pub use _dataset::Dataset;
+pub use _dataset_import_options::DatasetImportOptions;
 pub use _manager::ObservationManager;
 pub use _observation::Observation;
 pub use _var_value::VarValue;
@@ -510,6 +510,9 @@ impl PropertyManager {
 
     /// Update static property's sub-field for context, where applicable.
     /// If not applicable, return `Err`.
+    ///
+    /// Only the syntax of the context is checked here. Its validity w.r.t. the model is checked
+    /// on the level of the whole `Sketch`.
     pub fn set_stat_context(&mut self, id: &StatPropertyId, context: String) -> Result<(), String> {
         self.assert_valid_static(id)?;
         let prop = self.stat_properties.get_mut(id).unwrap();
@@ -1,4 +1,7 @@
 use crate::sketchbook::ids::{DatasetId, ObservationId};
+use crate::sketchbook::model::ModelState;
+use crate::sketchbook::observations::{Dataset, ObservationManager};
+use crate::sketchbook::properties::dynamic_props::_mk_hctl_formulas::*;
 use crate::sketchbook::properties::dynamic_props::*;
 use crate::sketchbook::utils::assert_name_valid;
 use serde::{Deserialize, Serialize};
@@ -17,6 +20,13 @@ pub struct DynProperty {
 
 /// Creating dynamic properties.
 impl DynProperty {
+    /// The largest `maximal` bound supported by attractor count properties.
+    ///
+    /// The HCTL formula encoding the attractor count quantifies `maximal + 1` state variables,
+    /// and each of them needs its own copy of the network's state variables during model
+    /// checking. Beyond this bound, the symbolic computation becomes infeasible.
+    pub const MAX_ATTRACTOR_COUNT: usize = 5;
+
     /// Create "generic" `DynProperty` instance directly from a formula, which must be in a
     /// correct format (which is verified).
     pub fn mk_generic(name: &str, raw_formula: &str) -> Result<DynProperty, String> {
@@ -88,6 +98,7 @@ impl DynProperty {
         if minimal > maximal {
             return Err("`minimal` attractor count cannot be larger than `maximal`.".to_string());
         }
+        Self::check_attractor_count_bound(maximal)?;
         if minimal == 0 || maximal == 0 {
             return Err("Attractor count must be larger than 0.".to_string());
         }
@@ -283,4 +294,151 @@ impl DynProperty {
     pub fn get_prop_data(&self) -> &DynPropertyType {
         &self.variant
     }
+
+    /// Get the dataset referenced by the property, if the property has a `dataset` field and
+    /// the dataset is specified.
+    pub fn get_dataset(&self) -> Option<&DatasetId> {
+        match &self.variant {
+            DynPropertyType::ExistsFixedPoint(prop) => prop.dataset.as_ref(),
+            DynPropertyType::ExistsTrapSpace(prop) => prop.dataset.as_ref(),
+            DynPropertyType::ExistsTrajectory(prop) => prop.dataset.as_ref(),
+            DynPropertyType::HasAttractor(prop) => prop.dataset.as_ref(),
+            DynPropertyType::GenericDynProp(_) | DynPropertyType::AttractorCount(_) => None,
+        }
+    }
+
+    /// Get the observation referenced by the property, if the property has an `observation`
+    /// field and the observation is specified.
+    pub fn get_observation(&self) -> Option<&ObservationId> {
+        match &self.variant {
+            DynPropertyType::ExistsFixedPoint(prop) => prop.observation.as_ref(),
+            DynPropertyType::ExistsTrapSpace(prop) => prop.observation.as_ref(),
+            DynPropertyType::HasAttractor(prop) => prop.observation.as_ref(),
+            DynPropertyType::GenericDynProp(_)
+            | DynPropertyType::ExistsTrajectory(_)
+            | DynPropertyType::AttractorCount(_) => None,
+        }
+    }
+
+    /// Check that the property is valid with respect to the `model`. Currently, only generic
+    /// properties refer to the model directly (their HCTL formulas must only use propositions
+    /// corresponding to the model's variables), other variants are always considered valid.
+    pub fn check_with_model(&self, model: &ModelState) -> Result<(), String> {
+        match &self.variant {
+            DynPropertyType::GenericDynProp(prop) => {
+                HctlFormula::check_syntax_with_model(&prop.raw_formula, model)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Encoding dynamic properties into HCTL formulas.
+impl DynProperty {
+    /// Encode the property as a `HctlFormula`, using the templates for particular variants
+    /// (existence of a fixed point, attractor, trajectory, ...). Generic properties are
+    /// already given by a HCTL formula.
+    ///
+    /// The `observations` are used to encode properties referring to datasets. All the
+    /// required fields (datasets, observations) must be specified.
+    pub fn encode_hctl(&self, observations: &ObservationManager) -> Result<HctlFormula, String> {
+        let formula = match &self.variant {
+            DynPropertyType::GenericDynProp(prop) => return Ok(prop.processed_formula.clone()),
+            DynPropertyType::ExistsFixedPoint(prop) => {
+                let state = Self::encode_single_observation(
+                    observations,
+                    &prop.dataset,
+                    &prop.observation,
+                )?;
+                mk_formula_fixed_point(&state)
+            }
+            DynPropertyType::ExistsTrapSpace(prop) => {
+                if prop.minimal || prop.nonpercolable {
+                    return Err(
+                        "Minimal and non-percolable trap spaces cannot be encoded in HCTL."
+                            .to_string(),
+                    );
+                }
+                let sub_space = Self::encode_single_observation(
+                    observations,
+                    &prop.dataset,
+                    &prop.observation,
+                )?;
+                mk_formula_trap_space(&sub_space)
+            }
+            DynPropertyType::ExistsTrajectory(prop) => {
+                let states = Self::encode_all_observations(observations, &prop.dataset)?;
+                mk_formula_reachability_chain(&states)
+            }
+            DynPropertyType::AttractorCount(prop) => {
+                if prop.minimal > prop.maximal {
+                    return Err(
+                        "`minimal` attractor count cannot be larger than `maximal`.".to_string()
+                    );
+                }
+                Self::check_attractor_count_bound(prop.maximal)?;
+                mk_formula_attractor_count(prop.minimal, prop.maximal)
+            }
+            DynPropertyType::HasAttractor(prop) => {
+                if prop.observation.is_some() {
+                    let state = Self::encode_single_observation(
+                        observations,
+                        &prop.dataset,
+                        &prop.observation,
+                    )?;
+                    mk_formula_attractor(&state)
+                } else {
+                    let states = Self::encode_all_observations(observations, &prop.dataset)?;
+                    mk_formula_attractor_set(&states)
+                }
+            }
+        };
+        HctlFormula::try_from_str(&formula)
+    }
+
+    /// **(internal)** Check that the `maximal` bound of an attractor count property does not
+    /// exceed [DynProperty::MAX_ATTRACTOR_COUNT].
+    fn check_attractor_count_bound(maximal: usize) -> Result<(), String> {
+        if maximal > Self::MAX_ATTRACTOR_COUNT {
+            return Err(format!(
+                "`maximal` attractor count cannot be larger than {}.",
+                Self::MAX_ATTRACTOR_COUNT
+            ));
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Encode a single observation of a dataset as a conjunction of literals.
+    fn encode_single_observation(
+        observations: &ObservationManager,
+        dataset_id: &Option<DatasetId>,
+        obs_id: &Option<ObservationId>,
+    ) -> Result<String, String> {
+        let dataset_id = dataset_id.as_ref().ok_or("Dataset is not specified.")?;
+        let obs_id = obs_id.as_ref().ok_or("Observation is not specified.")?;
+        let dataset = observations.get_dataset(dataset_id)?;
+        let observation = dataset.get_observation(obs_id)?;
+        encode_observation(observation, &Self::dataset_prop_names(dataset))
+    }
+
+    /// **(internal)** Encode all observations of a dataset, each as a conjunction of literals.
+    /// The dataset must contain at least one observation.
+    fn encode_all_observations(
+        observations: &ObservationManager,
+        dataset_id: &Option<DatasetId>,
+    ) -> Result<Vec<String>, String> {
+        let dataset_id = dataset_id.as_ref().ok_or("Dataset is not specified.")?;
+        let dataset = observations.get_dataset(dataset_id)?;
+        if dataset.num_observations() == 0 {
+            return Err(format!(
+                "Dataset `{dataset_id}` does not contain any observations."
+            ));
+        }
+        encode_multiple_observations(dataset.observations(), &Self::dataset_prop_names(dataset))
+    }
+
+    /// **(internal)** Names of the propositions corresponding to the dataset's variables.
+    fn dataset_prop_names(dataset: &Dataset) -> Vec<String> {
+        dataset.variables().iter().map(|v| v.to_string()).collect()
+    }
 }
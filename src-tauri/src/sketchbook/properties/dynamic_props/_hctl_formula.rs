@@ -1,7 +1,8 @@
 use crate::sketchbook::model::ModelState;
 use crate::sketchbook::observations::{Dataset, Observation};
 use crate::sketchbook::properties::dynamic_props::_mk_hctl_formulas::*;
-use biodivine_hctl_model_checker::preprocessing::node::HctlTreeNode;
+use biodivine_hctl_model_checker::preprocessing::hctl_tree::{HctlTreeNode, NodeType};
+use biodivine_hctl_model_checker::preprocessing::operator_enums::{Atomic, HybridOp};
 use biodivine_hctl_model_checker::preprocessing::parser::parse_hctl_formula;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
@@ -91,6 +92,14 @@ impl HctlFormula {
     }
 }
 
+/// Observing HCTL formulas.
+impl HctlFormula {
+    /// Syntax tree of the HCTL formula.
+    pub fn get_tree(&self) -> &HctlTreeNode {
+        &self.tree
+    }
+}
+
 /// Static methods (to check validity of formula strings).
 impl HctlFormula {
     /// Assert that formula is correctly formed based on HCTL syntactic rules.
@@ -105,8 +114,133 @@ impl HctlFormula {
 
     /// Assert that formula is correctly formed based on HCTL syntactic rules, and also
     /// whether the propositions correspond to valid network variables used in the `model`.
+    ///
+    /// Every atomic proposition must be a valid `VarId` of the `model`, and every hybrid
+    /// variable must be properly bound (each use of a variable must be in the scope of its
+    /// binder/quantifier, and a variable cannot be bound again in its own scope). The error
+    /// names the offending proposition (or variable) and its position in the `formula`.
     pub fn check_syntax_with_model(formula: &str, model: &ModelState) -> Result<(), String> {
-        println!("For now, {formula} cannot be checked with respect to the {model:?}.");
-        todo!()
+        let tree = parse_hctl_formula(formula)?;
+        check_tree_with_model(&tree, formula, model, &mut Vec::new())
+    }
+}
+
+/// **(internal)** Recursively check that propositions in the HCTL `tree` are valid variables of
+/// the `model`, and that all hybrid variables are bound. The `bound_vars` contains variables
+/// bound by the hybrid operators above the current node. The raw `formula` string is used to
+/// find positions of the problematic symbols.
+fn check_tree_with_model(
+    tree: &HctlTreeNode,
+    formula: &str,
+    model: &ModelState,
+    bound_vars: &mut Vec<String>,
+) -> Result<(), String> {
+    match &tree.node_type {
+        NodeType::Terminal(Atomic::Prop(name)) => {
+            if !model.is_valid_var_id_str(name) {
+                return Err(format!(
+                    "Proposition `{name}`{} does not correspond to any variable of the model.",
+                    describe_position(find_proposition(formula, name))
+                ));
+            }
+        }
+        NodeType::Terminal(Atomic::Var(name)) => {
+            if !bound_vars.contains(name) {
+                return Err(format!(
+                    "Variable `{name}`{} is not bound by any hybrid operator.",
+                    describe_position(formula.find(&format!("{{{name}}}")))
+                ));
+            }
+        }
+        NodeType::Terminal(_) => {}
+        NodeType::Unary(_, child) => check_tree_with_model(child, formula, model, bound_vars)?,
+        NodeType::Binary(_, left, right) => {
+            check_tree_with_model(left, formula, model, bound_vars)?;
+            check_tree_with_model(right, formula, model, bound_vars)?;
+        }
+        NodeType::Hybrid(HybridOp::Jump, var, _, child) => {
+            if !bound_vars.contains(var) {
+                return Err(format!(
+                    "Variable `{var}`{} is used in a jump operator, but it is not bound.",
+                    describe_position(formula.find(&format!("@{{{var}}}")))
+                ));
+            }
+            check_tree_with_model(child, formula, model, bound_vars)?;
+        }
+        NodeType::Hybrid(_, var, _, child) => {
+            if bound_vars.contains(var) {
+                return Err(format!(
+                    "Variable `{var}` is bound several times in one sub-formula."
+                ));
+            }
+            bound_vars.push(var.clone());
+            let result = check_tree_with_model(child, formula, model, bound_vars);
+            bound_vars.pop();
+            result?;
+        }
+    }
+    Ok(())
+}
+
+/// **(internal)** Find the (character) position of the first occurrence of the proposition
+/// `name` in the `formula`, only considering occurrences that are whole words.
+fn find_proposition(formula: &str, name: &str) -> Option<usize> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    formula
+        .match_indices(name)
+        .find(|(idx, _)| {
+            let before = formula[..*idx].chars().next_back();
+            let after = formula[idx + name.len()..].chars().next();
+            !before.is_some_and(|c| is_word_char(c) || c == '{')
+                && !after.is_some_and(|c| is_word_char(c) || c == '}')
+        })
+        .map(|(idx, _)| formula[..idx].chars().count())
+}
+
+/// **(internal)** Describe an optional position in a formula, to be used in error messages.
+fn describe_position(position: Option<usize>) -> String {
+    match position {
+        Some(position) => format!(" at position {position}"),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::ModelState;
+    use crate::sketchbook::properties::HctlFormula;
+
+    #[test]
+    /// Test checking HCTL formulas with respect to a model.
+    fn test_check_syntax_with_model() {
+        let model = ModelState::new_from_vars(vec![("a", "a"), ("ab", "ab")]).unwrap();
+        let valid_formulas = vec![
+            "a & ab",
+            "3{x}: @{x}: AX {x} & a",
+            "!{x}: (V{y}: (@{y}: ~{x} & ab))",
+            "(3{x}: {x}) & (3{x}: ~{x})",
+        ];
+        for formula in valid_formulas {
+            assert!(HctlFormula::check_syntax_with_model(formula, &model).is_ok());
+        }
+
+        let err = HctlFormula::check_syntax_with_model("ab & abc", &model).unwrap_err();
+        assert_eq!(
+            err,
+            "Proposition `abc` at position 5 does not correspond to any variable of the model."
+        );
+        let err = HctlFormula::check_syntax_with_model("3{x}: {y}", &model).unwrap_err();
+        assert_eq!(
+            err,
+            "Variable `y` at position 6 is not bound by any hybrid operator."
+        );
+        let err = HctlFormula::check_syntax_with_model("a & (@{x}: a)", &model).unwrap_err();
+        assert_eq!(
+            err,
+            "Variable `x` at position 5 is used in a jump operator, but it is not bound."
+        );
+        assert!(HctlFormula::check_syntax_with_model("3{x}: 3{x}: {x}", &model).is_err());
+        // syntax errors are also reported
+        assert!(HctlFormula::check_syntax_with_model("a & ", &model).is_err());
     }
 }
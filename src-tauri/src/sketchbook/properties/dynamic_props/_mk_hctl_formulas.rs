@@ -274,6 +274,58 @@ pub fn mk_formula_reachability_chain(states_sequence: &[String]) -> String {
     formula
 }
 
+/// Create a formula describing that a (partially specified) state forms a trap space, i.e.,
+/// that the sub-space cannot be left by any transition.
+///
+/// > `FORALL x. JUMP x. ({state} => AX {state})`
+///
+/// Param `sub_space` is a formula describing the sub-space (conjunction of literals).
+pub fn mk_formula_trap_space(sub_space: &str) -> String {
+    assert!(!sub_space.is_empty());
+    format!("(V{{x}}: (@{{x}}: ({sub_space} => (AX ({sub_space})))))")
+}
+
+/// Create a formula describing the existence of at least `num_attractors` distinct attractors.
+///
+/// Each of the quantified states must lie in an attractor, and no two of them can reach each
+/// other (which means they are in different attractors).
+///
+/// > `EXISTS x1, ..., xN. (JUMP x1. AG EF x1) & ... & (JUMP x1. !EF x2) & ...`
+///
+/// Note that the formula uses `num_attractors` different HCTL variables.
+pub fn mk_formula_min_attractors(num_attractors: usize) -> String {
+    if num_attractors == 0 {
+        return "true".to_string();
+    }
+    let vars: Vec<String> = (1..=num_attractors).map(|i| format!("x{i}")).collect();
+    let mut conjuncts = Vec::new();
+    for (i, var_i) in vars.iter().enumerate() {
+        conjuncts.push(format!("(@{{{var_i}}}: (AG EF {{{var_i}}}))"));
+        for var_j in vars.iter().skip(i + 1) {
+            conjuncts.push(format!("(@{{{var_i}}}: ~(EF {{{var_j}}}))"));
+        }
+    }
+    let mut formula = conjuncts.join(" & ");
+    for var in vars.iter().rev() {
+        formula = format!("(3{{{var}}}: {formula})");
+    }
+    formula
+}
+
+/// Create a formula describing that the number of attractors falls into the range
+/// <`minimal`, `maximal`>.
+///
+/// > `MIN_ATTRACTORS(minimal) & !MIN_ATTRACTORS(maximal + 1)`
+///
+/// See [mk_formula_min_attractors] for details. The formula uses `maximal + 1` different
+/// HCTL variables.
+pub fn mk_formula_attractor_count(minimal: usize, maximal: usize) -> String {
+    assert!(minimal <= maximal);
+    let lower_bound = mk_formula_min_attractors(minimal);
+    let upper_bound = mk_formula_min_attractors(maximal + 1);
+    format!("({lower_bound} & ~{upper_bound})")
+}
+
 #[cfg(test)]
 mod tests {
     use crate::sketchbook::observations::{DataCategory, Dataset, Observation};
@@ -433,4 +485,19 @@ mod tests {
             "(3{x}: (@{x}: (a & b & ~c) & EF ((a & b & c) & EF (~a & b & c))))",
         );
     }
+
+    #[test]
+    /// Test creating formulas for trap spaces and attractor counts.
+    fn test_trap_space_and_attractor_count() {
+        let trap_space = mk_formula_trap_space("(a & ~b)");
+        assert_eq!(trap_space, "(V{x}: (@{x}: ((a & ~b) => (AX ((a & ~b))))))");
+
+        assert_eq!(mk_formula_min_attractors(0), "true");
+        let expected =
+            "(3{x1}: (3{x2}: (@{x1}: (AG EF {x1})) & (@{x1}: ~(EF {x2})) & (@{x2}: (AG EF {x2}))))";
+        assert_eq!(mk_formula_min_attractors(2), expected);
+
+        let count = mk_formula_attractor_count(0, 0);
+        assert_eq!(count, "(true & ~(3{x1}: (@{x1}: (AG EF {x1}))))");
+    }
 }
@@ -0,0 +1,126 @@
+use crate::sketchbook::model::BinaryOp;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Quantifiers that can be used in first-order formulas. All the quantified variables range
+/// over Boolean values.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Quantifier {
+    Exists,
+    Forall,
+}
+
+impl fmt::Display for Quantifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Quantifier::Exists => write!(f, "\\exists"),
+            Quantifier::Forall => write!(f, "\\forall"),
+        }
+    }
+}
+
+/// Syntax tree of a first-order formula.
+///
+/// The formulas are built from Boolean constants, (quantified) Boolean variables, applications
+/// of uninterpreted function symbols, and standard Boolean connectives.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum FolTreeNode {
+    /// Boolean constant (`true` or `false`).
+    Constant(bool),
+    /// Boolean variable, given by its name.
+    Var(String),
+    /// Application of a function symbol (given by its name) to a list of arguments.
+    Function(String, Vec<FolTreeNode>),
+    /// Negation of a sub-formula.
+    Not(Box<FolTreeNode>),
+    /// Binary Boolean operator applied to two sub-formulas.
+    Binary(BinaryOp, Box<FolTreeNode>, Box<FolTreeNode>),
+    /// Sub-formula with a variable (given by its name) bound by a quantifier.
+    Quantified(Quantifier, String, Box<FolTreeNode>),
+}
+
+impl fmt::Display for FolTreeNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FolTreeNode::Constant(value) => write!(f, "{value}"),
+            FolTreeNode::Var(name) => write!(f, "{name}"),
+            FolTreeNode::Function(name, args) => {
+                let args_str: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                write!(f, "{name}({})", args_str.join(", "))
+            }
+            FolTreeNode::Not(inner) => write!(f, "!{inner}"),
+            FolTreeNode::Binary(op, left, right) => write!(f, "({left} {op} {right})"),
+            FolTreeNode::Quantified(quantifier, var, inner) => {
+                write!(f, "({quantifier} {var}: {inner})")
+            }
+        }
+    }
+}
+
+impl FolTreeNode {
+    /// Collect names of all variables that appear in the formula without being bound by
+    /// a quantifier.
+    pub fn collect_free_vars(&self) -> HashSet<String> {
+        let mut free_vars = HashSet::new();
+        self.collect_free_vars_rec(&mut Vec::new(), &mut free_vars);
+        free_vars
+    }
+
+    /// Collect all function symbols used in the formula, each paired with the number of
+    /// arguments it is applied to (a symbol can appear multiple times with different counts).
+    pub fn collect_fn_symbols(&self) -> Vec<(String, usize)> {
+        let mut symbols = Vec::new();
+        self.collect_fn_symbols_rec(&mut symbols);
+        symbols
+    }
+
+    /// **(internal)** Recursively collect free variables, given the list of currently
+    /// bound ones.
+    fn collect_free_vars_rec(&self, bound: &mut Vec<String>, free_vars: &mut HashSet<String>) {
+        match self {
+            FolTreeNode::Constant(_) => {}
+            FolTreeNode::Var(name) => {
+                if !bound.contains(name) {
+                    free_vars.insert(name.clone());
+                }
+            }
+            FolTreeNode::Function(_, args) => {
+                args.iter()
+                    .for_each(|arg| arg.collect_free_vars_rec(bound, free_vars));
+            }
+            FolTreeNode::Not(inner) => inner.collect_free_vars_rec(bound, free_vars),
+            FolTreeNode::Binary(_, left, right) => {
+                left.collect_free_vars_rec(bound, free_vars);
+                right.collect_free_vars_rec(bound, free_vars);
+            }
+            FolTreeNode::Quantified(_, var, inner) => {
+                bound.push(var.clone());
+                inner.collect_free_vars_rec(bound, free_vars);
+                bound.pop();
+            }
+        }
+    }
+
+    /// **(internal)** Recursively collect function symbols with their arities.
+    fn collect_fn_symbols_rec(&self, symbols: &mut Vec<(String, usize)>) {
+        match self {
+            FolTreeNode::Constant(_) | FolTreeNode::Var(_) => {}
+            FolTreeNode::Function(name, args) => {
+                let symbol = (name.clone(), args.len());
+                if !symbols.contains(&symbol) {
+                    symbols.push(symbol);
+                }
+                args.iter()
+                    .for_each(|arg| arg.collect_fn_symbols_rec(symbols));
+            }
+            FolTreeNode::Not(inner) | FolTreeNode::Quantified(_, _, inner) => {
+                inner.collect_fn_symbols_rec(symbols)
+            }
+            FolTreeNode::Binary(_, left, right) => {
+                left.collect_fn_symbols_rec(symbols);
+                right.collect_fn_symbols_rec(symbols);
+            }
+        }
+    }
+}
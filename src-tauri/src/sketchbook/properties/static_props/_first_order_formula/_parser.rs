@@ -0,0 +1,228 @@
+use super::_fol_tree::{FolTreeNode, Quantifier};
+use super::_tokenizer::{tokenize_fol_formula, FolToken};
+use crate::sketchbook::model::BinaryOp;
+
+/// Parse a first-order formula string into its syntax tree.
+///
+/// The grammar (in the order of increasing operator priority) is following:
+///  - quantifiers `\exists x, y: φ` and `\forall x: φ` (the body extends as far right as possible)
+///  - equivalence `<=>`, implication `=>`, disjunction `|`, conjunction `&`, and exclusive
+///    disjunction `^` (all binary operators are right-associative)
+///  - negation `!`
+///  - constants `true` and `false`, variables, function applications `f(φ, ψ, ...)`,
+///    and parenthesized formulas
+pub fn parse_fol_formula(formula: &str) -> Result<FolTreeNode, String> {
+    let tokens = tokenize_fol_formula(formula)?;
+    let mut parser = FolParser {
+        tokens: &tokens,
+        index: 0,
+        length: formula.chars().count(),
+    };
+    let tree = parser.parse_formula()?;
+    if let Some((token, position)) = parser.peek() {
+        return Err(format!(
+            "Unexpected `{token}` at position {position}, expected end of formula."
+        ));
+    }
+    Ok(tree)
+}
+
+/// **(internal)** Simple recursive-descent parser operating on a list of positioned tokens.
+struct FolParser<'a> {
+    tokens: &'a [(FolToken, usize)],
+    index: usize,
+    length: usize,
+}
+
+impl FolParser<'_> {
+    /// **(internal)** Look at the current token, without consuming it.
+    fn peek(&self) -> Option<&(FolToken, usize)> {
+        self.tokens.get(self.index)
+    }
+
+    /// **(internal)** Consume the current token, returning it (and its position).
+    fn next(&mut self) -> Result<(FolToken, usize), String> {
+        let token = self.tokens.get(self.index).cloned().ok_or(format!(
+            "Unexpected end of formula at position {}.",
+            self.length
+        ))?;
+        self.index += 1;
+        Ok(token)
+    }
+
+    /// **(internal)** Consume the current token, which must be the `expected` one.
+    fn expect(&mut self, expected: FolToken) -> Result<(), String> {
+        let (token, position) = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(format!(
+                "Unexpected `{token}` at position {position}, expected `{expected}`."
+            ))
+        }
+    }
+
+    /// **(internal)** Consume the current token if it matches the given one.
+    fn consume_if(&mut self, token: &FolToken) -> bool {
+        if self.peek().map(|(t, _)| t) == Some(token) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// **(internal)** Parse a formula, starting with the operator of lowest priority.
+    fn parse_formula(&mut self) -> Result<FolTreeNode, String> {
+        self.parse_binary(0)
+    }
+
+    /// **(internal)** Parse a (right-associative) binary operator on the given priority level,
+    /// or anything with a higher priority.
+    fn parse_binary(&mut self, level: usize) -> Result<FolTreeNode, String> {
+        const LEVELS: [(FolToken, BinaryOp); 5] = [
+            (FolToken::Iff, BinaryOp::Iff),
+            (FolToken::Imp, BinaryOp::Imp),
+            (FolToken::Or, BinaryOp::Or),
+            (FolToken::And, BinaryOp::And),
+            (FolToken::Xor, BinaryOp::Xor),
+        ];
+        if level >= LEVELS.len() {
+            return self.parse_unary();
+        }
+        let (token, op) = &LEVELS[level];
+        let left = self.parse_binary(level + 1)?;
+        if self.consume_if(token) {
+            let right = self.parse_binary(level)?;
+            Ok(FolTreeNode::Binary(*op, Box::new(left), Box::new(right)))
+        } else {
+            Ok(left)
+        }
+    }
+
+    /// **(internal)** Parse a negation, a quantified formula, or an atomic formula.
+    fn parse_unary(&mut self) -> Result<FolTreeNode, String> {
+        if self.consume_if(&FolToken::Not) {
+            let inner = self.parse_unary()?;
+            return Ok(FolTreeNode::Not(Box::new(inner)));
+        }
+        if self.consume_if(&FolToken::Exists) {
+            return self.parse_quantified(Quantifier::Exists);
+        }
+        if self.consume_if(&FolToken::Forall) {
+            return self.parse_quantified(Quantifier::Forall);
+        }
+        self.parse_atom()
+    }
+
+    /// **(internal)** Parse the list of quantified variables and the body of a quantified
+    /// formula (the quantifier itself is already consumed). Multiple quantified variables
+    /// are represented by nested quantifiers.
+    fn parse_quantified(&mut self, quantifier: Quantifier) -> Result<FolTreeNode, String> {
+        let mut vars = vec![self.parse_var_name()?];
+        while self.consume_if(&FolToken::Comma) {
+            vars.push(self.parse_var_name()?);
+        }
+        self.expect(FolToken::Colon)?;
+        let body = self.parse_formula()?;
+        Ok(vars.into_iter().rev().fold(body, |inner, var| {
+            FolTreeNode::Quantified(quantifier, var, Box::new(inner))
+        }))
+    }
+
+    /// **(internal)** Parse a name of a quantified variable.
+    fn parse_var_name(&mut self) -> Result<String, String> {
+        match self.next()? {
+            (FolToken::Ident(name), position) if is_constant(&name) => Err(format!(
+                "Constant `{name}` at position {position} cannot be used as a variable."
+            )),
+            (FolToken::Ident(name), _) => Ok(name),
+            (token, position) => Err(format!(
+                "Unexpected `{token}` at position {position}, expected a variable name."
+            )),
+        }
+    }
+
+    /// **(internal)** Parse a constant, variable, function application, or a parenthesized
+    /// formula.
+    fn parse_atom(&mut self) -> Result<FolTreeNode, String> {
+        match self.next()? {
+            (FolToken::LParen, _) => {
+                let inner = self.parse_formula()?;
+                self.expect(FolToken::RParen)?;
+                Ok(inner)
+            }
+            (FolToken::Ident(name), _) if name == "true" => Ok(FolTreeNode::Constant(true)),
+            (FolToken::Ident(name), _) if name == "false" => Ok(FolTreeNode::Constant(false)),
+            (FolToken::Ident(name), _) => {
+                if !self.consume_if(&FolToken::LParen) {
+                    return Ok(FolTreeNode::Var(name));
+                }
+                let mut args = Vec::new();
+                if !self.consume_if(&FolToken::RParen) {
+                    args.push(self.parse_formula()?);
+                    while self.consume_if(&FolToken::Comma) {
+                        args.push(self.parse_formula()?);
+                    }
+                    self.expect(FolToken::RParen)?;
+                }
+                Ok(FolTreeNode::Function(name, args))
+            }
+            (token, position) => Err(format!(
+                "Unexpected `{token}` at position {position}, expected a formula."
+            )),
+        }
+    }
+}
+
+/// **(internal)** Check whether the identifier is a Boolean constant.
+fn is_constant(name: &str) -> bool {
+    name == "true" || name == "false"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_fol_formula;
+
+    #[test]
+    /// Test parsing valid formulas, checking the priority of operators and quantifiers.
+    fn test_parse_valid() {
+        let tree = parse_fol_formula("a & b | !c ^ d => e <=> f").unwrap();
+        assert_eq!(tree.to_string(), "((((a & b) | (!c ^ d)) => e) <=> f)");
+
+        let tree = parse_fol_formula("\\forall x, y: f(x, y) => f(y, x) | false").unwrap();
+        assert_eq!(
+            tree.to_string(),
+            "(\\forall x: (\\forall y: (f(x, y) => (f(y, x) | false))))"
+        );
+
+        let tree = parse_fol_formula("!(\\exists x: g(!x)) & h()").unwrap();
+        assert_eq!(tree.to_string(), "(!(\\exists x: g(!x)) & h())");
+
+        // formula printed by the tree can be parsed again
+        let reparsed = parse_fol_formula(&tree.to_string()).unwrap();
+        assert_eq!(tree, reparsed);
+    }
+
+    #[test]
+    /// Test that parsing invalid formulas fails.
+    fn test_parse_invalid() {
+        let invalid_formulas = vec![
+            "",
+            "a &",
+            "(a | b",
+            "a b",
+            "f(a, )",
+            "\\exists: a",
+            "\\exists x a",
+            "\\forall true: a",
+            "f(a))",
+        ];
+        for formula in invalid_formulas {
+            assert!(parse_fol_formula(formula).is_err());
+        }
+
+        let err = parse_fol_formula("a & & b").unwrap_err();
+        assert_eq!(err, "Unexpected `&` at position 4, expected a formula.");
+    }
+}
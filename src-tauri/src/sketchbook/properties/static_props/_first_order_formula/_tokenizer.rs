@@ -0,0 +1,177 @@
+use std::fmt;
+
+/// Tokens that can appear in a first-order formula.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FolToken {
+    /// Existential quantifier `\exists`.
+    Exists,
+    /// Universal quantifier `\forall`.
+    Forall,
+    /// Negation `!`.
+    Not,
+    /// Conjunction `&`.
+    And,
+    /// Disjunction `|`.
+    Or,
+    /// Exclusive disjunction `^`.
+    Xor,
+    /// Implication `=>`.
+    Imp,
+    /// Equivalence `<=>`.
+    Iff,
+    /// Left parenthesis `(`.
+    LParen,
+    /// Right parenthesis `)`.
+    RParen,
+    /// Comma `,` (separating function arguments or quantified variables).
+    Comma,
+    /// Colon `:` (ending a list of quantified variables).
+    Colon,
+    /// Identifier of a variable, function symbol, or a constant (`true`/`false`).
+    Ident(String),
+}
+
+impl fmt::Display for FolToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FolToken::Exists => write!(f, "\\exists"),
+            FolToken::Forall => write!(f, "\\forall"),
+            FolToken::Not => write!(f, "!"),
+            FolToken::And => write!(f, "&"),
+            FolToken::Or => write!(f, "|"),
+            FolToken::Xor => write!(f, "^"),
+            FolToken::Imp => write!(f, "=>"),
+            FolToken::Iff => write!(f, "<=>"),
+            FolToken::LParen => write!(f, "("),
+            FolToken::RParen => write!(f, ")"),
+            FolToken::Comma => write!(f, ","),
+            FolToken::Colon => write!(f, ":"),
+            FolToken::Ident(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Split the formula string into a list of tokens, each paired with its (character) position
+/// in the original string.
+pub fn tokenize_fol_formula(formula: &str) -> Result<Vec<(FolToken, usize)>, String> {
+    let chars: Vec<char> = formula.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '!' => FolToken::Not,
+            '&' => FolToken::And,
+            '|' => FolToken::Or,
+            '^' => FolToken::Xor,
+            '(' => FolToken::LParen,
+            ')' => FolToken::RParen,
+            ',' => FolToken::Comma,
+            ':' => FolToken::Colon,
+            '=' if chars.get(i + 1) == Some(&'>') => {
+                i += 1;
+                FolToken::Imp
+            }
+            '<' if chars.get(i + 1) == Some(&'=') && chars.get(i + 2) == Some(&'>') => {
+                i += 2;
+                FolToken::Iff
+            }
+            '\\' => {
+                let keyword = read_identifier(&chars, i + 1);
+                i += keyword.len();
+                match keyword.as_str() {
+                    "exists" => FolToken::Exists,
+                    "forall" => FolToken::Forall,
+                    _ => {
+                        return Err(format!(
+                            "Unknown quantifier `\\{keyword}` at position {start}."
+                        ))
+                    }
+                }
+            }
+            c if is_identifier_char(c) => {
+                let name = read_identifier(&chars, i);
+                i += name.len() - 1;
+                FolToken::Ident(name)
+            }
+            _ => return Err(format!("Unexpected symbol `{c}` at position {start}.")),
+        };
+        tokens.push((token, start));
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+/// **(internal)** Check whether the character can be part of an identifier. Same as for
+/// identifiers of variables and functions, only ASCII alphanumeric characters and underscores
+/// are allowed (so that the length of an identifier in bytes and in characters is the same).
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// **(internal)** Read an identifier (ASCII alphanumeric characters and underscores) starting
+/// at the given position.
+fn read_identifier(chars: &[char], start: usize) -> String {
+    chars[start..]
+        .iter()
+        .take_while(|c| is_identifier_char(**c))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize_fol_formula, FolToken};
+
+    #[test]
+    /// Test tokenizing a valid formula.
+    fn test_tokenize_valid() {
+        let tokens = tokenize_fol_formula("\\exists x: f(x, !y) <=> true").unwrap();
+        let expected = vec![
+            (FolToken::Exists, 0),
+            (FolToken::Ident("x".to_string()), 8),
+            (FolToken::Colon, 9),
+            (FolToken::Ident("f".to_string()), 11),
+            (FolToken::LParen, 12),
+            (FolToken::Ident("x".to_string()), 13),
+            (FolToken::Comma, 14),
+            (FolToken::Not, 16),
+            (FolToken::Ident("y".to_string()), 17),
+            (FolToken::RParen, 18),
+            (FolToken::Iff, 20),
+            (FolToken::Ident("true".to_string()), 24),
+        ];
+        assert_eq!(tokens, expected);
+    }
+
+    #[test]
+    /// Test tokenizing invalid formulas.
+    fn test_tokenize_invalid() {
+        assert!(tokenize_fol_formula("\\exist x: x").is_err());
+        assert!(tokenize_fol_formula("x + y").is_err());
+        assert!(tokenize_fol_formula("x = y").is_err());
+        assert!(tokenize_fol_formula("x <= y").is_err());
+    }
+
+    #[test]
+    /// Test that non-ASCII characters are refused, and that they do not shift the positions.
+    fn test_tokenize_non_ascii() {
+        let error = tokenize_fol_formula("\\exists é: true").unwrap_err();
+        assert_eq!(error, "Unexpected symbol `é` at position 8.");
+        let error = tokenize_fol_formula("\\forallé x: x").unwrap_err();
+        assert_eq!(error, "Unexpected symbol `é` at position 7.");
+
+        // positions are counted in characters, even after non-ASCII whitespace
+        let tokens = tokenize_fol_formula("x\u{a0}& y").unwrap();
+        let expected = vec![
+            (FolToken::Ident("x".to_string()), 0),
+            (FolToken::And, 2),
+            (FolToken::Ident("y".to_string()), 4),
+        ];
+        assert_eq!(tokens, expected);
+    }
+}
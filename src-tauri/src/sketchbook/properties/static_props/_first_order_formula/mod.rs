@@ -0,0 +1,148 @@
+use crate::sketchbook::model::ModelState;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// **(internal)** Syntax tree representation of first-order formulas.
+mod _fol_tree;
+/// **(internal)** Parser turning a sequence of tokens into a syntax tree.
+mod _parser;
+/// **(internal)** Tokenizer splitting formula strings into tokens.
+mod _tokenizer;
+
+pub use _fol_tree::{FolTreeNode, Quantifier};
+use _parser::parse_fol_formula;
+
+/// A typesafe representation of a first-order formula used in static properties.
+///
+/// The formula is built from uninterpreted function symbols applied to Boolean arguments,
+/// Boolean connectives, and quantifiers over Boolean variables. For example, formula
+/// `\forall x, y: f(x, y) <=> f(y, x)` states that function `f` is symmetric.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct FirstOrderFormula {
+    formula: String,
+    tree: FolTreeNode,
+}
+
+impl fmt::Display for FirstOrderFormula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.formula)
+    }
+}
+
+/// Creating first-order formulas.
+impl FirstOrderFormula {
+    /// Parse `FirstOrderFormula` instance directly from a string, which must be in a
+    /// correct format (see [Self::check_pure_syntax]).
+    pub fn try_from_str(formula: &str) -> Result<FirstOrderFormula, String> {
+        Ok(FirstOrderFormula {
+            formula: formula.to_string(),
+            tree: Self::parse_and_check(formula)?,
+        })
+    }
+}
+
+/// Editing first-order formulas.
+impl FirstOrderFormula {
+    /// Change the formula represented by this instance.
+    pub fn change_formula(&mut self, new_formula: &str) -> Result<(), String> {
+        self.tree = Self::parse_and_check(new_formula)?;
+        self.formula = new_formula.to_string();
+        Ok(())
+    }
+}
+
+/// Observing first-order formulas.
+impl FirstOrderFormula {
+    /// Raw str version of the first-order formula.
+    pub fn as_str(&self) -> &str {
+        &self.formula
+    }
+
+    /// Syntax tree of the first-order formula.
+    pub fn get_tree(&self) -> &FolTreeNode {
+        &self.tree
+    }
+
+    /// Check that this formula respects the `model`, i.e., that all function symbols correspond
+    /// to uninterpreted functions of the model, and that they are applied to correct number
+    /// of arguments.
+    pub fn check_with_model(&self, model: &ModelState) -> Result<(), String> {
+        for (fn_name, num_args) in self.tree.collect_fn_symbols() {
+            let fn_id = model.get_uninterpreted_fn_id(&fn_name).map_err(|_| {
+                format!(
+                    "Function symbol `{fn_name}` is not an uninterpreted function of the model."
+                )
+            })?;
+            let arity = model.get_uninterpreted_fn(&fn_id)?.get_arity();
+            if arity != num_args {
+                return Err(format!(
+                    "Function `{fn_name}` has arity {arity}, but it is applied to {num_args} arguments."
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Static methods (to check validity of formula strings).
+impl FirstOrderFormula {
+    /// Check if the formula is correctly formed based on predefined FO syntactic rules.
+    /// Apart from the syntax itself, all variables must be bound by a quantifier.
+    pub fn check_pure_syntax(formula: &str) -> Result<(), String> {
+        Self::parse_and_check(formula).map(|_| ())
+    }
+
+    /// Check if the formula is correctly formed based on predefined FO syntactic rules, and also
+    /// whether formula respects the model (function symbols must be valid uninterpreted
+    /// functions of the model, applied to a correct number of arguments).
+    pub fn check_syntax_with_model(formula: &str, model: &ModelState) -> Result<(), String> {
+        Self::try_from_str(formula)?.check_with_model(model)
+    }
+
+    /// **(internal)** Parse the formula and check that it does not contain free variables.
+    fn parse_and_check(formula: &str) -> Result<FolTreeNode, String> {
+        let tree = parse_fol_formula(formula)?;
+        let mut free_vars: Vec<String> = tree.collect_free_vars().into_iter().collect();
+        free_vars.sort();
+        if !free_vars.is_empty() {
+            return Err(format!(
+                "Variables must be bound by a quantifier, but following are free: {}.",
+                free_vars.join(", ")
+            ));
+        }
+        Ok(tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::ModelState;
+    use crate::sketchbook::properties::FirstOrderFormula;
+
+    #[test]
+    /// Test checking syntax of formulas, including free variables.
+    fn test_pure_syntax() {
+        assert!(FirstOrderFormula::check_pure_syntax("true").is_ok());
+        assert!(FirstOrderFormula::check_pure_syntax("\\exists x: f(x) & !g(x, true)").is_ok());
+        assert!(FirstOrderFormula::check_pure_syntax("f(x)").is_err());
+        assert!(FirstOrderFormula::check_pure_syntax("(\\exists x: f(x)) & g(x)").is_err());
+        assert!(FirstOrderFormula::check_pure_syntax("\\exists x f(x)").is_err());
+    }
+
+    #[test]
+    /// Test checking formulas with respect to a model.
+    fn test_syntax_with_model() {
+        let mut model = ModelState::new_from_vars(vec![("a", "a")]).unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 2).unwrap();
+
+        let valid = "\\forall x, y: f(x, y) <=> f(y, x)";
+        assert!(FirstOrderFormula::check_syntax_with_model(valid, &model).is_ok());
+        let wrong_arity = "\\forall x: f(x)";
+        assert!(FirstOrderFormula::check_syntax_with_model(wrong_arity, &model).is_err());
+        let unknown_fn = "\\forall x: g(x, x)";
+        assert!(FirstOrderFormula::check_syntax_with_model(unknown_fn, &model).is_err());
+        // variables of the model are not function symbols
+        let var_as_fn = "\\forall x: a(x)";
+        assert!(FirstOrderFormula::check_syntax_with_model(var_as_fn, &model).is_err());
+    }
+}
@@ -1,9 +1,11 @@
 use crate::sketchbook::ids::{UninterpretedFnId, VarId};
-use crate::sketchbook::model::{Essentiality, Monotonicity};
+use crate::sketchbook::model::{Essentiality, FnTree, ModelState, Monotonicity};
 use crate::sketchbook::properties::static_props::*;
 use crate::sketchbook::properties::FirstOrderFormula;
 use crate::sketchbook::utils::assert_name_valid;
+use biodivine_lib_bdd::boolean_expression::BooleanExpression;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// A typesafe representation of various kinds of static properties.
 /// Each property has a `name` and field `variant` encompassing inner data.
@@ -21,8 +23,6 @@ pub struct StatProperty {
 impl StatProperty {
     /// Create "generic" `StatProperty` instance directly from a formula, which must be in a
     /// correct format (which is verified).
-    ///
-    /// TODO: `FirstOrderFormula` struct currently lacks syntax check
     pub fn mk_generic(name: &str, raw_formula: &str) -> Result<StatProperty, String> {
         let property = GenericStatProp {
             raw_formula: raw_formula.to_string(),
@@ -62,6 +62,7 @@ impl StatProperty {
         value: Essentiality,
         context: String,
     ) -> Result<StatProperty, String> {
+        Self::check_context_syntax(&context)?;
         let property = RegulationEssential {
             input,
             target,
@@ -102,6 +103,7 @@ impl StatProperty {
         value: Monotonicity,
         context: String,
     ) -> Result<StatProperty, String> {
+        Self::check_context_syntax(&context)?;
         let property = RegulationMonotonic {
             input,
             target,
@@ -130,7 +132,7 @@ impl StatProperty {
         };
         Ok(StatProperty {
             name: name.to_string(),
-            variant: StatPropertyType::FnInputEssentialContext(property),
+            variant: StatPropertyType::FnInputEssential(property),
         })
     }
 
@@ -143,6 +145,7 @@ impl StatProperty {
         value: Essentiality,
         context: String,
     ) -> Result<StatProperty, String> {
+        Self::check_context_syntax(&context)?;
         let property = FnInputEssential {
             input_index,
             target,
@@ -184,6 +187,7 @@ impl StatProperty {
         value: Monotonicity,
         context: String,
     ) -> Result<StatProperty, String> {
+        Self::check_context_syntax(&context)?;
         let property = FnInputMonotonic {
             input_index,
             target,
@@ -432,7 +436,10 @@ impl StatProperty {
 
     /// Update property's sub-field for context, where applicable.
     /// If not applicable, return `Err`.
+    ///
+    /// The context must be a syntactically valid Boolean formula (or an empty string).
     pub fn set_context(&mut self, context: String) -> Result<(), String> {
+        Self::check_context_syntax(&context)?;
         let context = Some(context);
         match &mut self.variant {
             StatPropertyType::FnInputEssentialContext(prop) => prop.context = context,
@@ -476,4 +483,156 @@ impl StatProperty {
     pub fn get_prop_data(&self) -> &StatPropertyType {
         &self.variant
     }
+
+    /// Get property's context (a Boolean formula), where applicable. Returns `None` if the
+    /// property has no context, or if the context is empty.
+    pub fn get_context(&self) -> Option<&str> {
+        let context = match &self.variant {
+            StatPropertyType::FnInputEssentialContext(prop) => prop.context.as_ref(),
+            StatPropertyType::FnInputMonotonicContext(prop) => prop.context.as_ref(),
+            StatPropertyType::RegulationEssentialContext(prop) => prop.context.as_ref(),
+            StatPropertyType::RegulationMonotonicContext(prop) => prop.context.as_ref(),
+            _ => None,
+        };
+        context.map(|c| c.as_str()).filter(|c| !c.trim().is_empty())
+    }
+}
+
+/// Validating contexts of static properties.
+impl StatProperty {
+    /// Check that a `context` string is a syntactically valid Boolean formula. The formula can
+    /// use constants `true`/`false`, variable identifiers, parentheses, and operators `!`, `&`,
+    /// `|`, `^`, `=>` and `<=>`. An empty string is also valid (meaning there is no context).
+    ///
+    /// Only the syntax is checked here, see [StatProperty::get_context_tree] for a check that
+    /// also validates the variables against a `ModelState`.
+    pub fn check_context_syntax(context: &str) -> Result<(), String> {
+        if context.trim().is_empty() {
+            return Ok(());
+        }
+        let expression = BooleanExpression::try_from(context)
+            .map_err(|e| format!("Context `{context}` is not a valid Boolean formula: {e}"))?;
+        Self::check_context_expression(&expression)
+            .map_err(|e| format!("Context `{context}` is not a valid Boolean formula: {e}"))
+    }
+
+    /// **(internal)** Recursively check that the parsed context only uses valid identifiers and
+    /// supported operators.
+    fn check_context_expression(expression: &BooleanExpression) -> Result<(), String> {
+        match expression {
+            BooleanExpression::Const(_) => Ok(()),
+            BooleanExpression::Variable(name) => {
+                VarId::from_str(name)
+                    .map_err(|_| format!("`{name}` is not a valid identifier."))?;
+                Ok(())
+            }
+            BooleanExpression::Not(inner) => Self::check_context_expression(inner),
+            BooleanExpression::And(l, r)
+            | BooleanExpression::Or(l, r)
+            | BooleanExpression::Xor(l, r)
+            | BooleanExpression::Imp(l, r)
+            | BooleanExpression::Iff(l, r) => {
+                Self::check_context_expression(l)?;
+                Self::check_context_expression(r)
+            }
+            BooleanExpression::Cond(..) => {
+                Err("Conditional expressions are not supported.".to_string())
+            }
+        }
+    }
+
+    /// Parse the property's context into a syntactic tree, validating it against the given
+    /// `ModelState`. Returns `None` if the property has no (or empty) context.
+    ///
+    /// Contexts of regulation properties are formulas over network variables of the model.
+    /// Contexts of properties of uninterpreted functions are formulas over the function's
+    /// arguments `var0`, `var1`, ... (up to its arity). Function symbols are not allowed.
+    pub fn get_context_tree(&self, model: &ModelState) -> Result<Option<FnTree>, String> {
+        let Some(context) = self.get_context() else {
+            return Ok(None);
+        };
+        let tree = match &self.variant {
+            StatPropertyType::FnInputEssentialContext(FnInputEssential { target, .. })
+            | StatPropertyType::FnInputMonotonicContext(FnInputMonotonic { target, .. }) => {
+                let fn_id = target
+                    .as_ref()
+                    .ok_or("Context cannot be checked, target function is not specified.")?;
+                let uninterpreted_fn = model.get_uninterpreted_fn(fn_id)?;
+                FnTree::try_from_str(context, model, Some((fn_id, uninterpreted_fn)))?
+            }
+            _ => FnTree::try_from_str(context, model, None)?,
+        };
+        if !tree.collect_fn_symbols().is_empty() {
+            return Err(format!(
+                "Context `{context}` cannot contain uninterpreted functions."
+            ));
+        }
+        Ok(Some(tree))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+    use crate::sketchbook::properties::StatProperty;
+
+    #[test]
+    /// Test syntax checks of property contexts.
+    fn test_context_syntax() {
+        let mut property = StatProperty::default_regulation_essential_context();
+        assert!(property.set_context("a & !(b | c)".to_string()).is_ok());
+        assert!(property.set_context("a => (b <=> c)".to_string()).is_ok());
+        assert!(property.set_context("".to_string()).is_ok());
+        assert_eq!(property.get_context(), None);
+
+        assert!(property.set_context("a & ".to_string()).is_err());
+        assert!(property.set_context("a ? b : c".to_string()).is_err());
+        assert!(property.set_context("a + b".to_string()).is_err());
+        assert!(StatProperty::mk_regulation_monotonic_context(
+            "p",
+            None,
+            None,
+            Monotonicity::Activation,
+            "(a".to_string()
+        )
+        .is_err());
+
+        // property without context
+        let mut property = StatProperty::default_regulation_essential();
+        assert!(property.set_context("a".to_string()).is_err());
+    }
+
+    #[test]
+    /// Test validating property contexts against a model.
+    fn test_context_with_model() {
+        let mut model = ModelState::new_from_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model.add_uninterpreted_fn_by_str("f", "f", 2).unwrap();
+        model.add_uninterpreted_fn_by_str("g", "g", 1).unwrap();
+        let f = model.get_uninterpreted_fn_id("f").unwrap();
+        let g = model.get_uninterpreted_fn_id("g").unwrap();
+
+        let mut property = StatProperty::default_regulation_monotonic_context();
+        property.set_context("a & !b".to_string()).unwrap();
+        assert!(property.get_context_tree(&model).unwrap().is_some());
+        property.set_context("a & c".to_string()).unwrap();
+        assert!(property.get_context_tree(&model).is_err());
+        // function symbols are not allowed
+        assert!(property.set_context("a & f(a, b)".to_string()).is_err());
+
+        let mut property = StatProperty::mk_fn_input_essential_context(
+            "p",
+            Some(0),
+            Some(f),
+            Essentiality::True,
+            "var1".to_string(),
+        )
+        .unwrap();
+        assert!(property.get_context_tree(&model).unwrap().is_some());
+        property.set_context("a".to_string()).unwrap();
+        assert!(property.get_context_tree(&model).is_err());
+        // `g` only has a single argument
+        property.set_target_fn(g).unwrap();
+        property.set_context("var1".to_string()).unwrap();
+        assert!(property.get_context_tree(&model).is_err());
+    }
 }
@@ -5,6 +5,6 @@ mod _property_types;
 /// **(internal)** Definition and methods for `StatProperty`.
 mod _static_property;
 
-pub use _first_order_formula::FirstOrderFormula;
+pub use _first_order_formula::{FirstOrderFormula, FolTreeNode, Quantifier};
 pub use _property_types::*;
 pub use _static_property::StatProperty;
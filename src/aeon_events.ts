@@ -114,7 +114,7 @@ interface AeonState {
       /** VariableData (with updated `update_fn`) for a variable with modified update function. */
       variableUpdateFnChanged: Observable<VariableData>
       /** Set an expression of update function for variable with given ID. */
-      setVariableUpdateFn: (varId: string, newExpression: string) => void
+      setVariableUpdateFn: (varId: string, newExpression: string, addRegulations?: boolean) => void
 
       /** Uninterpreted function-related setter events: */
 
@@ -976,10 +976,12 @@ export const aeonState: AeonState = {
           payload: newId
         })
       },
-      setVariableUpdateFn (varId: string, newExpression: string): void {
+      setVariableUpdateFn (varId: string, newExpression: string, addRegulations: boolean = false): void {
         aeonEvents.emitAction({
           path: ['sketch', 'model', 'variable', varId, 'set_update_fn'],
-          payload: newExpression.toString()
+          payload: addRegulations
+            ? JSON.stringify({ expression: newExpression.toString(), add_regulations: true })
+            : newExpression.toString()
         })
       },
       addUninterpretedFn (uninterpretedFnId: string, arity: number, uninterpretedFnName: string = ''): void {